@@ -0,0 +1,114 @@
+//!
+//! The combined build artifact bundle.
+//!
+
+use std::collections::BTreeMap;
+
+///
+/// The combined build artifact bundle.
+///
+/// Gathers all artifacts of a single contract code segment under one serializable entity.
+/// The binary artifacts are hexadecimal-encoded without the `0x` prefix.
+///
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ArtifactBundle {
+    /// The contract path.
+    pub contract_path: String,
+    /// The code segment, if the target builds them separately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_segment: Option<String>,
+    /// The bytecode.
+    pub bytecode: String,
+    /// The bytecode hash, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytecode_hash: Option<String>,
+    /// The project metadata hash, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_hash: Option<String>,
+    /// The text assembly, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assembly: Option<String>,
+    /// The hash-to-full-path mapping of the contract factory dependencies.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub factory_dependencies: BTreeMap<String, String>,
+}
+
+impl ArtifactBundle {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        contract_path: String,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        bytecode: &[u8],
+    ) -> Self {
+        Self {
+            contract_path,
+            code_segment: code_segment.map(|code_segment| code_segment.to_string()),
+            bytecode: Self::encode_hex(bytecode),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Returns the bundle key, which consists of the contract path and the optional code segment.
+    ///
+    pub fn key(&self) -> String {
+        match self.code_segment {
+            Some(ref code_segment) => format!("{}:{code_segment}", self.contract_path),
+            None => self.contract_path.to_owned(),
+        }
+    }
+
+    ///
+    /// Encodes `data` into a hexadecimal string without the `0x` prefix.
+    ///
+    pub fn encode_hex(data: &[u8]) -> String {
+        data.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::eravm::context::build::Build as EraVMBuild;
+    use crate::evm::context::build::Build as EVMBuild;
+
+    use super::ArtifactBundle;
+
+    #[test]
+    pub fn check_artifact_bundle() {
+        assert_eq!(ArtifactBundle::encode_hex(&[0x00, 0x0a, 0xff]), "000aff");
+
+        let mut build = EraVMBuild::new_with_bytecode_hash(
+            vec![0xde, 0xad],
+            [0x11; era_compiler_common::BYTE_LENGTH_FIELD],
+            Some(vec![0x22]),
+            Some("nop".to_owned()),
+        );
+        build.factory_dependencies =
+            BTreeMap::from([("ab".to_owned(), "factory.sol:Factory".to_owned())]);
+        let bundle = build.into_bundle("test.sol:Test");
+        assert_eq!(bundle.key(), "test.sol:Test");
+        assert_eq!(bundle.code_segment, None);
+        assert_eq!(bundle.bytecode, "dead");
+        assert_eq!(bundle.bytecode_hash, Some("11".repeat(32)));
+        assert_eq!(bundle.metadata_hash.as_deref(), Some("22"));
+        assert_eq!(bundle.assembly.as_deref(), Some("nop"));
+        assert_eq!(bundle.factory_dependencies.len(), 1);
+
+        let bundle = EVMBuild::new(vec![0x60, 0x00], None)
+            .into_bundle("test.sol:Test", era_compiler_common::CodeSegment::Runtime);
+        assert_eq!(
+            bundle.key(),
+            format!(
+                "test.sol:Test:{}",
+                era_compiler_common::CodeSegment::Runtime
+            )
+        );
+        assert_eq!(bundle.bytecode, "6000");
+        assert_eq!(bundle.bytecode_hash, None);
+        assert_eq!(bundle.metadata_hash, None);
+    }
+}
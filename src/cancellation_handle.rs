@@ -0,0 +1,86 @@
+//!
+//! The compilation cancellation handle.
+//!
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::error::Error;
+
+///
+/// The compilation cancellation handle.
+///
+/// The handle is cheap to clone, and all clones share the same flag, so a host compiler may keep
+/// one of them and cancel the compilation from another thread.
+///
+#[derive(Debug, Default, Clone)]
+pub struct CancellationHandle {
+    /// The shared cancellation flag.
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationHandle {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Requests the cancellation.
+    ///
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    ///
+    /// Whether the cancellation has been requested.
+    ///
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+
+    ///
+    /// Returns an error if the cancellation has been requested before `stage`.
+    ///
+    pub fn check(&self, stage: &str) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            anyhow::bail!(Error::Cancelled {
+                stage: stage.to_owned(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+
+    use super::CancellationHandle;
+
+    #[test]
+    pub fn check_cancellation_handle() {
+        let handle = CancellationHandle::new();
+        let clone = handle.clone();
+        assert!(!handle.is_cancelled());
+        assert!(handle.check("optimization").is_ok());
+
+        clone.cancel();
+        assert!(handle.is_cancelled());
+        let error = handle.check("optimization").expect_err("Must be cancelled");
+        assert!(
+            matches!(
+                error.downcast_ref::<Error>(),
+                Some(Error::Cancelled { stage }) if stage == "optimization"
+            ),
+            "{error}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "compilation cancelled before optimization"
+        );
+    }
+}
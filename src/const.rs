@@ -4,3 +4,15 @@
 
 /// The LLVM framework version.
 pub const LLVM_VERSION: semver::Version = semver::Version::new(15, 0, 4);
+
+/// The maximum length in bytes of the memory range whose hash can be folded at compile time.
+pub const HASH_CONSTANT_FOLDING_LENGTH_LIMIT: u64 = 1024;
+
+/// The heap offset of the free memory pointer, as used by the Solidity and Yul allocator.
+pub const HEAP_FREE_POINTER_OFFSET: u64 = 0x40;
+
+/// The upper bound of the heap free memory pointer, beyond which the allocation reverts.
+pub const HEAP_ALLOCATION_LIMIT: u64 = u64::MAX;
+
+/// The maximum number of indexed topics in an event, as supported by `log0`..`log4`.
+pub const EVENT_TOPICS_LIMIT: usize = 4;
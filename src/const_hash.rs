@@ -0,0 +1,27 @@
+//!
+//! The compile-time hashing utilities.
+//!
+
+use zkevm_opcode_defs::sha2::Digest;
+
+///
+/// Computes the keccak256 hash of `input` at compile time.
+///
+/// The result is target-independent and matches the value computed by the `keccak256` instruction
+/// of both EraVM and EVM for the same input.
+///
+pub fn keccak256(input: &[u8]) -> [u8; era_compiler_common::BYTE_LENGTH_FIELD] {
+    match era_compiler_common::Hash::keccak256(input) {
+        era_compiler_common::Hash::Keccak256 { bytes, .. } => bytes,
+        _ => unreachable!("keccak256 always yields the keccak256 hash variant"),
+    }
+}
+
+///
+/// Computes the sha256 hash of `input` at compile time.
+///
+/// The result matches the value returned by the `sha256` precompile for the same input.
+///
+pub fn sha256(input: &[u8]) -> [u8; era_compiler_common::BYTE_LENGTH_FIELD] {
+    zkevm_opcode_defs::sha2::Sha256::digest(input).into()
+}
@@ -0,0 +1,90 @@
+//!
+//! The compile-time constant operand of an instruction.
+//!
+
+use num::ToPrimitive;
+
+///
+/// The compile-time constant operand of an instruction.
+///
+/// Wraps the optional constant value preserved by the front-end, and converts it to the narrower
+/// integer types, returning an error instead of truncating the value.
+///
+#[derive(Debug, Clone)]
+pub struct ConstOperand {
+    /// The operand name used in error messages.
+    pub name: &'static str,
+    /// The preserved constant value, if available.
+    pub value: Option<num::BigUint>,
+}
+
+impl ConstOperand {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: &'static str, value: Option<num::BigUint>) -> Self {
+        Self { name, value }
+    }
+
+    ///
+    /// Takes the operand at `index` from the `constants` vector of an instruction.
+    ///
+    pub fn take(name: &'static str, constants: &mut [Option<num::BigUint>], index: usize) -> Self {
+        Self::new(
+            name,
+            constants.get_mut(index).and_then(|option| option.take()),
+        )
+    }
+
+    ///
+    /// Returns the constant value, or an error if it is not known at compile time.
+    ///
+    pub fn into_big(self) -> anyhow::Result<num::BigUint> {
+        self.value
+            .ok_or_else(|| anyhow::anyhow!("{} is missing", self.name))
+    }
+
+    ///
+    /// Returns the constant value as a `u8`.
+    ///
+    pub fn to_u8(&self) -> anyhow::Result<u8> {
+        self.convert(8, ToPrimitive::to_u8)
+    }
+
+    ///
+    /// Returns the constant value as a `u16`.
+    ///
+    pub fn to_u16(&self) -> anyhow::Result<u16> {
+        self.convert(16, ToPrimitive::to_u16)
+    }
+
+    ///
+    /// Returns the constant value as a `u32`.
+    ///
+    pub fn to_u32(&self) -> anyhow::Result<u32> {
+        self.convert(32, ToPrimitive::to_u32)
+    }
+
+    ///
+    /// Returns the constant value as a `u64`.
+    ///
+    pub fn to_u64(&self) -> anyhow::Result<u64> {
+        self.convert(64, ToPrimitive::to_u64)
+    }
+
+    ///
+    /// Converts the constant value with `converter`, which fails if it exceeds `bit_length`.
+    ///
+    fn convert<T>(
+        &self,
+        bit_length: usize,
+        converter: fn(&num::BigUint) -> Option<T>,
+    ) -> anyhow::Result<T> {
+        let value = self
+            .value
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("{} is missing", self.name))?;
+        converter(value)
+            .ok_or_else(|| anyhow::anyhow!("{} must fit into {bit_length} bits", self.name))
+    }
+}
@@ -0,0 +1,108 @@
+//!
+//! The module-level constant bytes pool.
+//!
+
+use std::collections::HashMap;
+
+use crate::context::pointer::Pointer;
+use crate::context::traits::address_space::IAddressSpace;
+
+///
+/// The module-level constant bytes pool.
+///
+/// Deduplicates the constant byte arrays, e.g. revert strings, per address space, so each
+/// distinct constant is emitted as a single private global.
+///
+#[derive(Debug)]
+pub struct ConstantPool<'ctx, AS>
+where
+    AS: IAddressSpace
+        + Clone
+        + Copy
+        + PartialEq
+        + Eq
+        + std::hash::Hash
+        + Into<inkwell::AddressSpace>
+        + std::fmt::Debug,
+{
+    /// The interned constants.
+    constants: HashMap<(AS, Vec<u8>), Pointer<'ctx, AS>>,
+}
+
+impl<'ctx, AS> Default for ConstantPool<'ctx, AS>
+where
+    AS: IAddressSpace
+        + Clone
+        + Copy
+        + PartialEq
+        + Eq
+        + std::hash::Hash
+        + Into<inkwell::AddressSpace>
+        + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self {
+            constants: HashMap::new(),
+        }
+    }
+}
+
+impl<'ctx, AS> ConstantPool<'ctx, AS>
+where
+    AS: IAddressSpace
+        + Clone
+        + Copy
+        + PartialEq
+        + Eq
+        + std::hash::Hash
+        + Into<inkwell::AddressSpace>
+        + std::fmt::Debug,
+{
+    /// The constant global name prefix.
+    pub const GLOBAL_NAME_PREFIX: &'static str = "constant_bytes_";
+
+    ///
+    /// Returns the pointer to the constant `bytes` in `address_space`, declaring it on first use.
+    ///
+    pub fn intern(
+        &mut self,
+        llvm: &'ctx inkwell::context::Context,
+        module: &inkwell::module::Module<'ctx>,
+        bytes: &[u8],
+        address_space: AS,
+    ) -> Pointer<'ctx, AS> {
+        let key = (address_space, bytes.to_vec());
+        if let Some(pointer) = self.constants.get(&key) {
+            return *pointer;
+        }
+
+        let r#type = llvm.i8_type().array_type(bytes.len() as u32);
+        let global = module.add_global(
+            r#type,
+            Some(address_space.into()),
+            format!("{}{}", Self::GLOBAL_NAME_PREFIX, self.constants.len()).as_str(),
+        );
+        global.set_linkage(inkwell::module::Linkage::Private);
+        global.set_unnamed_addr(true);
+        global.set_constant(true);
+        global.set_initializer(&llvm.const_string(bytes, false));
+
+        let pointer = Pointer::new(r#type, address_space, global.as_pointer_value());
+        self.constants.insert(key, pointer);
+        pointer
+    }
+
+    ///
+    /// Returns the number of interned constants.
+    ///
+    pub fn len(&self) -> usize {
+        self.constants.len()
+    }
+
+    ///
+    /// Whether the pool is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.constants.is_empty()
+    }
+}
@@ -0,0 +1,96 @@
+//!
+//! The LLVM function EVM legacy assembly block builder.
+//!
+
+use crate::context::function::block::evmla_data::EVMLAData as BlockEVMLAData;
+use crate::context::function::block::key::Key as BlockKey;
+use crate::context::function::block::Block;
+use crate::context::function::evmla_data::EVMLAData;
+
+///
+/// The LLVM function EVM legacy assembly block builder.
+///
+/// Creates the function blocks, deduplicating them by the initial stack hash, and produces
+/// the function EVM legacy assembly data.
+///
+#[derive(Debug)]
+pub struct EVMLAFunctionBuilder<'ctx> {
+    /// The inner LLVM context.
+    llvm: &'ctx inkwell::context::Context,
+    /// The function the blocks are appended to.
+    function: inkwell::values::FunctionValue<'ctx>,
+    /// The function data being built.
+    data: EVMLAData<'ctx>,
+}
+
+impl<'ctx> EVMLAFunctionBuilder<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        llvm: &'ctx inkwell::context::Context,
+        function: inkwell::values::FunctionValue<'ctx>,
+        stack_size: usize,
+    ) -> Self {
+        Self {
+            llvm,
+            function,
+            data: EVMLAData::new(stack_size),
+        }
+    }
+
+    ///
+    /// Registers a block instance with `key` and initial `stack_hash`.
+    ///
+    /// If an instance with the same stack hash is already registered, it is returned instead.
+    ///
+    pub fn add_block(
+        &mut self,
+        key: BlockKey,
+        stack_hash: [u8; era_compiler_common::BYTE_LENGTH_FIELD],
+    ) -> Block<'ctx> {
+        if let Some(block) = self.data.blocks.get(&key).and_then(|blocks| {
+            blocks
+                .iter()
+                .find(|block| block.evm().stack_hashes.contains(&stack_hash))
+        }) {
+            return block.to_owned();
+        }
+
+        let inner = self.llvm.append_basic_block(
+            self.function,
+            format!("block_{key}/{}", self.instances(&key)).as_str(),
+        );
+        let mut block = Block::new(inner);
+        block.set_evmla_data(BlockEVMLAData::new(vec![stack_hash]));
+        self.data.insert_block(key, block.clone());
+        block
+    }
+
+    ///
+    /// Returns the block with the specified tag and initial stack pattern.
+    ///
+    /// See `EVMLAData::find_block` for details.
+    ///
+    pub fn find_block(
+        &self,
+        key: &BlockKey,
+        stack_hash: &[u8; era_compiler_common::BYTE_LENGTH_FIELD],
+    ) -> anyhow::Result<Block<'ctx>> {
+        self.data.find_block(key, stack_hash)
+    }
+
+    ///
+    /// Returns the number of registered instances of the block with `key`.
+    ///
+    pub fn instances(&self, key: &BlockKey) -> usize {
+        self.data.blocks.get(key).map_or(0, Vec::len)
+    }
+
+    ///
+    /// Returns the function EVM legacy assembly data.
+    ///
+    pub fn build(self) -> EVMLAData<'ctx> {
+        self.data
+    }
+}
@@ -42,4 +42,60 @@ impl<'ctx> EVMLAData<'ctx> {
             self.blocks.insert(key, vec![block]);
         }
     }
+
+    ///
+    /// Returns the block with the specified tag and initial stack pattern.
+    ///
+    /// If there is only one block, it is returned unconditionally.
+    /// On mismatch, the error lists the declared blocks or the candidate stack hashes.
+    ///
+    pub fn find_block(
+        &self,
+        key: &BlockKey,
+        stack_hash: &[u8; era_compiler_common::BYTE_LENGTH_FIELD],
+    ) -> anyhow::Result<Block<'ctx>> {
+        let blocks = self.blocks.get(key).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Undeclared function block {key}. Declared blocks: [{}]",
+                self.blocks
+                    .keys()
+                    .map(BlockKey::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })?;
+
+        if let [block] = blocks.as_slice() {
+            return Ok(block.to_owned());
+        }
+
+        blocks
+            .iter()
+            .find(|block| block.evm().stack_hashes.contains(stack_hash))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Function block {key} has no instance with stack hash {}. Candidates: [{}]",
+                    Self::format_stack_hash(stack_hash),
+                    blocks
+                        .iter()
+                        .flat_map(|block| block.evm().stack_hashes.iter())
+                        .map(Self::format_stack_hash)
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    ///
+    /// Formats `stack_hash` as a hexadecimal string.
+    ///
+    fn format_stack_hash(stack_hash: &[u8; era_compiler_common::BYTE_LENGTH_FIELD]) -> String {
+        let mut result = String::with_capacity(2 + stack_hash.len() * 2);
+        result.push_str("0x");
+        for byte in stack_hash.iter() {
+            result.push_str(format!("{byte:02x}").as_str());
+        }
+        result
+    }
 }
@@ -4,5 +4,6 @@
 
 pub mod block;
 pub mod declaration;
+pub mod evmla_builder;
 pub mod evmla_data;
 pub mod r#return;
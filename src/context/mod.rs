@@ -3,24 +3,38 @@
 //!
 
 pub mod attribute;
+pub mod const_operand;
+pub mod constant_pool;
 pub mod function;
 pub mod r#loop;
 pub mod pointer;
+pub mod return_data_copy_policy;
+pub mod selector_dispatch;
 pub mod traits;
 pub mod value;
 
+#[cfg(test)]
+mod tests;
+
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use inkwell::types::AsTypeRef;
 use inkwell::types::BasicType;
+use inkwell::values::AsValueRef;
 use inkwell::values::BasicValue;
 
 use crate::debug_config::DebugConfig;
 use crate::debug_info::DebugInfo;
+use crate::diagnostic::severity::Severity as DiagnosticSeverity;
+use crate::diagnostic::Diagnostic;
+use crate::optimizer::Optimizer;
 
+use self::attribute::Attribute;
 use self::function::declaration::Declaration as FunctionDeclaration;
 use self::pointer::Pointer;
 use self::r#loop::Loop;
+use self::return_data_copy_policy::ReturnDataCopyPolicy;
 use self::traits::address_space::IAddressSpace;
 use self::traits::evmla_data::IEVMLAData;
 use self::traits::evmla_function::IEVMLAFunction;
@@ -80,6 +94,18 @@ pub trait IContext<'ctx> {
     ///
     fn module(&self) -> &inkwell::module::Module<'ctx>;
 
+    ///
+    /// Serializes the current LLVM IR module to bitcode.
+    ///
+    fn write_bitcode(&self) -> inkwell::memory_buffer::MemoryBuffer {
+        self.module().write_bitcode_to_memory()
+    }
+
+    ///
+    /// Returns the optimizer reference.
+    ///
+    fn optimizer(&self) -> &Optimizer;
+
     ///
     /// Returns the debug config reference.
     ///
@@ -88,7 +114,36 @@ pub trait IContext<'ctx> {
     ///
     /// Returns the debug info reference.
     ///
-    fn debug_info(&self) -> &DebugInfo;
+    fn debug_info(&self) -> &DebugInfo<'ctx>;
+
+    ///
+    /// Returns the diagnostics collected during lowering.
+    ///
+    fn diagnostics(&self) -> &[Diagnostic];
+
+    ///
+    /// Appends a diagnostic to the collected ones.
+    ///
+    fn push_diagnostic(&mut self, diagnostic: Diagnostic);
+
+    ///
+    /// Reports a diagnostic attributed to the function being lowered, if any.
+    ///
+    fn diagnose(&mut self, severity: DiagnosticSeverity, code: &str, message: String) {
+        let function = self
+            .builder()
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .map(|function| function.get_name().to_string_lossy().to_string());
+        self.push_diagnostic(Diagnostic::new(severity, code, message).with_function(function));
+    }
+
+    ///
+    /// Reports a warning attributed to the function being lowered, if any.
+    ///
+    fn warn(&mut self, code: &str, message: String) {
+        self.diagnose(DiagnosticSeverity::Warning, code, message);
+    }
 
     ///
     /// Sets the code type.
@@ -186,7 +241,8 @@ pub trait IContext<'ctx> {
     ///
     /// Builds a stack load instruction.
     ///
-    /// Sets the alignment to 256 bits for the stack and 1 bit for the heap, parent, and child.
+    /// Sets the alignment defined by the address space, which is 256 bits for the stack and 1 byte
+    /// for the other address spaces by default.
     ///
     fn build_load(
         &self,
@@ -197,16 +253,10 @@ pub trait IContext<'ctx> {
             .builder()
             .build_load(pointer.r#type, pointer.value, name)?;
 
-        let alignment = if Self::AddressSpace::stack() == pointer.address_space {
-            era_compiler_common::BYTE_LENGTH_FIELD
-        } else {
-            era_compiler_common::BYTE_LENGTH_BYTE
-        };
-
         self.basic_block()
             .get_last_instruction()
             .expect("Always exists")
-            .set_alignment(alignment as u32)
+            .set_alignment(pointer.address_space.alignment())
             .map_err(|error| anyhow::anyhow!(error))?;
         Ok(value)
     }
@@ -214,7 +264,10 @@ pub trait IContext<'ctx> {
     ///
     /// Builds a stack store instruction.
     ///
-    /// Sets the alignment to 256 bits for the stack and 1 bit for the heap, parent, and child.
+    /// Sets the alignment defined by the address space, which is 256 bits for the stack and 1 byte
+    /// for the other address spaces by default.
+    ///
+    /// Returns an error if the address space is read-only.
     ///
     fn build_store<V>(
         &self,
@@ -224,16 +277,16 @@ pub trait IContext<'ctx> {
     where
         V: BasicValue<'ctx>,
     {
-        let instruction = self.builder().build_store(pointer.value, value)?;
-
-        let alignment = if Self::AddressSpace::stack() == pointer.address_space {
-            era_compiler_common::BYTE_LENGTH_FIELD
-        } else {
-            era_compiler_common::BYTE_LENGTH_BYTE
-        };
+        if !pointer.address_space.is_writable() {
+            anyhow::bail!(
+                "Storing to the read-only address space {:?}",
+                pointer.address_space
+            );
+        }
 
+        let instruction = self.builder().build_store(pointer.value, value)?;
         instruction
-            .set_alignment(alignment as u32)
+            .set_alignment(pointer.address_space.alignment())
             .map_err(|error| anyhow::anyhow!(error))?;
         Ok(())
     }
@@ -258,6 +311,79 @@ pub trait IContext<'ctx> {
         Ok(Pointer::new(element_type, pointer.address_space, value))
     }
 
+    ///
+    /// Returns the pointer to the constant `bytes` in `address_space`.
+    ///
+    /// Equal constants in the same address space share a single module-level global.
+    ///
+    fn intern_constant_bytes_in(
+        &mut self,
+        bytes: &[u8],
+        address_space: Self::AddressSpace,
+    ) -> Pointer<'ctx, Self::AddressSpace>;
+
+    ///
+    /// Returns the pointer to the constant `bytes` in the target constant data address space.
+    ///
+    /// See `IAddressSpace::constants` for the placement on each target.
+    ///
+    fn intern_constant_bytes(&mut self, bytes: &[u8]) -> Pointer<'ctx, Self::AddressSpace> {
+        self.intern_constant_bytes_in(bytes, Self::AddressSpace::constants())
+    }
+
+    ///
+    /// Returns the pointer to the free memory pointer slot in the heap.
+    ///
+    fn heap_free_pointer(&self) -> anyhow::Result<Pointer<'ctx, Self::AddressSpace>> {
+        Pointer::new_with_offset(
+            self,
+            Self::AddressSpace::heap(),
+            self.field_type(),
+            self.field_const(crate::r#const::HEAP_FREE_POINTER_OFFSET),
+            "heap_free_pointer",
+        )
+    }
+
+    ///
+    /// Allocates `size` bytes in the heap by bumping the free memory pointer.
+    ///
+    /// Returns the offset of the allocated range. Reverts if the new free memory pointer
+    /// overflows or exceeds `HEAP_ALLOCATION_LIMIT`, like the Solidity allocator does.
+    ///
+    fn allocate_heap(
+        &self,
+        size: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let free_pointer = self.heap_free_pointer()?;
+        let offset = self
+            .build_load(free_pointer, "heap_allocation_offset")?
+            .into_int_value();
+        let (offset_new, is_overflow) =
+            self.build_checked_add(offset, size, false, "heap_allocation_offset_new")?;
+        let is_limit_exceeded = self.builder().build_int_compare(
+            inkwell::IntPredicate::UGT,
+            offset_new,
+            self.field_const(crate::r#const::HEAP_ALLOCATION_LIMIT),
+            "heap_allocation_is_limit_exceeded",
+        )?;
+        let is_invalid = self.builder().build_or(
+            is_overflow,
+            is_limit_exceeded,
+            "heap_allocation_is_invalid",
+        )?;
+
+        let revert_block = self.append_basic_block("heap_allocation_revert_block");
+        let join_block = self.append_basic_block("heap_allocation_join_block");
+        self.build_conditional_branch(is_invalid, revert_block, join_block)?;
+
+        self.set_basic_block(revert_block);
+        self.build_empty_revert()?;
+
+        self.set_basic_block(join_block);
+        self.build_store(free_pointer, offset_new)?;
+        Ok(offset)
+    }
+
     ///
     /// Builds a conditional branch.
     ///
@@ -296,6 +422,240 @@ pub trait IContext<'ctx> {
         Ok(())
     }
 
+    ///
+    /// Builds a checked addition, returning the result and the overflow flag.
+    ///
+    fn build_checked_add(
+        &self,
+        operand_1: inkwell::values::IntValue<'ctx>,
+        operand_2: inkwell::values::IntValue<'ctx>,
+        is_signed: bool,
+        name: &str,
+    ) -> anyhow::Result<(
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    )> {
+        let intrinsic = if is_signed {
+            "llvm.sadd.with.overflow"
+        } else {
+            "llvm.uadd.with.overflow"
+        };
+        self.build_with_overflow(intrinsic, operand_1, operand_2, name)
+    }
+
+    ///
+    /// Builds a checked subtraction, returning the result and the overflow flag.
+    ///
+    fn build_checked_sub(
+        &self,
+        operand_1: inkwell::values::IntValue<'ctx>,
+        operand_2: inkwell::values::IntValue<'ctx>,
+        is_signed: bool,
+        name: &str,
+    ) -> anyhow::Result<(
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    )> {
+        let intrinsic = if is_signed {
+            "llvm.ssub.with.overflow"
+        } else {
+            "llvm.usub.with.overflow"
+        };
+        self.build_with_overflow(intrinsic, operand_1, operand_2, name)
+    }
+
+    ///
+    /// Builds a checked multiplication, returning the result and the overflow flag.
+    ///
+    fn build_checked_mul(
+        &self,
+        operand_1: inkwell::values::IntValue<'ctx>,
+        operand_2: inkwell::values::IntValue<'ctx>,
+        is_signed: bool,
+        name: &str,
+    ) -> anyhow::Result<(
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    )> {
+        let intrinsic = if is_signed {
+            "llvm.smul.with.overflow"
+        } else {
+            "llvm.umul.with.overflow"
+        };
+        self.build_with_overflow(intrinsic, operand_1, operand_2, name)
+    }
+
+    ///
+    /// Declares the LLVM intrinsic `name` with the function type `r#type`.
+    ///
+    /// Allows using the target intrinsics which are not wrapped by the context yet. Overloaded
+    /// intrinsics are selected by the parameter types. The declaration is added to the module once
+    /// and reused by the subsequent calls.
+    ///
+    /// Returns an error if the intrinsic does not exist or its type differs from `r#type`.
+    ///
+    fn declare_intrinsic(
+        &self,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+    ) -> anyhow::Result<FunctionDeclaration<'ctx>> {
+        let intrinsic = inkwell::intrinsics::Intrinsic::find(name)
+            .ok_or_else(|| anyhow::anyhow!("Intrinsic function `{name}` does not exist"))?;
+        let overloaded_types = if intrinsic.is_overloaded() {
+            r#type.get_param_types()
+        } else {
+            vec![]
+        };
+        let value = intrinsic
+            .get_declaration(self.module(), overloaded_types.as_slice())
+            .ok_or_else(|| anyhow::anyhow!("Intrinsic function `{name}` declaration error"))?;
+        if value.get_type() != r#type {
+            anyhow::bail!(
+                "Intrinsic function `{name}` has type `{}`, but `{}` is requested",
+                value.get_type().print_to_string(),
+                r#type.print_to_string(),
+            );
+        }
+        Ok(FunctionDeclaration::new(r#type, value))
+    }
+
+    ///
+    /// Builds a call to the `intrinsic` from the `llvm.*.with.overflow` family, overloaded for the
+    /// operand type, returning the result and the overflow flag.
+    ///
+    fn build_with_overflow(
+        &self,
+        intrinsic: &str,
+        operand_1: inkwell::values::IntValue<'ctx>,
+        operand_2: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<(
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    )> {
+        let function = inkwell::intrinsics::Intrinsic::find(intrinsic)
+            .and_then(|intrinsic| {
+                intrinsic
+                    .get_declaration(self.module(), &[operand_1.get_type().as_basic_type_enum()])
+            })
+            .ok_or_else(|| anyhow::anyhow!("Intrinsic function `{intrinsic}` declaration error"))?;
+
+        let result = self
+            .builder()
+            .build_call(
+                function,
+                &[operand_1.into(), operand_2.into()],
+                format!("{name}_with_overflow").as_str(),
+            )?
+            .try_as_basic_value()
+            .left()
+            .expect("Always exists")
+            .into_struct_value();
+        let value = self
+            .builder()
+            .build_extract_value(result, 0, format!("{name}_value").as_str())?
+            .into_int_value();
+        let is_overflow = self
+            .builder()
+            .build_extract_value(result, 1, format!("{name}_is_overflow").as_str())?
+            .into_int_value();
+        Ok((value, is_overflow))
+    }
+
+    ///
+    /// Builds a PHI node with the `incoming` values and their predecessor blocks.
+    ///
+    fn build_phi<T>(
+        &self,
+        r#type: T,
+        incoming: &[(
+            inkwell::values::BasicValueEnum<'ctx>,
+            inkwell::basic_block::BasicBlock<'ctx>,
+        )],
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+    where
+        T: BasicType<'ctx>,
+    {
+        let phi = self.builder().build_phi(r#type, name)?;
+        let incoming: Vec<(
+            &dyn BasicValue<'ctx>,
+            inkwell::basic_block::BasicBlock<'ctx>,
+        )> = incoming
+            .iter()
+            .map(|(value, block)| (value as &dyn BasicValue<'ctx>, *block))
+            .collect();
+        phi.add_incoming(incoming.as_slice());
+        Ok(phi.as_basic_value())
+    }
+
+    ///
+    /// Merges the `incoming` values computed in different blocks.
+    ///
+    /// Appends a join block, terminates each of the incoming blocks with a branch to it, and
+    /// builds a PHI node there. The incoming blocks must be the unterminated blocks the values
+    /// are available at. The join block is set as the current one.
+    ///
+    fn merge_values<T>(
+        &self,
+        r#type: T,
+        incoming: &[(
+            inkwell::values::BasicValueEnum<'ctx>,
+            inkwell::basic_block::BasicBlock<'ctx>,
+        )],
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+    where
+        T: BasicType<'ctx>,
+    {
+        let join_block = self.append_basic_block(format!("{name}_join_block").as_str());
+        for (_value, block) in incoming.iter() {
+            self.set_basic_block(*block);
+            self.build_unconditional_branch(join_block)?;
+        }
+
+        self.set_basic_block(join_block);
+        self.build_phi(r#type, incoming, name)
+    }
+
+    ///
+    /// Builds a switch.
+    ///
+    /// Checks if there are no other terminators in the block.
+    ///
+    /// If `is_jump_table_allowed` is unset, the current function is marked with
+    /// `"no-jump-tables"="true"`, so the switches in it are lowered to compare trees, which is
+    /// useful for sparse cases. The attribute is function-wide and is never cleared, so the
+    /// front-end setting is preserved.
+    ///
+    fn build_switch(
+        &self,
+        scrutinee: inkwell::values::IntValue<'ctx>,
+        default_block: inkwell::basic_block::BasicBlock<'ctx>,
+        cases: &[(
+            inkwell::values::IntValue<'ctx>,
+            inkwell::basic_block::BasicBlock<'ctx>,
+        )],
+        is_jump_table_allowed: bool,
+    ) -> anyhow::Result<()> {
+        if self.basic_block().get_terminator().is_some() {
+            return Ok(());
+        }
+
+        if !is_jump_table_allowed {
+            let function = self.basic_block().get_parent().expect("Always exists");
+            function.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                self.llvm()
+                    .create_string_attribute("no-jump-tables", "true"),
+            );
+        }
+
+        self.builder()
+            .build_switch(scrutinee, default_block, cases)?;
+        Ok(())
+    }
+
     ///
     /// Builds a call.
     ///
@@ -316,6 +676,20 @@ pub trait IContext<'ctx> {
         name: &str,
     ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>;
 
+    ///
+    /// Builds a call with the call site `attributes`, e.g. `AlwaysInline`, `NoInline`, or `Cold`.
+    ///
+    /// Allows controlling the inlining of individual call sites, whereas the function attributes
+    /// affect all of them. Returns an error if the call site is both always and never inlined.
+    ///
+    fn build_call_with_attributes(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: &[inkwell::values::BasicValueEnum<'ctx>],
+        attributes: &[Attribute],
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>;
+
     ///
     /// Builds an invoke.
     ///
@@ -333,6 +707,8 @@ pub trait IContext<'ctx> {
     ///
     /// Sets the alignment to `1`, since all non-stack memory pages have such alignment.
     ///
+    /// Returns an error if the copy between the address spaces is not allowed.
+    ///
     fn build_memcpy(
         &self,
         function: FunctionDeclaration<'ctx>,
@@ -341,6 +717,17 @@ pub trait IContext<'ctx> {
         size: inkwell::values::IntValue<'ctx>,
         name: &str,
     ) -> anyhow::Result<()> {
+        if !destination
+            .address_space
+            .is_copy_legal_from(source.address_space)
+        {
+            anyhow::bail!(
+                "Copying from the address space {:?} to {:?} is not allowed",
+                source.address_space,
+                destination.address_space,
+            );
+        }
+
         let call_site_value = self.builder().build_indirect_call(
             function.r#type,
             function.value.as_global_value().as_pointer_value(),
@@ -358,6 +745,74 @@ pub trait IContext<'ctx> {
         Ok(())
     }
 
+    ///
+    /// Builds a return data copy with the out-of-bounds behavior defined by `policy`.
+    ///
+    /// With the revert policy, the copy reverts if `source_offset + size` exceeds
+    /// `return_data_size`. With the clamp policy, the copy size is set to
+    /// `min(size, return_data_size)`.
+    ///
+    fn build_bounded_returndata_copy(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        destination: Pointer<'ctx, Self::AddressSpace>,
+        source: Pointer<'ctx, Self::AddressSpace>,
+        source_offset: inkwell::values::IntValue<'ctx>,
+        size: inkwell::values::IntValue<'ctx>,
+        return_data_size: inkwell::values::IntValue<'ctx>,
+        policy: ReturnDataCopyPolicy,
+        name: &str,
+    ) -> anyhow::Result<()> {
+        let size = match policy {
+            ReturnDataCopyPolicy::Revert => {
+                let error_block = self.append_basic_block(format!("{name}_error_block").as_str());
+                let join_block = self.append_basic_block(format!("{name}_join_block").as_str());
+
+                let copy_slice_end = self.builder().build_int_add(
+                    source_offset,
+                    size,
+                    format!("{name}_slice_end").as_str(),
+                )?;
+                let is_copy_out_of_bounds = self.builder().build_int_compare(
+                    inkwell::IntPredicate::UGT,
+                    copy_slice_end,
+                    return_data_size,
+                    format!("{name}_is_out_of_bounds").as_str(),
+                )?;
+                self.build_conditional_branch(is_copy_out_of_bounds, error_block, join_block)?;
+
+                self.set_basic_block(error_block);
+                self.build_empty_revert()?;
+
+                self.set_basic_block(join_block);
+                size
+            }
+            ReturnDataCopyPolicy::Clamp => {
+                let is_return_data_size_lesser = self.builder().build_int_compare(
+                    inkwell::IntPredicate::ULT,
+                    return_data_size,
+                    size,
+                    format!("{name}_is_return_data_size_lesser").as_str(),
+                )?;
+                self.builder()
+                    .build_select(
+                        is_return_data_size_lesser,
+                        return_data_size,
+                        size,
+                        format!("{name}_min_size").as_str(),
+                    )?
+                    .into_int_value()
+            }
+        };
+
+        self.build_memcpy(function, destination, source, size, name)
+    }
+
+    ///
+    /// Builds a revert with empty data, terminating the current block.
+    ///
+    fn build_empty_revert(&self) -> anyhow::Result<()>;
+
     ///
     /// Builds a return.
     ///
@@ -386,6 +841,62 @@ pub trait IContext<'ctx> {
         Ok(())
     }
 
+    ///
+    /// Checks that every basic block of the module is terminated.
+    ///
+    /// Returns a single error listing all unterminated blocks grouped by function, which is more
+    /// actionable than the LLVM verifier message.
+    ///
+    fn check_terminators(&self) -> anyhow::Result<()> {
+        let mut unterminated = Vec::new();
+        for function in self.module().get_functions() {
+            let blocks: Vec<String> = function
+                .get_basic_blocks()
+                .into_iter()
+                .filter(|block| block.get_terminator().is_none())
+                .map(|block| format!("`{}`", block.get_name().to_string_lossy()))
+                .collect();
+            if !blocks.is_empty() {
+                unterminated.push(format!(
+                    "function `{}`: {}",
+                    function.get_name().to_string_lossy(),
+                    blocks.join(", ")
+                ));
+            }
+        }
+        if !unterminated.is_empty() {
+            anyhow::bail!(
+                "basic blocks without terminators:\n{}",
+                unterminated.join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Sets the source location of the instructions built next in the current function.
+    ///
+    /// The location is reset when another function is activated.
+    ///
+    fn set_source_location(&self, file: &str, line: u32, column: u32) {
+        let function = self.basic_block().get_parent().expect("Always exists");
+        self.debug_info().set_source_location(
+            self.llvm(),
+            self.builder(),
+            function,
+            file,
+            line,
+            column,
+        );
+    }
+
+    ///
+    /// Finalizes the debug info. Must be called after the last source location is set.
+    ///
+    fn finalize_debug_info(&self) {
+        self.debug_info().finalize();
+    }
+
     ///
     /// Returns a boolean type constant.
     ///
@@ -417,22 +928,237 @@ pub trait IContext<'ctx> {
     ///
     /// Returns a field type constant from a decimal string.
     ///
+    /// # Panics
+    /// If `value` is not a valid decimal field constant.
+    ///
     fn field_const_str_dec(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
+        self.try_field_const_str_dec(value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    ///
+    /// Returns a field type constant from a hexadecimal string.
+    ///
+    /// # Panics
+    /// If `value` is not a valid hexadecimal field constant.
+    ///
+    fn field_const_str_hex(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
+        self.try_field_const_str_hex(value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    ///
+    /// Returns a field type constant from a decimal string.
+    ///
+    fn try_field_const_str_dec(
+        &self,
+        value: &str,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
         self.field_type()
             .const_int_from_string(value, inkwell::types::StringRadix::Decimal)
-            .unwrap_or_else(|| panic!("Invalid string constant `{value}`"))
+            .ok_or_else(|| anyhow::anyhow!("Invalid string constant `{value}`"))
     }
 
     ///
     /// Returns a field type constant from a hexadecimal string.
     ///
-    fn field_const_str_hex(&self, value: &str) -> inkwell::values::IntValue<'ctx> {
+    fn try_field_const_str_hex(
+        &self,
+        value: &str,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
         self.field_type()
             .const_int_from_string(
                 value.strip_prefix("0x").unwrap_or(value),
                 inkwell::types::StringRadix::Hexadecimal,
             )
-            .unwrap_or_else(|| panic!("Invalid string constant `{value}`"))
+            .ok_or_else(|| anyhow::anyhow!("Invalid string constant `{value}`"))
+    }
+
+    ///
+    /// Returns a field type constant from a big integer.
+    ///
+    /// Returns an error if `value` does not fit into the field instead of truncating it.
+    ///
+    fn field_const_big(
+        &self,
+        value: &num::BigUint,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        if value.bits() > era_compiler_common::BIT_LENGTH_FIELD as u64 {
+            anyhow::bail!(
+                "Constant `{value}` does not fit into {} bits",
+                era_compiler_common::BIT_LENGTH_FIELD
+            );
+        }
+        self.try_field_const_str_hex(value.to_str_radix(16).as_str())
+    }
+
+    ///
+    /// Returns the keccak256 hash of `length` bytes at `offset` in `address_space`, if the whole
+    /// range has been written with constants earlier in the current basic block.
+    ///
+    /// Only the field-sized stores aligned to `offset` are used as the input. Their addresses are
+    /// resolved with `constant_pointer_address`. Returns `None` if the folding is disabled, the
+    /// operands are not constant, or the range may have been written by a call, a store with an
+    /// unresolved pointer or a non-constant value, or a partial store.
+    ///
+    fn fold_constant_hash(
+        &self,
+        address_space: Self::AddressSpace,
+        offset: inkwell::values::IntValue<'ctx>,
+        length: inkwell::values::IntValue<'ctx>,
+    ) -> Option<inkwell::values::IntValue<'ctx>> {
+        if !self
+            .optimizer()
+            .settings()
+            .is_hash_constant_folding_enabled()
+        {
+            return None;
+        }
+
+        let offset = offset.get_zero_extended_constant()?;
+        let length = length.get_zero_extended_constant()?;
+        let field_size = era_compiler_common::BYTE_LENGTH_FIELD as u64;
+        if length == 0
+            || length % field_size != 0
+            || length > crate::r#const::HASH_CONSTANT_FOLDING_LENGTH_LIMIT
+        {
+            return None;
+        }
+
+        let range_end = offset.checked_add(length)?;
+        let pointer_type = self.ptr_type(address_space.into());
+
+        let mut words = vec![None; (length / field_size) as usize];
+        let mut instruction = self.basic_block().get_last_instruction();
+        while let Some(current) = instruction {
+            if words.iter().all(Option::is_some) {
+                break;
+            }
+            instruction = current.get_previous_instruction();
+
+            match current.get_opcode() {
+                inkwell::values::InstructionOpcode::Store => {}
+                inkwell::values::InstructionOpcode::Call
+                | inkwell::values::InstructionOpcode::Invoke
+                | inkwell::values::InstructionOpcode::AtomicRMW
+                | inkwell::values::InstructionOpcode::AtomicCmpXchg => return None,
+                _ => continue,
+            }
+
+            let pointer = current.get_operand(1)?.left()?.into_pointer_value();
+            if pointer.get_type() != pointer_type {
+                continue;
+            }
+            let value = match current.get_operand(0)?.left()? {
+                inkwell::values::BasicValueEnum::IntValue(value) => value,
+                _ => return None,
+            };
+            let size = value.get_type().get_bit_width().div_ceil(8) as u64;
+            if size > field_size {
+                return None;
+            }
+            let address = self.constant_pointer_address(pointer)?;
+            if address.checked_add(size)? <= offset || address >= range_end {
+                continue;
+            }
+
+            let first_index = (address.max(offset) - offset) / field_size;
+            let last_index = ((address + size).min(range_end) - offset - 1) / field_size;
+            if (first_index..=last_index).all(|index| words[index as usize].is_some()) {
+                continue;
+            }
+            let is_aligned_word = value.get_type() == self.field_type()
+                && address >= offset
+                && (address - offset) % field_size == 0;
+            if !is_aligned_word || !value.is_constant_int() {
+                return None;
+            }
+            words[first_index as usize] = Some(value);
+        }
+
+        let mut input = Vec::with_capacity(length as usize);
+        for word in words.into_iter() {
+            input.extend(self.constant_field_bytes(word?)?);
+        }
+        let hash = crate::const_hash::keccak256(input.as_slice());
+        self.field_const_big(&num::BigUint::from_bytes_be(hash.as_slice()))
+            .ok()
+    }
+
+    ///
+    /// Returns the address of the constant `pointer`.
+    ///
+    /// The pointer operand is walked through the integer-to-pointer casts and the byte offsets
+    /// applied by `Pointer::with_offset_checked`. Returns `None` for any other pointer.
+    ///
+    fn constant_pointer_address(
+        &self,
+        pointer: inkwell::values::PointerValue<'ctx>,
+    ) -> Option<u64> {
+        if pointer.is_null() {
+            return Some(0);
+        }
+
+        let value = pointer.as_value_ref();
+        unsafe {
+            if inkwell::llvm_sys::core::LLVMIsAConstantExpr(value).is_null() {
+                return None;
+            }
+            match inkwell::llvm_sys::core::LLVMGetConstOpcode(value) {
+                inkwell::llvm_sys::LLVMOpcode::LLVMIntToPtr => inkwell::values::IntValue::new(
+                    inkwell::llvm_sys::core::LLVMGetOperand(value, 0),
+                )
+                .get_zero_extended_constant(),
+                inkwell::llvm_sys::LLVMOpcode::LLVMGetElementPtr => {
+                    if inkwell::llvm_sys::core::LLVMGetNumOperands(value) != 2
+                        || inkwell::llvm_sys::core::LLVMGetGEPSourceElementType(value)
+                            != self.byte_type().as_type_ref()
+                    {
+                        return None;
+                    }
+                    let base = inkwell::values::PointerValue::new(
+                        inkwell::llvm_sys::core::LLVMGetOperand(value, 0),
+                    );
+                    let offset = inkwell::values::IntValue::new(
+                        inkwell::llvm_sys::core::LLVMGetOperand(value, 1),
+                    );
+                    self.constant_pointer_address(base)?
+                        .checked_add(offset.get_zero_extended_constant()?)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    ///
+    /// Returns the big-endian bytes of the constant field `value`.
+    ///
+    /// The value must be a constant integer, so the shifts are folded by the builder instead of
+    /// being inserted. The value is split into 64-bit limbs, as the constant integer API cannot
+    /// read the wider integers at once.
+    ///
+    fn constant_field_bytes(&self, value: inkwell::values::IntValue<'ctx>) -> Option<Vec<u8>> {
+        let limb_type = self.integer_type(era_compiler_common::BIT_LENGTH_X64);
+        let mut bytes = Vec::with_capacity(era_compiler_common::BYTE_LENGTH_FIELD);
+        for shift in (0..era_compiler_common::BIT_LENGTH_FIELD)
+            .step_by(era_compiler_common::BIT_LENGTH_X64)
+            .rev()
+        {
+            let limb = self
+                .builder()
+                .build_right_shift(
+                    value,
+                    self.field_const(shift as u64),
+                    false,
+                    "constant_limb",
+                )
+                .ok()?;
+            let limb = limb
+                .const_truncate(limb_type)
+                .get_zero_extended_constant()?;
+            bytes.extend(limb.to_be_bytes());
+        }
+        Some(bytes)
     }
 
     ///
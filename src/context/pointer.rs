@@ -83,11 +83,9 @@ where
         C: IContext<'ctx>,
         T: BasicType<'ctx>,
     {
-        assert_ne!(
-            address_space,
-            AS::stack(),
-            "Stack pointers cannot be addressed"
-        );
+        if address_space == AS::stack() {
+            anyhow::bail!("`{name}`: stack pointers cannot be created from integer offsets");
+        }
 
         let value = context.builder().build_int_to_ptr(
             offset,
@@ -111,6 +109,99 @@ where
         }
     }
 
+    ///
+    /// Converts the pointer to a field-sized integer.
+    ///
+    /// Returns an error for stack pointers, whose addresses are not meaningful integers.
+    ///
+    pub fn to_int<C>(
+        self,
+        context: &C,
+        name: &str,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        C: IContext<'ctx>,
+    {
+        if self.address_space == AS::stack() {
+            anyhow::bail!("`{name}`: stack pointers cannot be converted to integers");
+        }
+
+        let value = context
+            .builder()
+            .build_ptr_to_int(self.value, context.field_type(), name)?;
+        Ok(value)
+    }
+
+    ///
+    /// Returns the pointer advanced by `offset` bytes within the same address space.
+    ///
+    /// Returns an error for stack pointers and offsets that are not field-sized integers.
+    ///
+    pub fn with_offset_checked<C>(
+        self,
+        context: &C,
+        offset: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<Self>
+    where
+        C: IContext<'ctx>,
+    {
+        if self.address_space == AS::stack() {
+            anyhow::bail!("`{name}`: stack pointers cannot be offset by bytes");
+        }
+        let bit_length = offset.get_type().get_bit_width() as usize;
+        if bit_length != era_compiler_common::BIT_LENGTH_FIELD {
+            anyhow::bail!(
+                "`{name}`: expected a {}-bit offset, found {bit_length}-bit",
+                era_compiler_common::BIT_LENGTH_FIELD,
+            );
+        }
+
+        let value = unsafe {
+            context
+                .builder()
+                .build_gep(context.byte_type(), self.value, &[offset], name)?
+        };
+        Ok(Self { value, ..self })
+    }
+
+    ///
+    /// Reinterprets the pointer in `address_space`.
+    ///
+    /// Returns an error if the cast is not legal, e.g. from or to the stack.
+    /// See `IAddressSpace::is_cast_legal_to` for the rules of each target.
+    ///
+    pub fn cast_address_space<C>(
+        self,
+        context: &C,
+        address_space: AS,
+        name: &str,
+    ) -> anyhow::Result<Self>
+    where
+        C: IContext<'ctx>,
+    {
+        if self.address_space == address_space {
+            return Ok(self);
+        }
+        if !self.address_space.is_cast_legal_to(address_space) {
+            anyhow::bail!(
+                "`{name}`: pointers cannot be cast from {:?} to {address_space:?}",
+                self.address_space,
+            );
+        }
+
+        let value = context.builder().build_address_space_cast(
+            self.value,
+            context.ptr_type(address_space.into()),
+            name,
+        )?;
+        Ok(Self {
+            address_space,
+            value,
+            ..self
+        })
+    }
+
     ///
     /// Converts the pointer to a value enum.
     ///
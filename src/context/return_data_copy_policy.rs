@@ -0,0 +1,14 @@
+//!
+//! The return data copy out-of-bounds policy.
+//!
+
+///
+/// The return data copy out-of-bounds policy.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnDataCopyPolicy {
+    /// Reverts with empty data if the copied slice exceeds the return data, as required by EVM.
+    Revert,
+    /// Clamps the copy size to the return data size, as done for EraVM call outputs.
+    Clamp,
+}
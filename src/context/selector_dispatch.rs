@@ -0,0 +1,288 @@
+//!
+//! The function selector dispatch.
+//!
+
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::IContext;
+
+///
+/// The function selector dispatch.
+///
+/// Emits a switch over the 4-byte function selector, calling a handler function for each known
+/// selector and falling back to the front-end code otherwise.
+///
+/// If the selectors are not dense enough, jump tables are disabled in the dispatching function,
+/// so the switch is lowered to a compare tree.
+///
+#[derive(Debug, Clone)]
+pub struct SelectorDispatch {
+    /// The selectors and their handler function names, in the declaration order.
+    handlers: Vec<(u32, String)>,
+    /// The minimal density in percent, below which jump tables are disabled.
+    density_threshold: u64,
+}
+
+impl Default for SelectorDispatch {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+            density_threshold: Self::DEFAULT_DENSITY_THRESHOLD,
+        }
+    }
+}
+
+impl SelectorDispatch {
+    /// The default density threshold in percent, equal to the LLVM one for size optimization.
+    pub const DEFAULT_DENSITY_THRESHOLD: u64 = 40;
+
+    /// The selector size in bytes.
+    pub const SELECTOR_SIZE: u64 = 4;
+
+    ///
+    /// Adds a handler function `function_name` for `selector`.
+    ///
+    /// The handler must take no arguments.
+    ///
+    pub fn with_handler(mut self, selector: u32, function_name: &str) -> Self {
+        self.handlers.push((selector, function_name.to_owned()));
+        self
+    }
+
+    ///
+    /// Sets the minimal density in percent, below which jump tables are disabled.
+    ///
+    pub fn with_density_threshold(mut self, density_threshold: u64) -> Self {
+        self.density_threshold = density_threshold;
+        self
+    }
+
+    ///
+    /// Returns the percentage of the selector range covered by the handlers.
+    ///
+    pub fn density(&self) -> u64 {
+        let minimum = self.handlers.iter().map(|(selector, _)| *selector).min();
+        let maximum = self.handlers.iter().map(|(selector, _)| *selector).max();
+        match (minimum, maximum) {
+            (Some(minimum), Some(maximum)) => {
+                let range = (maximum - minimum) as u64 + 1;
+                (self.handlers.len() as u64 * 100) / range
+            }
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Whether the switch is dense enough to be lowered to a jump table.
+    ///
+    /// If it is not, the dispatching function is marked with `"no-jump-tables"="true"`.
+    ///
+    pub fn is_dense(&self) -> bool {
+        !self.handlers.is_empty() && self.density() >= self.density_threshold
+    }
+
+    ///
+    /// Builds the dispatch in the current basic block.
+    ///
+    /// Calls with less than `SELECTOR_SIZE` bytes of calldata or with an unknown selector are
+    /// routed to `fallback_block`, and the handler calls are followed by a branch to `join_block`.
+    ///
+    /// Returns an error if a handler is not declared, takes arguments, or shares its selector
+    /// with another handler.
+    ///
+    pub fn build<'ctx, C>(
+        &self,
+        context: &C,
+        calldata_size: inkwell::values::IntValue<'ctx>,
+        calldata_word: inkwell::values::IntValue<'ctx>,
+        fallback_block: inkwell::basic_block::BasicBlock<'ctx>,
+        join_block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        C: IContext<'ctx>,
+    {
+        let mut handlers = Vec::with_capacity(self.handlers.len());
+        for (index, (selector, function_name)) in self.handlers.iter().enumerate() {
+            if let Some((_, duplicate)) = self.handlers[..index]
+                .iter()
+                .find(|(other, _)| other == selector)
+            {
+                anyhow::bail!(
+                    "Selector 0x{selector:08x} is dispatched to both `{duplicate}` and `{function_name}`"
+                );
+            }
+            let function = context
+                .module()
+                .get_function(function_name)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Selector handler `{function_name}` is not declared")
+                })?;
+            if function.count_params() != 0 {
+                anyhow::bail!("Selector handler `{function_name}` must not take arguments");
+            }
+            handlers.push((
+                *selector,
+                FunctionDeclaration::new(function.get_type(), function),
+            ));
+        }
+
+        let selector_block = context.append_basic_block("selector_dispatch_block");
+        let is_calldata_short = context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            calldata_size,
+            context.field_const(Self::SELECTOR_SIZE),
+            "selector_dispatch_is_calldata_short",
+        )?;
+        context.build_conditional_branch(is_calldata_short, fallback_block, selector_block)?;
+
+        context.set_basic_block(selector_block);
+        let selector = context.builder().build_right_shift(
+            calldata_word,
+            context.field_const(
+                (era_compiler_common::BIT_LENGTH_FIELD - era_compiler_common::BIT_LENGTH_X32)
+                    as u64,
+            ),
+            false,
+            "selector_dispatch_selector",
+        )?;
+        let mut cases = Vec::with_capacity(handlers.len());
+        let mut handler_blocks = Vec::with_capacity(handlers.len());
+        for (selector, declaration) in handlers.into_iter() {
+            let block =
+                context.append_basic_block(format!("selector_{selector:08x}_block").as_str());
+            cases.push((context.field_const(selector as u64), block));
+            handler_blocks.push((selector, declaration, block));
+        }
+        context.build_switch(selector, fallback_block, cases.as_slice(), self.is_dense())?;
+
+        for (selector, declaration, block) in handler_blocks.into_iter() {
+            context.set_basic_block(block);
+            context.build_invoke(
+                declaration,
+                &[],
+                format!("selector_{selector:08x}_call").as_str(),
+            )?;
+            context.build_unconditional_branch(join_block)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::IContext;
+    use crate::optimizer::settings::Settings as OptimizerSettings;
+    use crate::testing::file_check::FileCheck;
+
+    use super::SelectorDispatch;
+
+    #[test]
+    pub fn check_selector_dispatch() {
+        let llvm = inkwell::context::Context::create();
+
+        let dispatch = SelectorDispatch::default()
+            .with_handler(0x0a, "fun_a")
+            .with_handler(0x0c, "fun_b");
+        assert_eq!(dispatch.density(), 66);
+        assert!(dispatch.is_dense());
+        assert!(!dispatch.clone().with_density_threshold(80).is_dense());
+
+        let mut context = crate::testing::create_evm_context(
+            &llvm,
+            era_compiler_common::CodeSegment::Runtime,
+            OptimizerSettings::none(),
+        );
+        for name in ["fun_a", "fun_b"] {
+            crate::testing::add_function(&mut context, name, 0, 0).expect("Failed to add function");
+        }
+        let function = crate::testing::add_function(&mut context, "selector_dispatch", 2, 0)
+            .expect("Failed to add function");
+        let fallback_block = context.append_basic_block("fallback_block");
+        let join_block = context.append_basic_block("join_block");
+        dispatch
+            .build(
+                &context,
+                function
+                    .get_nth_param(0)
+                    .expect("Always exists")
+                    .into_int_value(),
+                function
+                    .get_nth_param(1)
+                    .expect("Always exists")
+                    .into_int_value(),
+                fallback_block,
+                join_block,
+            )
+            .expect("Failed to build");
+        let file_check = FileCheck::new()
+            .check("icmp ult i256 %0, 4")
+            .and_then(|file_check| file_check.check("lshr i256 %1, 224"))
+            .and_then(|file_check| file_check.check("switch i256"))
+            .and_then(|file_check| file_check.check_next("i256 10, label %selector_0000000a_block"))
+            .and_then(|file_check| file_check.check_next("i256 12, label %selector_0000000c_block"))
+            .and_then(|file_check| file_check.check("call void @fun_a()"))
+            .and_then(|file_check| file_check.check("call void @fun_b()"))
+            .expect("Always valid");
+        crate::testing::check_function(&context, "selector_dispatch", &file_check)
+            .expect("Must match");
+
+        let error = SelectorDispatch::default()
+            .with_handler(0x0a, "fun_a")
+            .with_handler(0x0a, "fun_b")
+            .build(
+                &context,
+                context.field_const(4),
+                context.field_const(0),
+                fallback_block,
+                join_block,
+            )
+            .expect_err("Must be rejected");
+        assert!(error.to_string().contains("0x0000000a"), "{error}");
+
+        for (density_threshold, file_check) in [
+            (
+                SelectorDispatch::DEFAULT_DENSITY_THRESHOLD,
+                FileCheck::new().check_not("no-jump-tables"),
+            ),
+            (
+                80,
+                FileCheck::new()
+                    .check("define void @selector_dispatch(i256 %0, i256 %1) #")
+                    .and_then(|file_check| file_check.check(r#""no-jump-tables"="true""#)),
+            ),
+        ] {
+            let mut context = crate::testing::create_evm_context(
+                &llvm,
+                era_compiler_common::CodeSegment::Runtime,
+                OptimizerSettings::none(),
+            );
+            for name in ["fun_a", "fun_b"] {
+                crate::testing::add_function(&mut context, name, 0, 0)
+                    .expect("Failed to add function");
+            }
+            let function = crate::testing::add_function(&mut context, "selector_dispatch", 2, 0)
+                .expect("Failed to add function");
+            let fallback_block = context.append_basic_block("fallback_block");
+            let join_block = context.append_basic_block("join_block");
+            dispatch
+                .clone()
+                .with_density_threshold(density_threshold)
+                .build(
+                    &context,
+                    function
+                        .get_nth_param(0)
+                        .expect("Always exists")
+                        .into_int_value(),
+                    function
+                        .get_nth_param(1)
+                        .expect("Always exists")
+                        .into_int_value(),
+                    fallback_block,
+                    join_block,
+                )
+                .expect("Failed to build");
+            crate::testing::check_module(&context, &file_check.expect("Always valid"))
+                .expect("Must match");
+        }
+    }
+}
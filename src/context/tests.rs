@@ -0,0 +1,423 @@
+//!
+//! The LLVM IR generator context trait tests.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::pointer::Pointer;
+use crate::context::traits::address_space::IAddressSpace;
+use crate::context::IContext;
+use crate::diagnostic::severity::Severity as DiagnosticSeverity;
+use crate::eravm::context::address_space::AddressSpace as EraVMAddressSpace;
+use crate::evm::context::address_space::AddressSpace as EVMAddressSpace;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::testing::file_check::FileCheck;
+
+#[test]
+pub fn check_try_field_const_str() {
+    let llvm = inkwell::context::Context::create();
+    let context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+
+    let value = context
+        .try_field_const_str_dec("255")
+        .expect("Always valid");
+    assert_eq!(value.get_zero_extended_constant(), Some(255));
+    assert_eq!(context.field_const_str_dec("255"), value);
+    for hex in ["ff", "0xff", "0xFF"] {
+        assert_eq!(
+            context.try_field_const_str_hex(hex).expect("Always valid"),
+            value,
+            "{hex}"
+        );
+    }
+
+    let error = context
+        .try_field_const_str_dec("12a")
+        .expect_err("Must be rejected")
+        .to_string();
+    assert_eq!(error, "Invalid string constant `12a`");
+    let error = context
+        .try_field_const_str_hex("0xxyz")
+        .expect_err("Must be rejected")
+        .to_string();
+    assert_eq!(error, "Invalid string constant `0xxyz`");
+}
+
+#[test]
+pub fn check_source_location() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+
+    let function = crate::testing::add_function(&mut context, "located", 2, 0)
+        .expect("Failed to add function");
+    context.set_source_location("test.sol", 12, 5);
+    context
+        .builder()
+        .build_int_add(
+            function
+                .get_nth_param(0)
+                .expect("Always exists")
+                .into_int_value(),
+            function
+                .get_nth_param(1)
+                .expect("Always exists")
+                .into_int_value(),
+            "located_sum",
+        )
+        .expect("Failed to build add");
+
+    let function = crate::testing::add_function(&mut context, "unlocated", 2, 0)
+        .expect("Failed to add function");
+    context
+        .builder()
+        .build_int_mul(
+            function
+                .get_nth_param(0)
+                .expect("Always exists")
+                .into_int_value(),
+            function
+                .get_nth_param(1)
+                .expect("Always exists")
+                .into_int_value(),
+            "unlocated_product",
+        )
+        .expect("Failed to build mul");
+    context.finalize_debug_info();
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: define void @located({{.*}}!dbg
+; CHECK: %located_sum = add i256 %0, %1, !dbg
+; CHECK: define void @unlocated(
+; CHECK-NOT: !dbg
+; CHECK: %unlocated_product = mul i256 %0, %1
+; CHECK-NOT: , !dbg
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+
+    let snapshot = crate::testing::snapshot_module(&context);
+    for expected in [
+        r#"!DIFile(filename: "test.sol""#,
+        r#"!DISubprogram(name: "located""#,
+        "!DILocation(line: 12, column: 5",
+    ] {
+        assert!(snapshot.contains(expected), "{expected}: {snapshot}");
+    }
+}
+
+#[test]
+pub fn check_diagnostics() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    assert!(context.diagnostics().is_empty());
+
+    context.warn("global", "outside of functions".to_owned());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    context.diagnose(
+        DiagnosticSeverity::Info,
+        "local",
+        "inside of the function".to_owned(),
+    );
+
+    let diagnostics = context.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(diagnostics[0].code, "global");
+    assert_eq!(diagnostics[0].function, None);
+    assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Info);
+    assert_eq!(diagnostics[1].code, "local");
+    assert_eq!(
+        diagnostics[1].function.as_deref(),
+        Some(crate::testing::FUNCTION_NAME)
+    );
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    context.warn("local", "inside of the function".to_owned());
+    assert_eq!(
+        context.diagnostics()[0].function.as_deref(),
+        Some(crate::testing::FUNCTION_NAME)
+    );
+}
+
+#[test]
+pub fn check_address_space_alignment() {
+    assert_eq!(
+        EraVMAddressSpace::Stack.alignment(),
+        era_compiler_common::BYTE_LENGTH_FIELD as u32
+    );
+    assert_eq!(
+        EraVMAddressSpace::Heap.alignment(),
+        era_compiler_common::BYTE_LENGTH_BYTE as u32
+    );
+    assert!(!EraVMAddressSpace::Code.is_writable());
+    assert!(!EraVMAddressSpace::Code.is_copy_legal_from(EraVMAddressSpace::Heap));
+    assert!(EraVMAddressSpace::Heap.is_copy_legal_from(EraVMAddressSpace::Code));
+    for address_space in [
+        EVMAddressSpace::Calldata,
+        EVMAddressSpace::ReturnData,
+        EVMAddressSpace::Code,
+    ] {
+        assert!(!address_space.is_writable(), "{address_space:?}");
+    }
+    assert!(EVMAddressSpace::Heap.is_writable());
+
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    let stack_pointer = context
+        .build_alloca(context.field_type(), "stack_pointer")
+        .expect("Always valid");
+    context
+        .build_store(stack_pointer, context.field_const(1))
+        .expect("Always valid");
+    let heap_pointer = Pointer::new_with_offset(
+        &context,
+        EraVMAddressSpace::Heap,
+        context.field_type(),
+        context.field_const(64),
+        "heap_pointer",
+    )
+    .expect("Always valid");
+    context
+        .build_store(heap_pointer, context.field_const(2))
+        .expect("Always valid");
+    context
+        .build_load(heap_pointer, "heap_value")
+        .expect("Always valid");
+    let code_pointer = Pointer::new_with_offset(
+        &context,
+        EraVMAddressSpace::Code,
+        context.field_type(),
+        context.field_const(0),
+        "code_pointer",
+    )
+    .expect("Always valid");
+    let error = context
+        .build_store(code_pointer, context.field_const(3))
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(error.contains("read-only"), "{error}");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %stack_pointer = alloca i256, align 32
+; CHECK-NEXT: store i256 1, ptr %stack_pointer, align 32
+; CHECK-NEXT: store i256 2, ptr addrspace(1) inttoptr (i256 64 to ptr addrspace(1)), align 1
+; CHECK-NEXT: %heap_value = load i256, ptr addrspace(1) inttoptr (i256 64 to ptr addrspace(1)), align 1
+; CHECK-NOT: store i256 3
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, crate::testing::FUNCTION_NAME, &file_check)
+        .expect("Must match");
+}
+
+#[test]
+pub fn check_build_switch() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    let function =
+        crate::testing::add_function(&mut context, "switch", 1, 0).expect("Failed to add function");
+    let scrutinee = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let entry_block = context.basic_block();
+    let case_one_block = context.append_basic_block("case_one");
+    let case_two_block = context.append_basic_block("case_two");
+    let default_block = context.append_basic_block("default");
+
+    context.set_basic_block(entry_block);
+    let cases = [
+        (context.field_const(1), case_one_block),
+        (context.field_const(2), case_two_block),
+    ];
+    context
+        .build_switch(scrutinee, default_block, &cases, true)
+        .expect("Always valid");
+    context
+        .build_switch(scrutinee, case_one_block, &cases, false)
+        .expect("Always valid");
+    for block in [case_one_block, case_two_block, default_block] {
+        context.set_basic_block(block);
+        context.build_unreachable().expect("Always valid");
+    }
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: switch i256 %0, label %default [
+; CHECK-NEXT: i256 1, label %case_one
+; CHECK-NEXT: i256 2, label %case_two
+; CHECK-NEXT: ]
+; CHECK-NOT: switch
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "switch", &file_check).expect("Must match");
+    assert!(!crate::testing::snapshot_module(&context).contains("no-jump-tables"));
+
+    let function = crate::testing::add_function(&mut context, "switch_sparse", 1, 0)
+        .expect("Failed to add function");
+    let scrutinee = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let default_block = context.append_basic_block("default");
+    context
+        .build_switch(scrutinee, default_block, &[], false)
+        .expect("Always valid");
+    context.set_basic_block(default_block);
+    context.build_unreachable().expect("Always valid");
+    assert_eq!(
+        function
+            .get_string_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                "no-jump-tables"
+            )
+            .map(|attribute| attribute.get_string_value().to_string_lossy().to_string()),
+        Some("true".to_owned())
+    );
+}
+
+#[test]
+pub fn check_merge_values() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    let function =
+        crate::testing::add_function(&mut context, "merge", 1, 0).expect("Failed to add function");
+    let condition = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let left_block = context.append_basic_block("left");
+    let right_block = context.append_basic_block("right");
+    let is_zero = context
+        .builder()
+        .build_int_compare(
+            inkwell::IntPredicate::EQ,
+            condition,
+            context.field_const(0),
+            "is_zero",
+        )
+        .expect("Always valid");
+    context
+        .build_conditional_branch(is_zero, left_block, right_block)
+        .expect("Always valid");
+
+    context.set_basic_block(left_block);
+    let left_value = context
+        .builder()
+        .build_int_add(condition, context.field_const(1), "left_value")
+        .expect("Always valid");
+    context.set_basic_block(right_block);
+    let right_value = context.field_const(2);
+
+    context
+        .merge_values(
+            context.field_type(),
+            &[
+                (left_value.as_basic_value_enum(), left_block),
+                (right_value.as_basic_value_enum(), right_block),
+            ],
+            "merged",
+        )
+        .expect("Always valid");
+    assert_eq!(
+        context.basic_block().get_name().to_string_lossy(),
+        "merged_join_block"
+    );
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: left:
+; CHECK-NEXT: %left_value = add i256 %0, 1
+; CHECK-NEXT: br label %merged_join_block
+; CHECK: right:
+; CHECK-NEXT: br label %merged_join_block
+; CHECK: merged_join_block:
+; CHECK-NEXT: %merged = phi i256 [ %left_value, %left ], [ 2, %right ]
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "merge", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_checked_arithmetic() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    let function = crate::testing::add_function(&mut context, "checked_arithmetic", 2, 0)
+        .expect("Failed to add function");
+    let operand_1 = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let operand_2 = function
+        .get_nth_param(1)
+        .expect("Always exists")
+        .into_int_value();
+
+    for is_signed in [false, true] {
+        let prefix = if is_signed { "signed" } else { "unsigned" };
+        context
+            .build_checked_add(
+                operand_1,
+                operand_2,
+                is_signed,
+                format!("{prefix}_add").as_str(),
+            )
+            .expect("Always valid");
+        context
+            .build_checked_sub(
+                operand_1,
+                operand_2,
+                is_signed,
+                format!("{prefix}_sub").as_str(),
+            )
+            .expect("Always valid");
+        context
+            .build_checked_mul(
+                operand_1,
+                operand_2,
+                is_signed,
+                format!("{prefix}_mul").as_str(),
+            )
+            .expect("Always valid");
+    }
+    let byte_operand = context.byte_type().const_int(255, false);
+    let (value, is_overflow) = context
+        .build_checked_add(byte_operand, byte_operand, false, "byte_add")
+        .expect("Always valid");
+    assert_eq!(value.get_type().get_bit_width(), 8);
+    assert_eq!(is_overflow.get_type().get_bit_width(), 1);
+
+    let mut checks = String::new();
+    for (sign, prefix) in [("u", "unsigned"), ("s", "signed")] {
+        for operation in ["add", "sub", "mul"] {
+            checks.push_str(
+                format!(
+                    r#"
+; CHECK: %{prefix}_{operation}_with_overflow = call {{ i256, i1 }} @llvm.{sign}{operation}.with.overflow.i256(i256 %0, i256 %1)
+; CHECK-NEXT: %{prefix}_{operation}_value = extractvalue {{ i256, i1 }} %{prefix}_{operation}_with_overflow, 0
+; CHECK-NEXT: %{prefix}_{operation}_is_overflow = extractvalue {{ i256, i1 }} %{prefix}_{operation}_with_overflow, 1"#
+                )
+                .as_str(),
+            );
+        }
+    }
+    let file_check =
+        FileCheck::parse(checks.as_str(), FileCheck::DEFAULT_PREFIX).expect("Always valid");
+    crate::testing::check_function(&context, "checked_arithmetic", &file_check)
+        .expect("Must match");
+}
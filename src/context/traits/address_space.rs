@@ -5,9 +5,59 @@
 ///
 /// The LLVM IR address space trait.
 ///
-pub trait IAddressSpace {
+/// Describes the memory model of a target, so the builder helpers do not have to know the
+/// address spaces of each target.
+///
+pub trait IAddressSpace: Copy + PartialEq {
     ///
     /// Returns the stack address space.
     ///
     fn stack() -> Self;
+
+    ///
+    /// Returns the heap address space.
+    ///
+    fn heap() -> Self;
+
+    ///
+    /// Returns the address space of the module-level constant data.
+    ///
+    fn constants() -> Self;
+
+    ///
+    /// Returns the alignment of the loads and stores in bytes.
+    ///
+    /// The stack is aligned to the field size, and the other address spaces are byte-aligned.
+    ///
+    fn alignment(self) -> u32 {
+        if self == Self::stack() {
+            era_compiler_common::BYTE_LENGTH_FIELD as u32
+        } else {
+            era_compiler_common::BYTE_LENGTH_BYTE as u32
+        }
+    }
+
+    ///
+    /// Whether the address space can be written to.
+    ///
+    fn is_writable(self) -> bool {
+        true
+    }
+
+    ///
+    /// Whether a pointer to `self` can be reinterpreted as a pointer to `target`.
+    ///
+    /// Stack pointers are never reinterpreted, and the other address spaces are compatible by
+    /// default.
+    ///
+    fn is_cast_legal_to(self, target: Self) -> bool {
+        self == target || (self != Self::stack() && target != Self::stack())
+    }
+
+    ///
+    /// Whether the memory can be copied from the `source` address space to `self`.
+    ///
+    fn is_copy_legal_from(self, _source: Self) -> bool {
+        self.is_writable()
+    }
 }
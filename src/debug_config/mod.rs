@@ -165,6 +165,65 @@ impl DebugConfig {
         Ok(())
     }
 
+    ///
+    /// Dumps the LLVM IR after a stage of the staged optimization pipeline.
+    ///
+    pub fn dump_llvm_ir_stage(
+        &self,
+        contract_path: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        module: &inkwell::module::Module,
+        stage_index: usize,
+        stage_name: &str,
+    ) -> anyhow::Result<()> {
+        let llvm_code = module.print_to_string().to_string();
+
+        let suffix = format!("stage{stage_index}_{stage_name}");
+
+        let mut file_path = self.output_directory.to_owned();
+        let full_file_name = Self::full_file_name(
+            contract_path,
+            code_segment,
+            Some(suffix.as_str()),
+            IRType::LLVM,
+        );
+        file_path.push(full_file_name);
+        std::fs::write(file_path, llvm_code)?;
+
+        Ok(())
+    }
+
+    ///
+    /// Dumps the timings of the staged optimization pipeline.
+    ///
+    pub fn dump_stage_timings(
+        &self,
+        contract_path: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        timings: &[(String, std::time::Duration)],
+    ) -> anyhow::Result<()> {
+        let text = timings
+            .iter()
+            .enumerate()
+            .map(|(index, (stage_name, duration))| {
+                format!("stage{index}_{stage_name}: {}ms", duration.as_millis())
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut file_path = self.output_directory.to_owned();
+        let mut full_file_name = Self::sanitize_filename_fragment(contract_path);
+        if let Some(code_segment) = code_segment {
+            full_file_name.push('.');
+            full_file_name.push_str(code_segment.to_string().as_str());
+        }
+        full_file_name.push_str(".stage_timings.txt");
+        file_path.push(full_file_name);
+        std::fs::write(file_path, text)?;
+
+        Ok(())
+    }
+
     ///
     /// Dumps the assembly.
     ///
@@ -2,6 +2,9 @@
 //! The LLVM debug information.
 //!
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use inkwell::debug_info::AsDIScope;
 use num::Zero;
 
@@ -13,6 +16,8 @@ pub struct DebugInfo<'ctx> {
     compile_unit: inkwell::debug_info::DICompileUnit<'ctx>,
     /// The debug info builder.
     builder: inkwell::debug_info::DebugInfoBuilder<'ctx>,
+    /// The source files created so far.
+    files: RefCell<HashMap<String, inkwell::debug_info::DIFile<'ctx>>>,
 }
 
 impl<'ctx> DebugInfo<'ctx> {
@@ -41,6 +46,7 @@ impl<'ctx> DebugInfo<'ctx> {
         Self {
             compile_unit,
             builder,
+            files: RefCell::new(HashMap::new()),
         }
     }
 
@@ -100,6 +106,63 @@ impl<'ctx> DebugInfo<'ctx> {
             .map_err(|error| anyhow::anyhow!("debug info: {error}"))
     }
 
+    ///
+    /// Sets the source location of the instructions built by `builder` next.
+    ///
+    /// If `function` has no subprogram yet, it is created in `file` at `line`.
+    ///
+    pub fn set_source_location(
+        &self,
+        llvm: &'ctx inkwell::context::Context,
+        builder: &inkwell::builder::Builder<'ctx>,
+        function: inkwell::values::FunctionValue<'ctx>,
+        file: &str,
+        line: u32,
+        column: u32,
+    ) {
+        let file = *self
+            .files
+            .borrow_mut()
+            .entry(file.to_owned())
+            .or_insert_with(|| self.builder.create_file(file, ""));
+
+        let subprogram = match function.get_subprogram() {
+            Some(subprogram) => subprogram,
+            None => {
+                let subroutine_type = self.builder.create_subroutine_type(
+                    file,
+                    None,
+                    &[],
+                    inkwell::debug_info::DIFlags::zero(),
+                );
+                let subprogram = self.builder.create_function(
+                    file.as_debug_info_scope(),
+                    function.get_name().to_string_lossy().as_ref(),
+                    None,
+                    file,
+                    line,
+                    subroutine_type,
+                    true,
+                    true,
+                    line,
+                    inkwell::debug_info::DIFlags::zero(),
+                    false,
+                );
+                function.set_subprogram(subprogram);
+                subprogram
+            }
+        };
+
+        let location = self.builder.create_debug_location(
+            llvm,
+            line,
+            column,
+            subprogram.as_debug_info_scope(),
+            None,
+        );
+        builder.set_current_debug_location(location);
+    }
+
     ///
     /// Finalizes the builder.
     ///
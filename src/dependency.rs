@@ -2,14 +2,115 @@
 //! The dependency trait.
 //!
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
 ///
 /// Implemented by entities managing project dependencies.
 ///
+/// Only `resolve_path` is required. The other single-item methods fail by default, so they only
+/// have to be implemented if the frontend uses them.
+///
+/// The batch methods resolve the items one by one by default. Managers backed by a remote or
+/// concurrent source should override them to look up all items at once, so multi-contract
+/// builds are not serialized on single lookups.
+///
 pub trait Dependency {
     ///
     /// Resolves a full contract path.
     ///
     fn resolve_path(&self, identifier: &str) -> anyhow::Result<String>;
+
+    ///
+    /// Resolves the bytecode hash of the contract with the full `path`.
+    ///
+    fn resolve_bytecode_hash(
+        &self,
+        path: &str,
+    ) -> anyhow::Result<[u8; era_compiler_common::BYTE_LENGTH_FIELD]> {
+        anyhow::bail!("bytecode hash of `{path}` cannot be resolved by the dependency manager")
+    }
+
+    ///
+    /// Resolves the address of the library with the full `path`.
+    ///
+    fn resolve_library(
+        &self,
+        path: &str,
+    ) -> anyhow::Result<[u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]> {
+        anyhow::bail!("library `{path}` cannot be resolved by the dependency manager")
+    }
+
+    ///
+    /// Resolves the full paths of the direct dependencies of the contract with the full `path`.
+    ///
+    fn resolve_dependencies(&self, path: &str) -> anyhow::Result<BTreeSet<String>> {
+        anyhow::bail!("dependencies of `{path}` cannot be resolved by the dependency manager")
+    }
+
+    ///
+    /// Resolves the full contract paths of `identifiers`.
+    ///
+    fn resolve_paths(&self, identifiers: &[&str]) -> anyhow::Result<BTreeMap<String, String>> {
+        identifiers
+            .iter()
+            .map(|identifier| Ok(((*identifier).to_owned(), self.resolve_path(identifier)?)))
+            .collect()
+    }
+
+    ///
+    /// Resolves the bytecode hashes of the contracts with the full `paths`.
+    ///
+    fn resolve_bytecode_hashes(
+        &self,
+        paths: &[&str],
+    ) -> anyhow::Result<BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_FIELD]>> {
+        paths
+            .iter()
+            .map(|path| Ok(((*path).to_owned(), self.resolve_bytecode_hash(path)?)))
+            .collect()
+    }
+
+    ///
+    /// Resolves the addresses of the libraries with the full `paths`.
+    ///
+    fn resolve_libraries(
+        &self,
+        paths: &[&str],
+    ) -> anyhow::Result<BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>> {
+        paths
+            .iter()
+            .map(|path| Ok(((*path).to_owned(), self.resolve_library(path)?)))
+            .collect()
+    }
+
+    ///
+    /// Resolves the full dependency graph reachable from the contracts with the full `paths`.
+    ///
+    /// Returns the direct dependencies of each reachable contract, including `paths` themselves.
+    /// Each reachable contract is passed to `resolve_dependencies` exactly once.
+    ///
+    fn resolve_dependency_graph(
+        &self,
+        paths: &[&str],
+    ) -> anyhow::Result<BTreeMap<String, BTreeSet<String>>> {
+        let mut graph = BTreeMap::new();
+        let mut queue: Vec<String> = paths.iter().map(|path| (*path).to_owned()).collect();
+        while let Some(path) = queue.pop() {
+            if graph.contains_key(path.as_str()) {
+                continue;
+            }
+            let dependencies = self.resolve_dependencies(path.as_str())?;
+            queue.extend(
+                dependencies
+                    .iter()
+                    .filter(|dependency| !graph.contains_key(dependency.as_str()))
+                    .cloned(),
+            );
+            graph.insert(path, dependencies);
+        }
+        Ok(graph)
+    }
 }
 
 ///
@@ -22,4 +123,46 @@ impl Dependency for DummyDependency {
     fn resolve_path(&self, _identifier: &str) -> anyhow::Result<String> {
         Ok(String::new())
     }
+
+    fn resolve_dependencies(&self, _path: &str) -> anyhow::Result<BTreeSet<String>> {
+        Ok(BTreeSet::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::Dependency;
+
+    #[test]
+    pub fn check_dependency_graph_resolution() {
+        struct Manager {}
+
+        impl Dependency for Manager {
+            fn resolve_path(&self, identifier: &str) -> anyhow::Result<String> {
+                Ok(format!("path/{identifier}"))
+            }
+
+            fn resolve_dependencies(&self, path: &str) -> anyhow::Result<BTreeSet<String>> {
+                Ok(match path {
+                    "A" => ["B".to_owned(), "C".to_owned()].into(),
+                    "B" => ["C".to_owned()].into(),
+                    _ => BTreeSet::new(),
+                })
+            }
+        }
+
+        let manager = Manager {};
+        let paths = Dependency::resolve_paths(&manager, &["A", "B"]).expect("Always valid");
+        assert_eq!(paths.get("B").map(String::as_str), Some("path/B"));
+
+        let graph = Dependency::resolve_dependency_graph(&manager, &["A"]).expect("Always valid");
+        assert_eq!(
+            graph.keys().map(String::as_str).collect::<Vec<&str>>(),
+            vec!["A", "B", "C"]
+        );
+        assert!(graph["C"].is_empty());
+        assert!(Dependency::resolve_library(&manager, "A").is_err());
+    }
 }
@@ -0,0 +1,78 @@
+//!
+//! The machine-readable diagnostic.
+//!
+
+pub mod severity;
+pub mod span;
+pub mod suggestion;
+
+#[cfg(test)]
+mod tests;
+
+use self::severity::Severity;
+use self::span::Span;
+
+///
+/// The machine-readable diagnostic.
+///
+/// Collected in the context during lowering and surfaced in the build.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Diagnostic {
+    /// The diagnostic severity.
+    pub severity: Severity,
+    /// The stable diagnostic code, e.g. `code-size`.
+    pub code: String,
+    /// The human-readable message.
+    pub message: String,
+    /// The LLVM function the diagnostic was produced in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The source span, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(severity: Severity, code: &str, message: String) -> Self {
+        Self {
+            severity,
+            code: code.to_owned(),
+            message,
+            function: None,
+            span: None,
+        }
+    }
+
+    ///
+    /// Sets the function the diagnostic was produced in.
+    ///
+    pub fn with_function(mut self, function: Option<String>) -> Self {
+        self.function = function;
+        self
+    }
+
+    ///
+    /// Sets the source span.
+    ///
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)?;
+        if let Some(ref function) = self.function {
+            write!(f, " in function `{function}`")?;
+        }
+        if let Some(ref span) = self.span {
+            write!(f, " at {span}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//!
+//! The diagnostic severity.
+//!
+
+///
+/// The diagnostic severity.
+///
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The informational note.
+    Info,
+    /// The warning, which does not prevent the build.
+    Warning,
+    /// The error, which the build is expected to fail with.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! The diagnostic source span.
+//!
+
+///
+/// The diagnostic source span.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Span {
+    /// The source file.
+    pub file: String,
+    /// The source line, starting from 1.
+    pub line: u32,
+    /// The source column, starting from 1.
+    pub column: u32,
+}
+
+impl Span {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(file: String, line: u32, column: u32) -> Self {
+        Self { file, line, column }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
@@ -0,0 +1,83 @@
+//!
+//! The "did you mean" suggestions for undeclared names.
+//!
+
+/// The maximum number of declared names listed in an error.
+pub const DECLARED_NAMES_LIMIT: usize = 32;
+
+///
+/// Returns the Levenshtein distance between `a` and `b`.
+///
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (index_a, character_a) in a.chars().enumerate() {
+        current[0] = index_a + 1;
+        for (index_b, character_b) in b.iter().enumerate() {
+            let substitution = previous[index_b] + usize::from(character_a != *character_b);
+            current[index_b + 1] = substitution
+                .min(previous[index_b + 1] + 1)
+                .min(current[index_b] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+///
+/// Returns the declared name closest to `name`, if it is close enough to be a likely typo.
+///
+/// A name is close enough if no more than a third of its characters differ.
+///
+pub fn closest<'a, I>(name: &str, declared: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let threshold = std::cmp::max(name.chars().count() / 3, 1);
+    declared
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+///
+/// Returns the error for undeclared `name` of `kind`, e.g. `global variable`.
+///
+/// The error suggests the closest declared name and lists the declared names in order.
+///
+pub fn undeclared<'a, I>(kind: &str, name: &str, declared: I) -> anyhow::Error
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut declared: Vec<&str> = declared.into_iter().collect();
+    declared.sort_unstable();
+
+    let mut message = format!("{kind} `{name}` is not declared.");
+    if let Some(suggestion) = closest(name, declared.iter().copied()) {
+        message.push_str(format!(" Did you mean `{suggestion}`?").as_str());
+    }
+    if declared.is_empty() {
+        message.push_str(format!(" No {kind}s are declared.").as_str());
+    } else {
+        message.push_str(
+            format!(
+                " Declared {kind}s: {}",
+                declared
+                    .iter()
+                    .take(DECLARED_NAMES_LIMIT)
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+            .as_str(),
+        );
+        if declared.len() > DECLARED_NAMES_LIMIT {
+            message
+                .push_str(format!(" and {} more", declared.len() - DECLARED_NAMES_LIMIT).as_str());
+        }
+    }
+    anyhow::anyhow!(message)
+}
@@ -0,0 +1,32 @@
+//!
+//! The diagnostic tests.
+//!
+
+use crate::diagnostic::severity::Severity;
+use crate::diagnostic::span::Span;
+use crate::diagnostic::Diagnostic;
+
+#[test]
+pub fn check_diagnostic_display() {
+    let diagnostic = Diagnostic::new(
+        Severity::Warning,
+        "code-size",
+        "the bytecode is close to the limit".to_owned(),
+    );
+    assert_eq!(
+        diagnostic.to_string(),
+        "warning[code-size]: the bytecode is close to the limit"
+    );
+
+    let diagnostic = diagnostic
+        .with_function(Some("test".to_owned()))
+        .with_span(Span::new("test.sol".to_owned(), 12, 5));
+    assert_eq!(
+        diagnostic.to_string(),
+        "warning[code-size]: the bytecode is close to the limit in function `test` at test.sol:12:5"
+    );
+    assert_eq!(diagnostic.function.as_deref(), Some("test"));
+
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+}
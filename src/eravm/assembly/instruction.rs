@@ -0,0 +1,60 @@
+//!
+//! The EraVM assembly instruction.
+//!
+
+///
+/// The EraVM assembly instruction.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Instruction {
+    /// The instruction mnemonic, including the modifiers, e.g. `add.s`.
+    pub mnemonic: String,
+    /// The instruction operands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operands: Vec<String>,
+}
+
+impl Instruction {
+    ///
+    /// Parses the instruction from a line stripped of the comment.
+    ///
+    pub fn parse(line: &str) -> Self {
+        let (mnemonic, operands) = match line.split_once(char::is_whitespace) {
+            Some((mnemonic, operands)) => (mnemonic, operands.trim()),
+            None => (line, ""),
+        };
+
+        let mut result = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (index, character) in operands.char_indices() {
+            match character {
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    result.push(operands[start..index].trim().to_owned());
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        if !operands[start..].trim().is_empty() {
+            result.push(operands[start..].trim().to_owned());
+        }
+
+        Self {
+            mnemonic: mnemonic.to_owned(),
+            operands: result,
+        }
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mnemonic)?;
+        if !self.operands.is_empty() {
+            write!(f, "\t{}", self.operands.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//!
+//! The EraVM assembly line.
+//!
+
+use super::instruction::Instruction;
+
+///
+/// The EraVM assembly line.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Line {
+    /// The label definition, without the trailing colon.
+    Label(String),
+    /// The directive other than a section switch.
+    Directive {
+        /// The directive name, e.g. `.globl`.
+        name: String,
+        /// The directive arguments as written.
+        arguments: String,
+    },
+    /// The instruction.
+    Instruction(Instruction),
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Label(label) => write!(f, "{label}:"),
+            Self::Directive { name, arguments } if arguments.is_empty() => write!(f, "\t{name}"),
+            Self::Directive { name, arguments } => write!(f, "\t{name}\t{arguments}"),
+            Self::Instruction(instruction) => write!(f, "\t{instruction}"),
+        }
+    }
+}
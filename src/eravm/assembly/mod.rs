@@ -0,0 +1,123 @@
+//!
+//! The EraVM structured assembly.
+//!
+
+pub mod instruction;
+pub mod line;
+pub mod section;
+
+#[cfg(test)]
+mod tests;
+
+use self::instruction::Instruction;
+use self::line::Line;
+use self::section::Section;
+
+///
+/// The EraVM structured assembly.
+///
+/// Is parsed from the text assembly emitted by LLVM. The comments and empty lines are dropped,
+/// so printing the structure back yields the assembly text without them.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Assembly {
+    /// The sections in the order of appearance. The same section may appear several times.
+    pub sections: Vec<Section>,
+}
+
+impl Assembly {
+    /// The default section of the lines preceding the first section switch.
+    pub const DEFAULT_SECTION: &'static str = ".text";
+
+    ///
+    /// Parses the text assembly.
+    ///
+    pub fn parse(assembly: &str) -> Self {
+        let mut sections: Vec<Section> = Vec::new();
+
+        for line in assembly.lines() {
+            let line = Self::strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let directive = line.split_whitespace().next().unwrap_or_default();
+            if directive == ".section" || (directive == line && Self::is_section_shortcut(line)) {
+                sections.push(Section::new(line.to_owned()));
+                continue;
+            }
+
+            let parsed = if let Some(label) = line
+                .strip_suffix(':')
+                .filter(|label| !label.contains(char::is_whitespace))
+            {
+                Line::Label(label.to_owned())
+            } else if line.starts_with('.') {
+                Line::Directive {
+                    name: directive.to_owned(),
+                    arguments: line[directive.len()..].trim().to_owned(),
+                }
+            } else {
+                Line::Instruction(Instruction::parse(line))
+            };
+            if sections.is_empty() {
+                sections.push(Section::new(Self::DEFAULT_SECTION.to_owned()));
+            }
+            sections
+                .last_mut()
+                .expect("Always exists")
+                .lines
+                .push(parsed);
+        }
+
+        Self { sections }
+    }
+
+    ///
+    /// Returns the instructions of the code sections.
+    ///
+    pub fn instructions(&self) -> impl Iterator<Item = &Instruction> {
+        self.sections
+            .iter()
+            .filter(|section| section.is_code())
+            .flat_map(|section| section.lines.iter())
+            .filter_map(|line| match line {
+                Line::Instruction(instruction) => Some(instruction),
+                _ => None,
+            })
+    }
+
+    ///
+    /// Returns `line` without the trailing comment, ignoring the semicolons in string literals.
+    ///
+    fn strip_comment(line: &str) -> &str {
+        let mut is_string = false;
+        for (index, character) in line.char_indices() {
+            match character {
+                '"' => is_string = !is_string,
+                ';' if !is_string => return &line[..index],
+                _ => {}
+            }
+        }
+        line
+    }
+
+    ///
+    /// Whether `directive` is a section switch without the `.section` keyword.
+    ///
+    fn is_section_shortcut(directive: &str) -> bool {
+        matches!(directive, ".text" | ".data" | ".rodata" | ".bss")
+    }
+}
+
+impl std::fmt::Display for Assembly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for section in self.sections.iter() {
+            writeln!(f, "\t{}", section.directive)?;
+            for line in section.lines.iter() {
+                writeln!(f, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
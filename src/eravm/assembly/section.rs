@@ -0,0 +1,50 @@
+//!
+//! The EraVM assembly section.
+//!
+
+use super::line::Line;
+
+///
+/// The EraVM assembly section.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Section {
+    /// The section switching directive as written, e.g. `.text` or `.section ".linker_symbols"`.
+    pub directive: String,
+    /// The section lines in the order of appearance.
+    pub lines: Vec<Line>,
+}
+
+impl Section {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(directive: String) -> Self {
+        Self {
+            directive,
+            lines: vec![],
+        }
+    }
+
+    ///
+    /// Returns the section name, e.g. `.text` or `.linker_symbols`.
+    ///
+    pub fn name(&self) -> &str {
+        match self.directive.strip_prefix(".section") {
+            Some(arguments) => arguments
+                .split(',')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .trim_matches('"'),
+            None => self.directive.as_str(),
+        }
+    }
+
+    ///
+    /// Whether the section contains code.
+    ///
+    pub fn is_code(&self) -> bool {
+        self.name().starts_with(".text")
+    }
+}
@@ -0,0 +1,28 @@
+//!
+//! The EraVM assembly tests.
+//!
+
+use crate::eravm::assembly::line::Line;
+use crate::eravm::assembly::Assembly;
+
+#[test]
+pub fn check_assembly_structured() {
+    let text = "\t.text ; code\n\t.file\t\"a;b.sol\"\n\t.globl\t__entry\n__entry:\n.BB0_1:\n\tadd\tstack[r1 + 1], r0, r2 ; comment\n\tret\n\t.section\t\".rodata\",\"a\"\nCPI0_0:\n\t.cell\t42\n";
+    let assembly = Assembly::parse(text);
+    assert_eq!(assembly.sections.len(), 2);
+    assert_eq!(assembly.sections[1].name(), ".rodata");
+    assert!(!assembly.sections[1].is_code());
+
+    let instructions: Vec<_> = assembly.instructions().collect();
+    assert_eq!(instructions.len(), 2);
+    assert_eq!(instructions[0].mnemonic, "add");
+    assert_eq!(instructions[0].operands, vec!["stack[r1 + 1]", "r0", "r2"]);
+    assert_eq!(
+        assembly.sections[0].lines[0],
+        Line::Directive {
+            name: ".file".to_owned(),
+            arguments: "\"a;b.sol\"".to_owned(),
+        }
+    );
+    assert_eq!(Assembly::parse(assembly.to_string().as_str()), assembly);
+}
@@ -35,6 +35,9 @@ pub static GLOBAL_EXTRA_ABI_DATA: &str = "extra_abi_data";
 /// The active pointer array global variable name.
 pub static GLOBAL_ACTIVE_POINTER_ARRAY: &str = "ptr_active";
 
+/// The global variable name of the immutables number expected by the runtime code.
+pub static GLOBAL_IMMUTABLES_NUMBER_EXPECTED: &str = "immutables_number_expected";
+
 /// The constant array global variable name prefix.
 pub static GLOBAL_CONST_ARRAY_PREFIX: &str = "const_array_";
 
@@ -60,12 +63,36 @@ pub static DEPLOYER_SIGNATURE_CREATE: &str = "create(bytes32,bytes32,bytes)";
 /// The `create2` method deployer signature.
 pub static DEPLOYER_SIGNATURE_CREATE2: &str = "create2(bytes32,bytes32,bytes)";
 
+/// The account `validateTransaction` success magic, which is the method selector.
+pub static ACCOUNT_VALIDATION_SUCCESS_MAGIC: &str = "202bcce7";
+
+/// The paymaster `validateAndPayForPaymasterTransaction` success magic, which is the method selector.
+pub static PAYMASTER_VALIDATION_SUCCESS_MAGIC: &str = "038a24bc";
+
+/// The nonce holder `getMinNonce` method signature.
+pub static NONCE_HOLDER_SIGNATURE_GET_MIN_NONCE: &str = "getMinNonce(address)";
+
+/// The nonce holder `incrementMinNonceIfEquals` method signature.
+pub static NONCE_HOLDER_SIGNATURE_INCREMENT_MIN_NONCE_IF_EQUALS: &str =
+    "incrementMinNonceIfEquals(uint256)";
+
+/// The factory dependency placeholder resolved with the canonical hash of the contract's own
+/// bytecode, which is the hash of the bytecode with the placeholders zeroed rather than the
+/// deployed code hash.
+pub static SELF_CANONICAL_BYTECODE_HASH_SYMBOL: &str = "$self_canonical_bytecode_hash$";
+
+/// The code oracle system contract address.
+pub const ADDRESS_CODE_ORACLE: u16 = 0x8012;
+
 /// The absence of system call bit.
 pub const NO_SYSTEM_CALL_BIT: bool = false;
 
 /// The system call bit.
 pub const SYSTEM_CALL_BIT: bool = true;
 
+/// The default bytecode size limit in instructions.
+pub const BYTECODE_SIZE_LIMIT_DEFAULT: usize = 1 << (era_compiler_common::BIT_LENGTH_BYTE * 2);
+
 /// The default disassembler mode.
 pub const DISASSEMBLER_DEFAULT_MODE: u64 = 1;
 
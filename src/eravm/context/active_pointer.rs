@@ -0,0 +1,317 @@
+//!
+//! The EraVM active pointer handle.
+//!
+
+use inkwell::types::BasicType;
+use inkwell::values::BasicValue;
+
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+///
+/// The EraVM active pointer handle.
+///
+/// Refers to an element of the active pointer array by its index. The array is stored in a
+/// global variable, so every operation loads the pointer and stores the result back if it is
+/// modified.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ActivePointer<'ctx> {
+    /// The index in the active pointer array.
+    index: inkwell::values::IntValue<'ctx>,
+}
+
+impl<'ctx> ActivePointer<'ctx> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(index: inkwell::values::IntValue<'ctx>) -> Self {
+        Self { index }
+    }
+
+    ///
+    /// Returns the handle of the primary active pointer, used by the Yul simulations.
+    ///
+    pub fn primary<D>(context: &Context<'ctx, D>) -> Self
+    where
+        D: Dependency,
+    {
+        Self::new(context.field_const(0))
+    }
+
+    ///
+    /// Returns the index in the active pointer array.
+    ///
+    pub fn index(&self) -> inkwell::values::IntValue<'ctx> {
+        self.index
+    }
+
+    ///
+    /// Loads the active pointer.
+    ///
+    pub fn get<D>(self, context: &Context<'ctx, D>) -> anyhow::Result<Pointer<'ctx, AddressSpace>>
+    where
+        D: Dependency,
+    {
+        let value = context.get_active_pointer(self.index)?;
+        Ok(Pointer::new(
+            context.byte_type(),
+            AddressSpace::Generic,
+            value,
+        ))
+    }
+
+    ///
+    /// Stores `pointer` to the active pointer.
+    ///
+    pub fn set<D>(
+        self,
+        context: &Context<'ctx, D>,
+        pointer: Pointer<'ctx, AddressSpace>,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        if pointer.address_space != AddressSpace::Generic {
+            anyhow::bail!(
+                "Active pointers must be generic, found a pointer to {:?}",
+                pointer.address_space
+            );
+        }
+        context.set_active_pointer(self.index, pointer.value)
+    }
+
+    ///
+    /// Loads the calldata pointer to the active pointer.
+    ///
+    pub fn load_calldata<D>(self, context: &Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        self.load_global(context, crate::eravm::GLOBAL_CALLDATA_POINTER)
+    }
+
+    ///
+    /// Loads the return data pointer to the active pointer.
+    ///
+    pub fn load_return_data<D>(self, context: &Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        self.load_global(context, crate::eravm::GLOBAL_RETURN_DATA_POINTER)
+    }
+
+    ///
+    /// Loads the decommit pointer to the active pointer.
+    ///
+    pub fn load_decommit<D>(self, context: &Context<'ctx, D>) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        self.load_global(context, crate::eravm::GLOBAL_DECOMMIT_POINTER)
+    }
+
+    ///
+    /// Shifts the active pointer by `offset` bytes.
+    ///
+    pub fn add<D>(
+        self,
+        context: &Context<'ctx, D>,
+        offset: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let active_pointer = self.get(context)?;
+        let active_pointer_shifted = context.build_gep(
+            active_pointer,
+            &[offset],
+            context.byte_type().as_basic_type_enum(),
+            "active_pointer_shifted",
+        )?;
+        self.set(context, active_pointer_shifted)
+    }
+
+    ///
+    /// Shrinks the active pointer data by `offset` bytes from the end.
+    ///
+    pub fn shrink<D>(
+        self,
+        context: &Context<'ctx, D>,
+        offset: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        self.modify(
+            context,
+            context.intrinsics().pointer_shrink,
+            offset,
+            "active_pointer_shrunken",
+        )
+    }
+
+    ///
+    /// Writes `data` into the upper 128 bits of the active pointer.
+    ///
+    pub fn pack<D>(
+        self,
+        context: &Context<'ctx, D>,
+        data: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        self.modify(
+            context,
+            context.intrinsics().pointer_pack,
+            data,
+            "active_pointer_packed",
+        )
+    }
+
+    ///
+    /// Reads a single word at `offset` from the active pointer data.
+    ///
+    pub fn read<D>(
+        self,
+        context: &Context<'ctx, D>,
+        offset: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        D: Dependency,
+    {
+        let active_pointer = self.get(context)?;
+        let active_pointer = context.build_gep(
+            active_pointer,
+            &[offset],
+            context.field_type().as_basic_type_enum(),
+            "active_pointer_with_offset",
+        )?;
+        let value = context.build_load(active_pointer, "active_pointer_value")?;
+        Ok(value.into_int_value())
+    }
+
+    ///
+    /// Returns the active pointer data size.
+    ///
+    pub fn size<D>(
+        self,
+        context: &Context<'ctx, D>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        D: Dependency,
+    {
+        let active_pointer = self.get(context)?;
+        let active_pointer_value = active_pointer.to_int(context, "active_pointer_value")?;
+        let active_pointer_value_shifted = context.builder().build_right_shift(
+            active_pointer_value,
+            context.field_const((era_compiler_common::BIT_LENGTH_X32 * 3) as u64),
+            false,
+            "active_pointer_value_shifted",
+        )?;
+        let active_pointer_length = context.builder().build_and(
+            active_pointer_value_shifted,
+            context.field_const(u32::MAX as u64),
+            "active_pointer_length",
+        )?;
+        Ok(active_pointer_length)
+    }
+
+    ///
+    /// Copies `size` bytes at `source_offset` of the active pointer data to the heap at
+    /// `destination_offset`.
+    ///
+    pub fn copy_to_heap<D>(
+        self,
+        context: &Context<'ctx, D>,
+        destination_offset: inkwell::values::IntValue<'ctx>,
+        source_offset: inkwell::values::IntValue<'ctx>,
+        size: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let destination = Pointer::new_with_offset(
+            context,
+            AddressSpace::Heap,
+            context.byte_type(),
+            destination_offset,
+            "active_pointer_data_copy_destination_pointer",
+        )?;
+
+        let active_pointer = self.get(context)?;
+        let source = context.build_gep(
+            active_pointer,
+            &[source_offset],
+            context.byte_type().as_basic_type_enum(),
+            "active_pointer_data_copy_source_pointer",
+        )?;
+
+        context.build_memcpy(
+            context.intrinsics().memory_copy_from_generic,
+            destination,
+            source,
+            size,
+            "active_pointer_data_copy_memcpy_from_child",
+        )
+    }
+
+    ///
+    /// Swaps the active pointer with `other`.
+    ///
+    pub fn swap<D>(self, context: &Context<'ctx, D>, other: Self) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let pointer_1 = self.get(context)?;
+        let pointer_2 = other.get(context)?;
+
+        self.set(context, pointer_2)?;
+        other.set(context, pointer_1)?;
+        Ok(())
+    }
+
+    ///
+    /// Loads the generic pointer stored in the global `name` to the active pointer.
+    ///
+    fn load_global<D>(self, context: &Context<'ctx, D>, name: &str) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let pointer = context.get_global_value(name)?;
+        context.set_active_pointer(self.index, pointer.into_pointer_value())
+    }
+
+    ///
+    /// Replaces the active pointer with the result of the pointer `intrinsic` applied to it and
+    /// `operand`.
+    ///
+    fn modify<D>(
+        self,
+        context: &Context<'ctx, D>,
+        intrinsic: FunctionDeclaration<'ctx>,
+        operand: inkwell::values::IntValue<'ctx>,
+        name: &str,
+    ) -> anyhow::Result<()>
+    where
+        D: Dependency,
+    {
+        let active_pointer = self.get(context)?;
+        let active_pointer_modified = context
+            .build_call(
+                intrinsic,
+                &[
+                    active_pointer.value.as_basic_value_enum(),
+                    operand.as_basic_value_enum(),
+                ],
+                name,
+            )?
+            .expect("Always returns a pointer");
+        context.set_active_pointer(self.index, active_pointer_modified.into_pointer_value())
+    }
+}
@@ -29,6 +29,29 @@ impl IAddressSpace for AddressSpace {
     fn stack() -> Self {
         Self::Stack
     }
+
+    fn heap() -> Self {
+        Self::Heap
+    }
+
+    fn constants() -> Self {
+        // The EraVM code is word-addressed, so the byte constants are kept in the generic globals.
+        Self::Stack
+    }
+
+    fn is_writable(self) -> bool {
+        !matches!(self, Self::Code)
+    }
+
+    fn is_cast_legal_to(self, target: Self) -> bool {
+        if self == target {
+            return true;
+        }
+        let is_linear = |address_space: Self| {
+            matches!(address_space, Self::Heap | Self::HeapAuxiliary | Self::Code)
+        };
+        is_linear(self) && is_linear(target)
+    }
 }
 
 impl From<AddressSpace> for inkwell::AddressSpace {
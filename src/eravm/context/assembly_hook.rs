@@ -0,0 +1,19 @@
+//!
+//! The EraVM assembly post-processing hook.
+//!
+
+///
+/// The EraVM assembly post-processing hook.
+///
+/// Implemented by the embedding compiler to modify the emitted assembly before it is assembled,
+/// e.g. to inject section annotations or strip comments. The structured form can be obtained
+/// with `Assembly::parse`.
+///
+/// The hook cannot be a part of the build cache key, so the build cache is bypassed if it is set.
+///
+pub trait AssemblyHook: Send + Sync {
+    ///
+    /// Returns the processed `assembly` of the contract at `contract_path`.
+    ///
+    fn process(&self, contract_path: &str, assembly: String) -> anyhow::Result<String>;
+}
@@ -4,6 +4,14 @@
 
 use std::collections::BTreeMap;
 
+use crate::artifact_bundle::ArtifactBundle;
+use crate::diagnostic::Diagnostic;
+use crate::eravm::assembly::Assembly;
+use crate::eravm::context::immutables_layout::ImmutablesLayout;
+use crate::eravm::context::metrics::Metrics;
+use crate::size_fallback::SizeFallback;
+use crate::source_map::SourceMap;
+
 ///
 /// The LLVM module build.
 ///
@@ -19,6 +27,42 @@ pub struct Build {
     pub factory_dependencies: BTreeMap<String, String>,
     /// The text assembly.
     pub assembly: Option<String>,
+    /// The warnings produced by the cross-contract analyses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// The diagnostics collected during lowering.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    /// The size fallback attempts.
+    #[serde(default)]
+    pub size_fallback: SizeFallback,
+    /// The build metrics.
+    #[serde(default)]
+    pub metrics: Metrics,
+    /// The bytecode source map. Only available if the assembly is emitted and has debug locations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_map: Option<SourceMap>,
+    /// The unoptimized LLVM IR. Only available if requested from the context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llvm_ir_unoptimized: Option<String>,
+    /// The static ergs costs of the functions. Only available if estimated after linking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ergs_estimates: Option<BTreeMap<String, u64>>,
+    /// The immutables layout. Only available if the Solidity or Vyper data is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immutables_layout: Option<ImmutablesLayout>,
+    /// The structured assembly. Only available if parsed from the text assembly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assembly_structured: Option<Assembly>,
+    /// The optimized LLVM IR. Only available if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llvm_ir: Option<String>,
+    /// The optimized LLVM IR bitcode. Only available if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcode: Option<Vec<u8>>,
+    /// The disassembly of the unlinked object. Only available if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disassembly: Option<String>,
 }
 
 impl Build {
@@ -36,6 +80,18 @@ impl Build {
             metadata_hash,
             factory_dependencies: BTreeMap::new(),
             assembly,
+            warnings: vec![],
+            diagnostics: vec![],
+            size_fallback: SizeFallback::default(),
+            metrics: Metrics::default(),
+            source_map: None,
+            llvm_ir_unoptimized: None,
+            ergs_estimates: None,
+            immutables_layout: None,
+            assembly_structured: None,
+            llvm_ir: None,
+            bitcode: None,
+            disassembly: None,
         }
     }
 
@@ -54,6 +110,63 @@ impl Build {
             metadata_hash,
             factory_dependencies: BTreeMap::new(),
             assembly,
+            warnings: vec![],
+            diagnostics: vec![],
+            size_fallback: SizeFallback::default(),
+            metrics: Metrics::default(),
+            source_map: None,
+            llvm_ir_unoptimized: None,
+            ergs_estimates: None,
+            immutables_layout: None,
+            assembly_structured: None,
+            llvm_ir: None,
+            bitcode: None,
+            disassembly: None,
         }
     }
+
+    ///
+    /// Estimates the static ergs costs of the bytecode functions.
+    ///
+    /// Must be called after linking, and requires the assembly to be emitted.
+    ///
+    pub fn estimate_ergs(&mut self) -> anyhow::Result<()> {
+        let assembly = self
+            .assembly
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("The assembly is required for ergs estimation"))?;
+        self.ergs_estimates = Some(crate::eravm::ergs_estimate::estimate(
+            self.bytecode.as_slice(),
+            assembly,
+        )?);
+        Ok(())
+    }
+
+    ///
+    /// Parses the text assembly into the structured representation.
+    ///
+    /// Does nothing if the assembly has not been emitted.
+    ///
+    pub fn parse_assembly(&mut self) {
+        self.assembly_structured = self.assembly.as_deref().map(Assembly::parse);
+    }
+
+    ///
+    /// Converts the build into a combined artifact bundle.
+    ///
+    /// EraVM builds both code segments together, so the code segment is not set.
+    ///
+    pub fn into_bundle(self, contract_path: &str) -> ArtifactBundle {
+        let mut bundle =
+            ArtifactBundle::new(contract_path.to_owned(), None, self.bytecode.as_slice());
+        bundle.bytecode_hash = self
+            .bytecode_hash
+            .map(|hash| ArtifactBundle::encode_hex(hash.as_slice()));
+        bundle.metadata_hash = self
+            .metadata_hash
+            .map(|hash| ArtifactBundle::encode_hex(hash.as_slice()));
+        bundle.assembly = self.assembly;
+        bundle.factory_dependencies = self.factory_dependencies;
+        bundle
+    }
 }
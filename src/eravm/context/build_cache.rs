@@ -0,0 +1,24 @@
+//!
+//! The EraVM build cache.
+//!
+
+use super::build::Build;
+
+///
+/// The EraVM build cache.
+///
+/// Implemented by the embedding compiler to reuse the builds of identical modules, e.g. between
+/// incremental compilations. The keys are computed by `Context::build_cache_key` from the
+/// unoptimized module and the options affecting the code generation.
+///
+pub trait BuildCache: Send + Sync {
+    ///
+    /// Returns the cached build for `key`, if any.
+    ///
+    fn get(&self, key: &[u8; era_compiler_common::BYTE_LENGTH_FIELD]) -> Option<Build>;
+
+    ///
+    /// Saves `build` for `key`.
+    ///
+    fn insert(&self, key: [u8; era_compiler_common::BYTE_LENGTH_FIELD], build: Build);
+}
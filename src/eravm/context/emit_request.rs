@@ -0,0 +1,77 @@
+//!
+//! The EraVM build artifact emission request.
+//!
+
+///
+/// The EraVM build artifact emission request.
+///
+/// Is a set of flags telling which artifacts are emitted from a single optimization run.
+/// The object is always emitted, as the bytecode is a part of every build.
+///
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct EmitRequest(u8);
+
+impl EmitRequest {
+    /// The optimized LLVM IR text.
+    pub const LLVM_IR: Self = Self(1 << 0);
+
+    /// The optimized LLVM IR bitcode.
+    pub const BITCODE: Self = Self(1 << 1);
+
+    /// The text assembly.
+    pub const ASSEMBLY: Self = Self(1 << 2);
+
+    /// The object.
+    pub const OBJECT: Self = Self(1 << 3);
+
+    /// The object disassembly.
+    pub const DISASSEMBLY: Self = Self(1 << 4);
+
+    ///
+    /// Returns the request of the object only.
+    ///
+    pub fn object() -> Self {
+        Self::OBJECT
+    }
+
+    ///
+    /// Returns the request of the object, and the assembly if `output_assembly` is set.
+    ///
+    pub fn new(output_assembly: bool) -> Self {
+        if output_assembly {
+            Self::OBJECT | Self::ASSEMBLY
+        } else {
+            Self::OBJECT
+        }
+    }
+
+    ///
+    /// Whether all artifacts of `other` are requested.
+    ///
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    ///
+    /// Returns the raw flags.
+    ///
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+}
+
+impl std::ops::BitOr for EmitRequest {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOrAssign for EmitRequest {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
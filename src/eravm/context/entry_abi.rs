@@ -0,0 +1,38 @@
+//!
+//! The EraVM entry function ABI.
+//!
+
+///
+/// The EraVM entry function ABI.
+///
+/// Defines which parts of the environment the `__entry` function initializes before calling
+/// the deploy or runtime code. The function signature is the same for all variants.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EntryAbi {
+    /// The calldata, return data, active pointers, call flags, and extra ABI data are initialized.
+    #[default]
+    Standard,
+    /// The calldata pointer and size are left zeroed.
+    /// Is used by pure system contracts which never read calldata.
+    NoCalldata,
+    /// Only the call flags are initialized to dispatch to the deploy or runtime code.
+    /// Is used by kernel-space contracts which access the environment via the EraVM extensions.
+    Bootstrap,
+}
+
+impl EntryAbi {
+    ///
+    /// Whether the calldata pointer and size are initialized.
+    ///
+    pub fn is_calldata_initialized(&self) -> bool {
+        matches!(self, Self::Standard)
+    }
+
+    ///
+    /// Whether the return data, decommit, and active pointers, and extra ABI data are initialized.
+    ///
+    pub fn is_environment_initialized(&self) -> bool {
+        !matches!(self, Self::Bootstrap)
+    }
+}
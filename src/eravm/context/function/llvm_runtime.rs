@@ -2,8 +2,11 @@
 //! The LLVM runtime functions.
 //!
 
+use std::collections::BTreeMap;
+
 use inkwell::types::BasicType;
 
+use crate::context::attribute::Attribute;
 use crate::context::function::declaration::Declaration as FunctionDeclaration;
 use crate::eravm::context::address_space::AddressSpace;
 use crate::eravm::context::function::Function;
@@ -86,6 +89,9 @@ pub struct LLVMRuntime<'ctx> {
     pub return_forward: FunctionDeclaration<'ctx>,
     /// The corresponding LLVM runtime function.
     pub revert_forward: FunctionDeclaration<'ctx>,
+
+    /// The runtime functions registered by the embedding compiler.
+    pub external: BTreeMap<String, FunctionDeclaration<'ctx>>,
 }
 
 impl<'ctx> LLVMRuntime<'ctx> {
@@ -699,22 +705,92 @@ impl<'ctx> LLVMRuntime<'ctx> {
 
             return_forward,
             revert_forward,
+
+            external: BTreeMap::new(),
         }
     }
 
     ///
     /// Declares an LLVM runtime function in the `module`,
     ///
+    /// The existing function is reused, e.g. if the module has been loaded from bitcode.
+    ///
     pub fn declare(
         module: &inkwell::module::Module<'ctx>,
         name: &str,
         r#type: inkwell::types::FunctionType<'ctx>,
         linkage: Option<inkwell::module::Linkage>,
     ) -> FunctionDeclaration<'ctx> {
-        let value = module.add_function(name, r#type, linkage);
+        let value = module
+            .get_function(name)
+            .unwrap_or_else(|| module.add_function(name, r#type, linkage));
         FunctionDeclaration::new(r#type, value)
     }
 
+    ///
+    /// Registers a runtime function provided by the embedding compiler.
+    ///
+    /// The `name` must start with `__` and must not clash with the functions already in `module`.
+    /// If `body` is set, it must be an LLVM IR module defining the function with type `r#type`,
+    /// which is linked into `module`. Otherwise, the function is declared with the external
+    /// linkage and must be provided by the LLVM runtime.
+    ///
+    pub fn register_external(
+        &mut self,
+        llvm: &'ctx inkwell::context::Context,
+        module: &inkwell::module::Module<'ctx>,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+        attributes: Vec<(Attribute, Option<u64>)>,
+        body: Option<&str>,
+    ) -> anyhow::Result<FunctionDeclaration<'ctx>> {
+        if !name.starts_with("__") {
+            anyhow::bail!("Runtime function `{name}` must be prefixed with `__`");
+        }
+        if module.get_function(name).is_some() {
+            anyhow::bail!("Runtime function `{name}` is already declared");
+        }
+
+        let value = match body {
+            Some(body) => {
+                let buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+                    body.as_bytes(),
+                    name,
+                );
+                let body_module = llvm.create_module_from_ir(buffer).map_err(|error| {
+                    anyhow::anyhow!("Runtime function `{name}` body parsing error: {error}")
+                })?;
+                module.link_in_module(body_module).map_err(|error| {
+                    anyhow::anyhow!("Runtime function `{name}` body linking error: {error}")
+                })?;
+                let value = module.get_function(name).ok_or_else(|| {
+                    anyhow::anyhow!("Runtime function `{name}` is not defined by its body")
+                })?;
+                if value.get_type() != r#type {
+                    anyhow::bail!(
+                        "Runtime function `{name}` is defined with type `{}`, but `{}` is registered",
+                        value.get_type().print_to_string(),
+                        r#type.print_to_string(),
+                    );
+                }
+                value
+            }
+            None => module.add_function(name, r#type, Some(inkwell::module::Linkage::External)),
+        };
+        Function::set_attributes(llvm, value, attributes, false);
+
+        let declaration = FunctionDeclaration::new(r#type, value);
+        self.external.insert(name.to_owned(), declaration);
+        Ok(declaration)
+    }
+
+    ///
+    /// Returns the runtime function registered by the embedding compiler.
+    ///
+    pub fn external(&self, name: &str) -> Option<FunctionDeclaration<'ctx>> {
+        self.external.get(name).copied()
+    }
+
     ///
     /// Modifies the external call function with `is_byref` and `is_system` modifiers.
     ///
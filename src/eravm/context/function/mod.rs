@@ -47,6 +47,8 @@ pub struct Function<'ctx> {
     /// jumping to it. This way it is easier to implement some additional checks and clean-ups
     /// before the returning.
     return_block: inkwell::basic_block::BasicBlock<'ctx>,
+    /// The revert block shared by the active pointer index checks, appended at the first check.
+    active_pointer_revert_block: Option<inkwell::basic_block::BasicBlock<'ctx>>,
 
     /// The Yul compiler data.
     yul_data: Option<YulData>,
@@ -85,6 +87,7 @@ impl<'ctx> Function<'ctx> {
 
             entry_block,
             return_block,
+            active_pointer_revert_block: None,
 
             yul_data: None,
             evmla_data: None,
@@ -115,7 +118,16 @@ impl<'ctx> Function<'ctx> {
     ///
     pub fn is_near_call_abi(name: &str) -> bool {
         name.starts_with(Self::ZKSYNC_NEAR_CALL_ABI_PREFIX)
-            || name == Self::ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER
+            || Self::is_near_call_abi_exception_handler(name)
+    }
+
+    ///
+    /// Checks whether the function is a near call ABI exception handler.
+    ///
+    /// Besides the default handler, named ones can be declared with its name as the prefix.
+    ///
+    pub fn is_near_call_abi_exception_handler(name: &str) -> bool {
+        name.starts_with(Self::ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER)
     }
 
     ///
@@ -357,6 +369,23 @@ impl<'ctx> Function<'ctx> {
         self.return_block
     }
 
+    ///
+    /// Returns the active pointer index revert block, if it has been appended.
+    ///
+    pub fn active_pointer_revert_block(&self) -> Option<inkwell::basic_block::BasicBlock<'ctx>> {
+        self.active_pointer_revert_block
+    }
+
+    ///
+    /// Sets the active pointer index revert block.
+    ///
+    pub fn set_active_pointer_revert_block(
+        &mut self,
+        block: inkwell::basic_block::BasicBlock<'ctx>,
+    ) {
+        self.active_pointer_revert_block = Some(block);
+    }
+
     ///
     /// Sets the EVM legacy assembly data.
     ///
@@ -457,37 +486,6 @@ impl<'ctx> IEVMLAFunction<'ctx> for Function<'ctx> {
         key: &BlockKey,
         stack_hash: &[u8; era_compiler_common::BYTE_LENGTH_FIELD],
     ) -> anyhow::Result<Block<'ctx>> {
-        let evmla_data = self.evmla();
-
-        if evmla_data
-            .blocks
-            .get(key)
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-            .len()
-            == 1
-        {
-            return evmla_data
-                .blocks
-                .get(key)
-                .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-                .first()
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key));
-        }
-
-        evmla_data
-            .blocks
-            .get(key)
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-            .iter()
-            .find(|block| {
-                block
-                    .evm()
-                    .stack_hashes
-                    .iter()
-                    .any(|hash| hash == stack_hash)
-            })
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))
+        self.evmla().find_block(key, stack_hash)
     }
 }
@@ -210,6 +210,8 @@ where
         )?;
         context.build_store(status_code_result_pointer, result_status_code)?;
 
+        let return_data_size = context.update_return_data(result_abi_data_pointer)?;
+
         let source = result_abi_data_casted;
 
         let destination = Pointer::new_with_offset(
@@ -225,17 +227,9 @@ where
             destination,
             source,
             output_length,
+            return_data_size,
             "contract_call_memcpy_from_child",
         )?;
-
-        context.write_abi_pointer(
-            result_abi_data_pointer,
-            crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-        )?;
-        context.write_abi_data_size(
-            result_abi_data_pointer,
-            crate::eravm::GLOBAL_RETURN_DATA_SIZE,
-        )?;
         context.build_unconditional_branch(context.current_function().borrow().return_block())?;
 
         context.set_basic_block(context.current_function().borrow().return_block());
@@ -25,6 +25,8 @@ where
 {
     /// The deploy code AST representation.
     inner: B,
+    /// Whether the immutables number returned by the deploy code is checked.
+    is_immutables_check_enabled: bool,
     /// The `D` phantom data.
     _pd: PhantomData<D>,
 }
@@ -40,9 +42,23 @@ where
     pub fn new(inner: B) -> Self {
         Self {
             inner,
+            is_immutables_check_enabled: false,
             _pd: PhantomData,
         }
     }
+
+    ///
+    /// Enables the check of the immutables number returned by the deploy code.
+    ///
+    /// Before returning, the deploy code compares the number of immutables it returns with the
+    /// number the runtime code expects, which is only known after the whole contract is lowered.
+    /// On mismatch, the deploy code traps, so the inconsistency is caught on deployment instead
+    /// of at the runtime immutable access.
+    ///
+    pub fn with_immutables_check(mut self) -> Self {
+        self.is_immutables_check_enabled = true;
+        self
+    }
 }
 
 impl<B, D> WriteLLVM<D> for DeployCode<B, D>
@@ -68,6 +84,18 @@ where
 
         context.set_basic_block(context.current_function().borrow().entry_block());
         context.set_code_segment(era_compiler_common::CodeSegment::Deploy);
+        if self.is_immutables_check_enabled
+            && context
+                .get_global(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)
+                .is_err()
+        {
+            context.set_global(
+                crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED,
+                context.field_type(),
+                AddressSpace::Stack,
+                context.field_const(0),
+            )?;
+        }
         if let Some(vyper) = context.vyper_data.as_ref() {
             for index in 0..vyper.immutables_size() / era_compiler_common::BYTE_LENGTH_FIELD {
                 let offset = (crate::eravm::r#const::HEAP_AUX_OFFSET_CONSTRUCTOR_RETURN_DATA
@@ -331,14 +331,7 @@ where
             AddressSpace::Generic,
             result_abi_data.into_pointer_value(),
         );
-        context.write_abi_pointer(
-            result_abi_data_pointer,
-            crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-        )?;
-        context.write_abi_data_size(
-            result_abi_data_pointer,
-            crate::eravm::GLOBAL_RETURN_DATA_SIZE,
-        )?;
+        context.update_return_data(result_abi_data_pointer)?;
         context.build_unconditional_branch(context.current_function().borrow().return_block())?;
 
         context.set_basic_block(context.current_function().borrow().return_block());
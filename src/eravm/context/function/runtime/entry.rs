@@ -5,6 +5,7 @@
 use inkwell::types::BasicType;
 
 use crate::context::pointer::Pointer;
+use crate::context::selector_dispatch::SelectorDispatch;
 use crate::context::IContext;
 use crate::eravm::context::address_space::AddressSpace;
 use crate::eravm::context::function::runtime::Runtime;
@@ -20,7 +21,10 @@ use crate::eravm::WriteLLVM;
 /// Is a special runtime function that is only used by the front-end generated code.
 ///
 #[derive(Debug, Default)]
-pub struct Entry {}
+pub struct Entry {
+    /// The optional selector dispatch emitted before the runtime code call.
+    selector_dispatch: Option<SelectorDispatch>,
+}
 
 impl Entry {
     /// The calldata ABI argument index.
@@ -32,6 +36,16 @@ impl Entry {
     /// The number of mandatory arguments.
     pub const MANDATORY_ARGUMENTS_COUNT: usize = 2;
 
+    ///
+    /// Sets the selector dispatch emitted before the runtime code call.
+    ///
+    /// The runtime code is only called for short calldata and unknown selectors.
+    ///
+    pub fn with_selector_dispatch(mut self, selector_dispatch: SelectorDispatch) -> Self {
+        self.selector_dispatch = Some(selector_dispatch);
+        self
+    }
+
     ///
     /// Initializes the global variables.
     ///
@@ -145,23 +159,39 @@ where
         context.set_basic_block(context.current_function().borrow().entry_block());
         Self::initialize_globals(context)?;
 
-        let calldata_abi = context
-            .current_function()
-            .borrow()
-            .get_nth_param(Self::ARGUMENT_INDEX_CALLDATA_ABI);
-        let calldata_abi_pointer = Pointer::new(
-            context.byte_type(),
-            AddressSpace::Generic,
-            calldata_abi.into_pointer_value(),
-        );
-        context.write_abi_pointer(calldata_abi_pointer, crate::eravm::GLOBAL_CALLDATA_POINTER)?;
-        context.write_abi_data_size(calldata_abi_pointer, crate::eravm::GLOBAL_CALLDATA_SIZE)?;
+        let entry_abi = context.yul().map(|yul| yul.entry_abi()).unwrap_or_default();
 
-        context.reset_named_pointers(&[
-            crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-            crate::eravm::GLOBAL_DECOMMIT_POINTER,
-        ])?;
-        context.reset_active_pointers()?;
+        if entry_abi.is_calldata_initialized() {
+            let calldata_abi = context
+                .current_function()
+                .borrow()
+                .get_nth_param(Self::ARGUMENT_INDEX_CALLDATA_ABI);
+            let calldata_abi_pointer = Pointer::new(
+                context.byte_type(),
+                AddressSpace::Generic,
+                calldata_abi.into_pointer_value(),
+            );
+            context
+                .write_abi_pointer(calldata_abi_pointer, crate::eravm::GLOBAL_CALLDATA_POINTER)?;
+            context
+                .write_abi_data_size(calldata_abi_pointer, crate::eravm::GLOBAL_CALLDATA_SIZE)?;
+        } else {
+            let calldata_null_pointer = Pointer::new(
+                context.byte_type(),
+                AddressSpace::Generic,
+                context.ptr_type(AddressSpace::Generic.into()).const_null(),
+            );
+            context
+                .write_abi_pointer(calldata_null_pointer, crate::eravm::GLOBAL_CALLDATA_POINTER)?;
+        }
+
+        if entry_abi.is_environment_initialized() {
+            context.reset_named_pointers(&[
+                crate::eravm::GLOBAL_RETURN_DATA_POINTER,
+                crate::eravm::GLOBAL_DECOMMIT_POINTER,
+            ])?;
+            context.reset_active_pointers()?;
+        }
 
         let call_flags = context
             .current_function()
@@ -174,28 +204,30 @@ where
             call_flags.into_int_value(),
         )?;
 
-        let extra_abi_data_global = context.get_global(crate::eravm::GLOBAL_EXTRA_ABI_DATA)?;
-        for (array_index, argument_index) in (Self::MANDATORY_ARGUMENTS_COUNT
-            ..Self::MANDATORY_ARGUMENTS_COUNT + crate::eravm::EXTRA_ABI_DATA_SIZE)
-            .enumerate()
-        {
-            let array_element_pointer = context.build_gep(
-                extra_abi_data_global.into(),
-                &[
-                    context.field_const(0),
-                    context
-                        .integer_type(era_compiler_common::BIT_LENGTH_X32)
-                        .const_int(array_index as u64, false),
-                ],
-                context.field_type().as_basic_type_enum(),
-                "extra_abi_data_array_element_pointer",
-            )?;
-            let argument_value = context
-                .current_function()
-                .borrow()
-                .get_nth_param(argument_index)
-                .into_int_value();
-            context.build_store(array_element_pointer, argument_value)?;
+        if entry_abi.is_environment_initialized() {
+            let extra_abi_data_global = context.get_global(crate::eravm::GLOBAL_EXTRA_ABI_DATA)?;
+            for (array_index, argument_index) in (Self::MANDATORY_ARGUMENTS_COUNT
+                ..Self::MANDATORY_ARGUMENTS_COUNT + crate::eravm::EXTRA_ABI_DATA_SIZE)
+                .enumerate()
+            {
+                let array_element_pointer = context.build_gep(
+                    extra_abi_data_global.into(),
+                    &[
+                        context.field_const(0),
+                        context
+                            .integer_type(era_compiler_common::BIT_LENGTH_X32)
+                            .const_int(array_index as u64, false),
+                    ],
+                    context.field_type().as_basic_type_enum(),
+                    "extra_abi_data_array_element_pointer",
+                )?;
+                let argument_value = context
+                    .current_function()
+                    .borrow()
+                    .get_nth_param(argument_index)
+                    .into_int_value();
+                context.build_store(array_element_pointer, argument_value)?;
+            }
         }
 
         let is_deploy_call_flag_truncated = context.builder().build_and(
@@ -220,6 +252,25 @@ where
         context.build_unconditional_branch(context.current_function().borrow().return_block())?;
 
         context.set_basic_block(runtime_code_call_block);
+        if let Some(selector_dispatch) = self.selector_dispatch {
+            if !entry_abi.is_calldata_initialized() {
+                anyhow::bail!("Selector dispatch requires the calldata to be initialized");
+            }
+
+            let runtime_code_fallback_block =
+                context.append_basic_block("runtime_code_fallback_block");
+            let calldata_size = crate::eravm::evm::calldata::size(context)?.into_int_value();
+            let calldata_word = crate::eravm::evm::calldata::load(context, context.field_const(0))?
+                .into_int_value();
+            selector_dispatch.build(
+                context,
+                calldata_size,
+                calldata_word,
+                runtime_code_fallback_block,
+                context.current_function().borrow().return_block(),
+            )?;
+            context.set_basic_block(runtime_code_fallback_block);
+        }
         context.build_invoke(runtime_code.borrow().declaration, &[], "runtime_code_call")?;
         context.build_unconditional_branch(context.current_function().borrow().return_block())?;
 
@@ -19,6 +19,10 @@ pub struct Global<'ctx> {
     pub r#type: inkwell::types::BasicTypeEnum<'ctx>,
     /// The global value.
     pub value: inkwell::values::GlobalValue<'ctx>,
+    /// The global address space.
+    pub address_space: AddressSpace,
+    /// Whether the global can be stored to after declaration.
+    pub is_mutable: bool,
 }
 
 impl<'ctx> Global<'ctx> {
@@ -42,7 +46,12 @@ impl<'ctx> Global<'ctx> {
         let value = context
             .module()
             .add_global(r#type, Some(address_space.into()), name);
-        let global = Self { r#type, value };
+        let global = Self {
+            r#type,
+            value,
+            address_space,
+            is_mutable: !matches!(address_space, AddressSpace::Code),
+        };
 
         global.value.set_linkage(inkwell::module::Linkage::Private);
         global
@@ -61,4 +70,12 @@ impl<'ctx> Global<'ctx> {
 
         Ok(global)
     }
+
+    ///
+    /// Makes the global constant, so it can no longer be stored to.
+    ///
+    pub fn set_constant(&mut self) {
+        self.value.set_constant(true);
+        self.is_mutable = false;
+    }
 }
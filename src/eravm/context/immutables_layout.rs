@@ -0,0 +1,55 @@
+//!
+//! The EraVM contract immutables layout.
+//!
+
+use std::collections::BTreeMap;
+
+///
+/// The EraVM contract immutables layout.
+///
+/// The immutables are stored in the array returned by the constructor, where each value takes
+/// a field-sized slot. The offset of an immutable is the key it is saved and requested by in the
+/// immutable simulator system contract.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ImmutablesLayout {
+    /// The identifier-to-offset mapping. Empty for Vyper, whose immutables are not identified.
+    pub offsets: BTreeMap<String, usize>,
+    /// The size of the immutables array in bytes.
+    pub size: usize,
+}
+
+impl ImmutablesLayout {
+    ///
+    /// A shortcut constructor for the layout of anonymous immutables of `size` bytes.
+    ///
+    pub fn new_anonymous(size: usize) -> Self {
+        Self {
+            offsets: BTreeMap::new(),
+            size,
+        }
+    }
+
+    ///
+    /// Allocates a slot for the immutable value.
+    ///
+    /// If the identifier is already known, just returns its offset.
+    ///
+    pub fn allocate(&mut self, identifier: &str) -> usize {
+        if let Some(offset) = self.offsets.get(identifier).copied() {
+            return offset;
+        }
+
+        let offset = self.size;
+        self.offsets.insert(identifier.to_owned(), offset);
+        self.size += era_compiler_common::BYTE_LENGTH_FIELD;
+        offset
+    }
+
+    ///
+    /// Returns the offset of the immutable value, if it is allocated.
+    ///
+    pub fn get(&self, identifier: &str) -> Option<usize> {
+        self.offsets.get(identifier).copied()
+    }
+}
@@ -0,0 +1,177 @@
+//!
+//! The EraVM exception landing pad.
+//!
+
+use inkwell::types::BasicType;
+use inkwell::values::BasicValue;
+
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+///
+/// The EraVM exception landing pad.
+///
+/// Captures the ABI data pointer and the flag of the failed call, which can be passed to the
+/// exception handler to inspect the revert reason.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct LandingPad<'ctx> {
+    /// The landing pad value.
+    value: inkwell::values::StructValue<'ctx>,
+}
+
+impl<'ctx> LandingPad<'ctx> {
+    /// The maximum number of the landing pad values passed to an exception handler.
+    pub const HANDLER_ARGUMENTS_LIMIT: usize = 2;
+
+    ///
+    /// Builds the landing pad in the current basic block.
+    ///
+    pub fn build<D>(context: &Context<'ctx, D>, name: &str) -> anyhow::Result<Self>
+    where
+        D: Dependency,
+    {
+        let r#type = context.structure_type(&[
+            context
+                .ptr_type(AddressSpace::Generic.into())
+                .as_basic_type_enum(),
+            context
+                .integer_type(era_compiler_common::BIT_LENGTH_BOOLEAN)
+                .as_basic_type_enum(),
+        ]);
+        let value = context.builder().build_landing_pad(
+            r#type,
+            context.llvm_runtime().personality.value,
+            &[context
+                .ptr_type(AddressSpace::Stack.into())
+                .const_zero()
+                .as_basic_value_enum()],
+            false,
+            format!("{name}_landing").as_str(),
+        )?;
+        Ok(Self {
+            value: value.into_struct_value(),
+        })
+    }
+
+    ///
+    /// Returns the ABI data pointer of the failed call.
+    ///
+    pub fn abi_data<D>(
+        &self,
+        context: &Context<'ctx, D>,
+    ) -> anyhow::Result<Pointer<'ctx, AddressSpace>>
+    where
+        D: Dependency,
+    {
+        let value = context
+            .builder()
+            .build_extract_value(self.value, 0, "landing_pad_abi_data")?
+            .into_pointer_value();
+        Ok(Pointer::new(
+            context.byte_type(),
+            AddressSpace::Generic,
+            value,
+        ))
+    }
+
+    ///
+    /// Returns the flag of the failed call.
+    ///
+    pub fn flag<D>(
+        &self,
+        context: &Context<'ctx, D>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+    where
+        D: Dependency,
+    {
+        let value = context
+            .builder()
+            .build_extract_value(self.value, 1, "landing_pad_flag")?
+            .into_int_value();
+        Ok(value)
+    }
+
+    ///
+    /// Checks whether the exception `handler` can receive the landing pad values.
+    ///
+    /// The handler may take the ABI data pointer and the flag, in this order. The ABI data
+    /// pointer must be taken as a pointer or an integer, and the flag as an integer.
+    ///
+    pub fn check_handler(handler: FunctionDeclaration<'ctx>) -> anyhow::Result<()> {
+        let parameter_types = handler.r#type.get_param_types();
+        if parameter_types.len() > Self::HANDLER_ARGUMENTS_LIMIT {
+            anyhow::bail!(
+                "Exception handler `{}` must take at most {} arguments",
+                handler.value.get_name().to_string_lossy(),
+                Self::HANDLER_ARGUMENTS_LIMIT,
+            );
+        }
+        if let Some(r#type) = parameter_types.first() {
+            if !r#type.is_int_type() && !r#type.is_pointer_type() {
+                anyhow::bail!(
+                    "Exception handler `{}` must take the ABI data as a pointer or an integer",
+                    handler.value.get_name().to_string_lossy()
+                );
+            }
+        }
+        if let Some(r#type) = parameter_types.get(1) {
+            if !r#type.is_int_type() {
+                anyhow::bail!(
+                    "Exception handler `{}` must take the flag as an integer",
+                    handler.value.get_name().to_string_lossy()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the arguments of the exception `handler`.
+    ///
+    /// Integer parameters receive the values converted or zero-extended to their type.
+    ///
+    pub fn handler_arguments<D>(
+        &self,
+        context: &Context<'ctx, D>,
+        handler: FunctionDeclaration<'ctx>,
+    ) -> anyhow::Result<Vec<inkwell::values::BasicValueEnum<'ctx>>>
+    where
+        D: Dependency,
+    {
+        Self::check_handler(handler)?;
+
+        let parameter_types = handler.r#type.get_param_types();
+        let mut arguments = Vec::with_capacity(parameter_types.len());
+        if let Some(r#type) = parameter_types.first() {
+            let abi_data = self.abi_data(context)?;
+            let argument = if r#type.is_int_type() {
+                context
+                    .builder()
+                    .build_ptr_to_int(
+                        abi_data.value,
+                        r#type.into_int_type(),
+                        "landing_pad_abi_data_casted",
+                    )?
+                    .as_basic_value_enum()
+            } else {
+                abi_data.value.as_basic_value_enum()
+            };
+            arguments.push(argument);
+        }
+        if let Some(r#type) = parameter_types.get(1) {
+            let flag = self.flag(context)?;
+            let argument = context.builder().build_int_z_extend_or_bit_cast(
+                flag,
+                r#type.into_int_type(),
+                "landing_pad_flag_extended",
+            )?;
+            arguments.push(argument.as_basic_value_enum());
+        }
+        Ok(arguments)
+    }
+}
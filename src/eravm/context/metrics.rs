@@ -0,0 +1,26 @@
+//!
+//! The LLVM module build metrics.
+//!
+
+use std::time::Duration;
+
+///
+/// The LLVM module build metrics.
+///
+/// Collected by every build regardless of whether the profiler output is used.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Metrics {
+    /// The LLVM IR verification duration, both before and after the optimization.
+    pub verification: Duration,
+    /// The LLVM IR optimization duration, including the unreachable function pruning.
+    pub optimization: Duration,
+    /// The assembly or bytecode emitting duration.
+    pub emitting: Duration,
+    /// The assembling duration.
+    pub assembling: Duration,
+    /// The number of LLVM IR instructions before the optimization.
+    pub instructions_before: usize,
+    /// The number of LLVM IR instructions after the optimization.
+    pub instructions_after: usize,
+}
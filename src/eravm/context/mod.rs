@@ -2,11 +2,22 @@
 //! The LLVM IR generator context.
 //!
 
+pub mod active_pointer;
 pub mod address_space;
+pub mod assembly_hook;
 pub mod build;
+pub mod build_cache;
+pub mod emit_request;
+pub mod entry_abi;
 pub mod evmla_data;
 pub mod function;
 pub mod global;
+pub mod immutables_layout;
+pub mod landing_pad;
+pub mod metrics;
+pub mod optimized_module;
+pub mod selfdestruct_policy;
+pub mod simulation_registry;
 pub mod solidity_data;
 pub mod vyper_data;
 pub mod yul_data;
@@ -15,34 +26,57 @@ pub mod yul_data;
 mod tests;
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
 
 use inkwell::types::BasicType;
 use inkwell::values::BasicMetadataValueEnum;
 use inkwell::values::BasicValue;
 
+use crate::cancellation_handle::CancellationHandle;
 use crate::context::attribute::Attribute;
+use crate::context::constant_pool::ConstantPool;
 use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::function::evmla_builder::EVMLAFunctionBuilder;
 use crate::context::function::r#return::Return as FunctionReturn;
 use crate::context::pointer::Pointer;
 use crate::context::r#loop::Loop;
+use crate::context::return_data_copy_policy::ReturnDataCopyPolicy;
 use crate::context::IContext;
 use crate::debug_info::DebugInfo;
 use crate::dependency::DummyDependency;
+use crate::diagnostic::suggestion;
+use crate::diagnostic::Diagnostic;
 use crate::eravm::DebugConfig;
 use crate::eravm::Dependency;
-use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::error::Error;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::fallback_policy::FallbackPolicy;
 use crate::optimizer::Optimizer;
-use crate::target_machine::TargetMachine;
+use crate::profiler::Profiler;
+use crate::target_machine::options::Options as TargetOptions;
+use crate::target_machine_pool::TargetMachinePool;
 
 use self::address_space::AddressSpace;
+use self::assembly_hook::AssemblyHook;
 use self::build::Build;
+use self::build_cache::BuildCache;
+use self::emit_request::EmitRequest;
 use self::evmla_data::EVMLAData;
 use self::function::intrinsics::Intrinsics;
 use self::function::llvm_runtime::LLVMRuntime;
 use self::function::Function;
 use self::global::Global;
+use self::immutables_layout::ImmutablesLayout;
+use self::landing_pad::LandingPad;
+use self::metrics::Metrics;
+use self::optimized_module::OptimizedModule;
+use self::simulation_registry::arguments::Arguments as SimulationArguments;
+use self::simulation_registry::Callback as SimulationCallback;
+use self::simulation_registry::SimulationRegistry;
 use self::solidity_data::SolidityData;
 use self::vyper_data::VyperData;
 use self::yul_data::YulData;
@@ -67,6 +101,8 @@ where
     module: inkwell::module::Module<'ctx>,
     /// The extra LLVM options.
     llvm_options: Vec<String>,
+    /// The target CPU and features.
+    target_options: TargetOptions,
     /// The current contract code type, which can be deploy or runtime.
     code_segment: Option<era_compiler_common::CodeSegment>,
     /// The global variables.
@@ -86,6 +122,24 @@ where
     debug_info: DebugInfo<'ctx>,
     /// The debug configuration telling whether to dump the needed IRs.
     debug_config: Option<DebugConfig>,
+    /// The function renaming map applied before the assembly emission.
+    symbol_renames: BTreeMap<String, String>,
+    /// The diagnostics collected during lowering.
+    diagnostics: Vec<Diagnostic>,
+    /// The module-level constant bytes pool.
+    constant_pool: ConstantPool<'ctx, AddressSpace>,
+    /// Whether the target VM version supports transient storage.
+    is_transient_storage_supported: bool,
+    /// Whether the unoptimized LLVM IR is saved to the build.
+    is_llvm_ir_unoptimized_kept: bool,
+    /// Whether the active pointer indexes are checked against the active pointer array size.
+    is_active_pointer_bound_checked: bool,
+    /// The build cache provided by the embedding compiler.
+    build_cache: Option<Arc<dyn BuildCache>>,
+    /// The assembly post-processing hook provided by the embedding compiler.
+    assembly_hook: Option<Arc<dyn AssemblyHook>>,
+    /// The custom simulations registered by the embedding compiler.
+    simulation_registry: SimulationRegistry<'ctx, D>,
 
     /// The Solidity data.
     solidity_data: Option<SolidityData>,
@@ -95,6 +149,10 @@ where
     evmla_data: Option<EVMLAData<'ctx>>,
     /// The Vyper data.
     vyper_data: Option<VyperData>,
+    /// The immutables layout of the deferred mode, if it is enabled without the Solidity data.
+    immutables_deferred: Option<ImmutablesLayout>,
+    /// Whether the immutables number is resolved at the end of lowering.
+    is_immutables_deferred: bool,
 
     /// Dependency phantom data.
     pd: std::marker::PhantomData<D>,
@@ -132,6 +190,7 @@ where
             llvm,
             builder,
             llvm_options,
+            target_options: TargetOptions::default(),
             optimizer,
             module,
             code_segment: None,
@@ -144,32 +203,330 @@ where
 
             debug_info,
             debug_config,
+            symbol_renames: BTreeMap::new(),
+            diagnostics: vec![],
+            constant_pool: ConstantPool::default(),
+            is_transient_storage_supported: true,
+            is_llvm_ir_unoptimized_kept: false,
+            is_active_pointer_bound_checked: false,
+            build_cache: None,
+            assembly_hook: None,
+            simulation_registry: SimulationRegistry::default(),
 
             solidity_data: None,
             yul_data: None,
             evmla_data: None,
             vyper_data: None,
+            immutables_deferred: None,
+            is_immutables_deferred: false,
 
             pd: std::marker::PhantomData,
         }
     }
 
+    ///
+    /// Initializes a new LLVM context from the module `bitcode`, e.g. written by `write_bitcode`.
+    ///
+    /// Returns an error if the module target triple or data layout does not match EraVM.
+    ///
+    /// Only the module is restored, so the context is meant to be built rather than extended.
+    ///
+    pub fn from_bitcode(
+        llvm: &'ctx inkwell::context::Context,
+        bitcode: &inkwell::memory_buffer::MemoryBuffer,
+        llvm_options: Vec<String>,
+        optimizer: Optimizer,
+        debug_config: Option<DebugConfig>,
+    ) -> anyhow::Result<Self> {
+        let module = inkwell::module::Module::parse_bitcode_from_buffer(bitcode, llvm)
+            .map_err(|error| anyhow::anyhow!("bitcode parsing: {error}"))?;
+        let target_machine = TargetMachinePool::global().get(
+            era_compiler_common::Target::EraVM,
+            optimizer.settings(),
+            llvm_options.as_slice(),
+            &TargetOptions::default(),
+        )?;
+        target_machine.check_target_data(&module)?;
+        Ok(Self::new(
+            llvm,
+            module,
+            llvm_options,
+            optimizer,
+            debug_config,
+        ))
+    }
+
     ///
     /// Builds the LLVM IR module, returning the build artifacts.
     ///
+    /// Runs `prepare`, the emission stages of the optimized module, and `finalize`.
+    ///
+    /// If `cancellation_handle` is set, it is checked between the pipeline stages.
+    ///
+    /// If the bytecode exceeds the size limit, the optimizer fallback policy chooses the settings
+    /// of the next attempt, if any.
+    ///
+    /// The bytecode size and duration of each attempt are saved to the build size fallback report.
+    ///
+    /// If the build cache is set, the cached build is returned on the key match, and the new build
+    /// is saved to the cache otherwise. The cache is bypassed if the build cannot be keyed.
+    ///
     pub fn build(
-        mut self,
+        self,
         contract_path: &str,
-        metadata_hash: Option<era_compiler_common::Hash>,
-        output_assembly: bool,
+        metadata: &MetadataBuilder,
+        emit_request: EmitRequest,
         is_fallback_to_size: bool,
+        profiler: &mut Profiler,
+        cancellation_handle: Option<&CancellationHandle>,
     ) -> anyhow::Result<Build> {
-        let module_clone = self.module.clone();
+        let build_cache = match self.build_cache {
+            Some(ref build_cache) => build_cache.clone(),
+            None => {
+                return self.build_uncached(
+                    contract_path,
+                    metadata,
+                    emit_request,
+                    is_fallback_to_size,
+                    profiler,
+                    cancellation_handle,
+                )
+            }
+        };
+
+        let key = match self.build_cache_key(metadata, emit_request, is_fallback_to_size) {
+            Some(key) => key,
+            None => {
+                return self.build_uncached(
+                    contract_path,
+                    metadata,
+                    emit_request,
+                    is_fallback_to_size,
+                    profiler,
+                    cancellation_handle,
+                )
+            }
+        };
+        if let Some(build) = build_cache.get(&key) {
+            return Ok(build);
+        }
+        let build = self.build_uncached(
+            contract_path,
+            metadata,
+            emit_request,
+            is_fallback_to_size,
+            profiler,
+            cancellation_handle,
+        )?;
+        build_cache.insert(key, build.clone());
+        Ok(build)
+    }
+
+    ///
+    /// Computes the build cache key.
+    ///
+    /// The key is the keccak256 hash of the unoptimized module, optimizer settings and fallback
+    /// policy, LLVM options, target options, packed metadata, emission request, symbol renames,
+    /// IR retention option, immutables layout, and registered simulation addresses, along with
+    /// the crate and LLVM versions.
+    ///
+    /// Returns `None` if the build depends on the embedder code which cannot be hashed, that is,
+    /// if the assembly hook, optimizer callbacks, or a custom fallback policy are set. Also
+    /// returns `None` if the debug config is set, since a cached build would skip the dumps.
+    ///
+    pub fn build_cache_key(
+        &self,
+        metadata: &MetadataBuilder,
+        emit_request: EmitRequest,
+        is_fallback_to_size: bool,
+    ) -> Option<[u8; era_compiler_common::BYTE_LENGTH_FIELD]> {
+        if self.assembly_hook.is_some()
+            || self.optimizer.has_callbacks()
+            || self.debug_config.is_some()
+            || matches!(
+                self.optimizer.fallback_policy(),
+                Some(FallbackPolicy::Custom(_))
+            )
+        {
+            return None;
+        }
+
+        let mut preimage = Vec::new();
+        for part in [
+            env!("CARGO_PKG_VERSION").to_owned(),
+            crate::r#const::LLVM_VERSION.to_string(),
+            format!("{:?}", self.optimizer.settings()),
+            format!("{:?}", self.optimizer.fallback_policy()),
+            is_fallback_to_size.to_string(),
+            self.llvm_options.join(" "),
+            format!("{:?}", self.target_options),
+            emit_request.bits().to_string(),
+            format!("{:?}", self.symbol_renames),
+            self.is_llvm_ir_unoptimized_kept.to_string(),
+            format!("{:?}", self.immutables_layout()),
+            format!(
+                "{:?}",
+                self.simulation_registry.addresses().collect::<Vec<u16>>()
+            ),
+        ] {
+            preimage.extend_from_slice(part.as_bytes());
+            preimage.push(0);
+        }
+        if let Some(metadata) = metadata.build() {
+            preimage.extend_from_slice(metadata.as_slice());
+        }
+        preimage.push(0);
+        preimage.extend_from_slice(self.module.print_to_string().to_bytes());
+        Some(crate::const_hash::keccak256(preimage.as_slice()))
+    }
+
+    ///
+    /// Builds the LLVM IR module without consulting the build cache.
+    ///
+    fn build_uncached(
+        self,
+        contract_path: &str,
+        metadata: &MetadataBuilder,
+        emit_request: EmitRequest,
+        is_fallback_to_size: bool,
+        profiler: &mut Profiler,
+        cancellation_handle: Option<&CancellationHandle>,
+    ) -> anyhow::Result<Build> {
+        let is_debug_config_set = self.debug_config.is_some();
+        let assembly_hook = self.assembly_hook.clone();
+        let mut optimized_module = self.prepare(
+            contract_path,
+            is_fallback_to_size,
+            profiler,
+            cancellation_handle,
+        )?;
 
-        let target_machine = TargetMachine::new(
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("emitting")?;
+        }
+        let assembly_buffer = if emit_request.contains(EmitRequest::ASSEMBLY)
+            || is_debug_config_set
+            || assembly_hook.is_some()
+        {
+            let assembly_buffer = optimized_module.emit_assembly(profiler)?;
+            match assembly_hook {
+                Some(ref assembly_hook) => {
+                    let assembly_text =
+                        String::from_utf8_lossy(assembly_buffer.as_slice()).to_string();
+                    let assembly_text = assembly_hook
+                        .process(contract_path, assembly_text)
+                        .map_err(|error| anyhow::anyhow!("assembly post-processing: {error}"))?;
+                    Some(
+                        inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+                            assembly_text.as_bytes(),
+                            "assembly_buffer",
+                        ),
+                    )
+                }
+                None => Some(assembly_buffer),
+            }
+        } else {
+            None
+        };
+
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("assembling")?;
+        }
+        let bytecode_buffer = optimized_module.emit_object(assembly_buffer.as_ref(), profiler)?;
+
+        let metadata_size = metadata.size();
+        if optimized_module.exceeds_size_limit(&bytecode_buffer, metadata_size) {
+            let attempt = optimized_module.size_fallback_attempt(&bytecode_buffer);
+            let size_limit = optimized_module.size_limit();
+            match optimized_module.into_fallback_context() {
+                Some(context) => {
+                    let mut build = context
+                        .build(
+                            contract_path,
+                            metadata,
+                            emit_request,
+                            true,
+                            profiler,
+                            cancellation_handle,
+                        )
+                        .map_err(|error| match error.downcast::<Error>() {
+                            Ok(error) => Error::SizeFallback(Box::new(error)).into(),
+                            Err(error) => {
+                                anyhow::anyhow!("falling back to optimizing for size: {error}")
+                            }
+                        })?;
+                    build.size_fallback.attempts.insert(0, attempt);
+                    return Ok(build);
+                }
+                None => anyhow::bail!(Error::BytecodeSizeExceeded {
+                    found: bytecode_buffer.as_slice().len() / era_compiler_common::BYTE_LENGTH_X64,
+                    limit: size_limit,
+                }),
+            }
+        }
+
+        let llvm_ir = emit_request
+            .contains(EmitRequest::LLVM_IR)
+            .then(|| optimized_module.module().print_to_string().to_string());
+        let bitcode = emit_request.contains(EmitRequest::BITCODE).then(|| {
+            optimized_module
+                .module()
+                .write_bitcode_to_memory()
+                .as_slice()
+                .to_vec()
+        });
+        let disassembly = if emit_request.contains(EmitRequest::DISASSEMBLY) {
+            Some(optimized_module.disassemble(&bytecode_buffer)?)
+        } else {
+            None
+        };
+
+        let mut build = optimized_module.finalize(bytecode_buffer, assembly_buffer, metadata)?;
+        build.llvm_ir = llvm_ir;
+        build.bitcode = bitcode;
+        build.disassembly = disassembly;
+        Ok(build)
+    }
+
+    ///
+    /// Verifies and optimizes the LLVM IR module, returning it ready for emission.
+    ///
+    /// The verification and optimization timings are saved to `profiler`.
+    ///
+    /// If `cancellation_handle` is set, it is checked between the pipeline stages.
+    ///
+    /// The stage durations and instruction counts are saved to the build metrics.
+    ///
+    pub fn prepare(
+        mut self,
+        contract_path: &str,
+        is_fallback_to_size: bool,
+        profiler: &mut Profiler,
+        cancellation_handle: Option<&CancellationHandle>,
+    ) -> anyhow::Result<OptimizedModule<'ctx, D>> {
+        let start = Instant::now();
+        let immutables_number = self
+            .immutables_layout()
+            .map(|layout| layout.size / era_compiler_common::BYTE_LENGTH_FIELD)
+            .unwrap_or_default();
+        let immutables_number = self.field_const(immutables_number as u64);
+        if let Some(global) = self
+            .globals
+            .get_mut(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)
+        {
+            global.value.set_initializer(&immutables_number);
+            global.set_constant();
+        }
+        let module_unoptimized = self.module.clone();
+        let llvm_ir_unoptimized = self
+            .is_llvm_ir_unoptimized_kept
+            .then(|| self.module.print_to_string().to_string());
+
+        let target_machine = TargetMachinePool::global().get(
             era_compiler_common::Target::EraVM,
             self.optimizer.settings(),
             self.llvm_options.as_slice(),
+            &self.target_options,
         )?;
         target_machine.set_target_data(self.module());
 
@@ -181,12 +538,61 @@ where
                 is_fallback_to_size,
             )?;
         }
-        self.verify()
-            .map_err(|error| anyhow::anyhow!("unoptimized LLVM IR verification: {error}",))?;
+        let mut metrics = Metrics {
+            instructions_before: self.instructions_count(),
+            ..Metrics::default()
+        };
+
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("verification")?;
+        }
+        let run = profiler.start_pipeline_element(
+            contract_path,
+            self.code_segment,
+            "unoptimized LLVM IR verification",
+            self.optimizer.settings(),
+        );
+        self.check_terminators().map_err(|error| {
+            Error::Verification(format!("unoptimized LLVM IR verification: {error}"))
+        })?;
+        self.verify().map_err(|error| {
+            Error::Verification(format!("unoptimized LLVM IR verification: {error}"))
+        })?;
+        metrics.verification += profiler.finish_pipeline_element(run);
 
+        let run = profiler.start_pipeline_element(
+            contract_path,
+            self.code_segment,
+            "unreachable function pruning",
+            self.optimizer.settings(),
+        );
         self.optimizer
-            .run(&target_machine, self.module())
-            .map_err(|error| anyhow::anyhow!("optimizing: {error}",))?;
+            .prune_unreachable(&target_machine, self.module())
+            .map_err(|error| {
+                Error::Optimization(format!("unreachable function pruning: {error}"))
+            })?;
+        metrics.optimization += profiler.finish_pipeline_element(run);
+
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("optimization")?;
+        }
+        let run = profiler.start_pipeline_element(
+            contract_path,
+            self.code_segment,
+            "LLVM IR optimization",
+            self.optimizer.settings(),
+        );
+        self.optimizer
+            .run(
+                &target_machine,
+                self.module(),
+                contract_path,
+                self.code_segment,
+                Some(&mut *profiler),
+                self.debug_config.as_ref(),
+            )
+            .map_err(|error| Error::Optimization(format!("optimizing: {error}")))?;
+        metrics.optimization += profiler.finish_pipeline_element(run);
         if let Some(ref debug_config) = self.debug_config {
             debug_config.dump_llvm_ir_optimized(
                 contract_path,
@@ -195,64 +601,105 @@ where
                 is_fallback_to_size,
             )?;
         }
-        self.verify()
-            .map_err(|error| anyhow::anyhow!("optimized LLVM IR verification: {error}",))?;
+        let run = profiler.start_pipeline_element(
+            contract_path,
+            self.code_segment,
+            "optimized LLVM IR verification",
+            self.optimizer.settings(),
+        );
+        self.verify().map_err(|error| {
+            Error::Verification(format!("optimized LLVM IR verification: {error}"))
+        })?;
+        metrics.verification += profiler.finish_pipeline_element(run);
 
-        let assembly_buffer = if output_assembly || self.debug_config.is_some() {
-            let assembly_buffer = target_machine
-                .write_to_memory_buffer(self.module(), inkwell::targets::FileType::Assembly)
-                .map_err(|error| anyhow::anyhow!("assembly emitting: {error}"))?;
+        metrics.instructions_after = self.instructions_count();
 
-            if let Some(ref debug_config) = self.debug_config {
-                let assembly_text = String::from_utf8_lossy(assembly_buffer.as_slice());
-                debug_config.dump_assembly(contract_path, None, assembly_text.as_ref())?;
-            }
+        self.apply_symbol_renames()?;
 
-            Some(assembly_buffer)
-        } else {
-            None
-        };
+        Ok(OptimizedModule::new(
+            self,
+            module_unoptimized,
+            target_machine,
+            contract_path.to_owned(),
+            llvm_ir_unoptimized,
+            metrics,
+            start,
+        ))
+    }
 
-        let bytecode_buffer = match assembly_buffer {
-            Some(ref assembly_buffer) => target_machine
-                .assemble(assembly_buffer)
-                .map_err(|error| anyhow::anyhow!("assembling: {error}")),
-            None => target_machine
-                .write_to_memory_buffer(self.module(), inkwell::targets::FileType::Object)
-                .map_err(|error| anyhow::anyhow!("bytecode emitting: {error}")),
-        }?;
+    ///
+    /// Returns the number of LLVM IR instructions in the module.
+    ///
+    pub fn instructions_count(&self) -> usize {
+        self.module
+            .get_functions()
+            .flat_map(|function| function.get_basic_blocks())
+            .map(|block| block.get_instructions().count())
+            .sum()
+    }
 
-        let metadata_size = metadata_hash
-            .as_ref()
-            .map(|array| array.as_bytes().len())
-            .unwrap_or_default();
+    ///
+    /// Sets the function renaming map applied before the assembly emission.
+    ///
+    /// Used to replace unstable names, e.g. hashed Yul function suffixes, with stable ones, so
+    /// the assembly of different compiler versions can be compared.
+    ///
+    pub fn set_symbol_renames(&mut self, symbol_renames: BTreeMap<String, String>) {
+        self.symbol_renames = symbol_renames;
+    }
 
-        if bytecode_buffer.exceeds_size_limit_eravm(metadata_size) {
-            if self.optimizer.settings() != &OptimizerSettings::size()
-                && self.optimizer.settings().is_fallback_to_size_enabled()
-            {
-                self.optimizer = Optimizer::new(OptimizerSettings::size());
-                self.module = module_clone;
-                for function in self.module.get_functions() {
-                    Function::set_size_attributes(self.llvm, function);
-                }
-                return self
-                    .build(contract_path, metadata_hash, output_assembly, true)
-                    .map_err(|error| {
-                        anyhow::anyhow!("falling back to optimizing for size: {error}")
-                    });
-            } else {
-                anyhow::bail!(
-                    "bytecode size exceeds the limit of {} instructions",
-                    1 << (era_compiler_common::BIT_LENGTH_BYTE * 2)
-                );
+    ///
+    /// Applies the function renaming map to the module and the declared functions.
+    ///
+    /// Functions absent from the module are skipped, as they may have been inlined or removed.
+    /// Only private and internal functions can be renamed.
+    ///
+    fn apply_symbol_renames(&mut self) -> anyhow::Result<()> {
+        for (name, new_name) in self.symbol_renames.iter() {
+            let function = match self.module.get_function(name) {
+                Some(function) => function,
+                None => continue,
+            };
+            if !matches!(
+                function.get_linkage(),
+                inkwell::module::Linkage::Private | inkwell::module::Linkage::Internal
+            ) {
+                anyhow::bail!("function `{name}` is not internal and cannot be renamed");
+            }
+            if self.module.get_function(new_name).is_some() {
+                anyhow::bail!("cannot rename function `{name}` to an existing name `{new_name}`");
             }
-        }
 
-        let assembly_text = assembly_buffer
-            .map(|assembly_buffer| String::from_utf8_lossy(assembly_buffer.as_slice()).to_string());
+            function.as_global_value().set_name(new_name);
+            if let Some(function) = self.functions.remove(name) {
+                self.functions.insert(new_name.to_owned(), function);
+            }
+        }
+        Ok(())
+    }
 
-        crate::eravm::build(bytecode_buffer, metadata_hash, assembly_text)
+    ///
+    /// Checks the unoptimized LLVM IR module without optimizing it and generating code.
+    ///
+    /// The module is dumped if the debug configuration is set. The verification errors are
+    /// returned as `Error::Verification`.
+    ///
+    pub fn check(&self, contract_path: &str) -> anyhow::Result<()> {
+        if let Some(ref debug_config) = self.debug_config {
+            debug_config.dump_llvm_ir_unoptimized(
+                contract_path,
+                self.code_segment,
+                self.module(),
+                false,
+            )?;
+        }
+        self.check_terminators().map_err(|error| {
+            Error::Verification(format!("unoptimized LLVM IR verification: {error}"))
+        })?;
+        self.verify().map_err(|error| {
+            Error::Verification(format!("unoptimized LLVM IR verification: {error}"))
+        })?;
+        Ok(())
     }
 
     ///
@@ -270,7 +717,11 @@ where
     pub fn get_global(&self, name: &str) -> anyhow::Result<Global<'ctx>> {
         match self.globals.get(name) {
             Some(global) => Ok(*global),
-            None => anyhow::bail!("global variable `{name}` is not declared"),
+            None => Err(suggestion::undeclared(
+                "global variable",
+                name,
+                self.globals.keys().map(String::as_str),
+            )),
         }
     }
 
@@ -300,6 +751,9 @@ where
         V: BasicValue<'ctx> + Clone + Copy,
     {
         match self.globals.get(name) {
+            Some(global) if !global.is_mutable => {
+                anyhow::bail!("global variable `{name}` is constant and cannot be stored to")
+            }
             Some(global) => {
                 let global = *global;
                 self.build_store(global.into(), value)?;
@@ -312,6 +766,48 @@ where
         Ok(())
     }
 
+    ///
+    /// Declares a constant global variable initialized with `value`.
+    ///
+    /// The global cannot be redeclared or stored to afterwards.
+    ///
+    pub fn const_global<T, V>(
+        &mut self,
+        name: &str,
+        r#type: T,
+        address_space: AddressSpace,
+        value: V,
+    ) -> anyhow::Result<Global<'ctx>>
+    where
+        T: BasicType<'ctx> + Clone + Copy,
+        V: BasicValue<'ctx> + Clone + Copy,
+    {
+        if self.globals.contains_key(name) {
+            anyhow::bail!("global variable `{name}` is already declared");
+        }
+        if r#type.as_basic_type_enum().is_pointer_type() {
+            anyhow::bail!("constant global variable `{name}` cannot be of a pointer type");
+        }
+
+        let mut global = Global::new(self, r#type, address_space, value, name)?;
+        global.set_constant();
+        self.globals.insert(name.to_owned(), global);
+        Ok(global)
+    }
+
+    ///
+    /// Returns the declared global variables sorted by name.
+    ///
+    pub fn globals(&self) -> impl Iterator<Item = (&str, &Global<'ctx>)> {
+        let mut globals: Vec<(&str, &Global<'ctx>)> = self
+            .globals
+            .iter()
+            .map(|(name, global)| (name.as_str(), global))
+            .collect();
+        globals.sort_unstable_by_key(|(name, _)| *name);
+        globals.into_iter()
+    }
+
     ///
     /// Returns the active pointer at `index`.
     ///
@@ -319,6 +815,7 @@ where
         &self,
         index: inkwell::values::IntValue<'ctx>,
     ) -> anyhow::Result<inkwell::values::PointerValue<'ctx>> {
+        self.check_active_pointer_index(index)?;
         let active_pointer_array_global = self
             .globals
             .get(crate::eravm_const::GLOBAL_ACTIVE_POINTER_ARRAY)
@@ -342,6 +839,7 @@ where
         index: inkwell::values::IntValue<'ctx>,
         pointer: inkwell::values::PointerValue<'ctx>,
     ) -> anyhow::Result<()> {
+        self.check_active_pointer_index(index)?;
         let active_pointer_array_global = self
             .globals
             .get(crate::eravm_const::GLOBAL_ACTIVE_POINTER_ARRAY)
@@ -357,6 +855,115 @@ where
         Ok(())
     }
 
+    ///
+    /// Builds a call with the call site `attributes`.
+    ///
+    /// Calls are not inlined without the middle-end optimizations, unless the call site is
+    /// explicitly marked as always inlined.
+    ///
+    fn build_call_site(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: &[inkwell::values::BasicMetadataValueEnum<'ctx>],
+        attributes: &[Attribute],
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        let is_inlining_forced = attributes.contains(&Attribute::AlwaysInline);
+        if is_inlining_forced && attributes.contains(&Attribute::NoInline) {
+            anyhow::bail!("Call site `{name}` cannot be both always and never inlined");
+        }
+
+        let call_site_value = self.builder.build_indirect_call(
+            function.r#type,
+            function.value.as_global_value().as_pointer_value(),
+            arguments,
+            name,
+        )?;
+        if self.optimizer.settings().level_middle_end == inkwell::OptimizationLevel::None
+            && !is_inlining_forced
+        {
+            call_site_value.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                self.llvm
+                    .create_enum_attribute(Attribute::NoInline as u32, 0),
+            );
+        }
+        for attribute in attributes.iter() {
+            call_site_value.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                self.llvm.create_enum_attribute(*attribute as u32, 0),
+            );
+        }
+        self.modify_call_site_value(arguments, call_site_value, function);
+        Ok(call_site_value.try_as_basic_value().left())
+    }
+
+    ///
+    /// Checks `index` against the active pointer array size, if the bound check is enabled.
+    ///
+    /// Constant indexes are checked at compile time, whereas the other ones are checked at
+    /// runtime, jumping to the revert block of the current function on violation.
+    ///
+    fn check_active_pointer_index(
+        &self,
+        index: inkwell::values::IntValue<'ctx>,
+    ) -> anyhow::Result<()> {
+        if !self.is_active_pointer_bound_checked {
+            return Ok(());
+        }
+
+        if let Some(constant) = index.get_zero_extended_constant() {
+            if constant >= crate::eravm_const::AVAILABLE_ACTIVE_POINTERS_NUMBER as u64 {
+                anyhow::bail!(
+                    "Active pointer index {constant} is out of bounds, as there are only {} active pointers",
+                    crate::eravm_const::AVAILABLE_ACTIVE_POINTERS_NUMBER
+                );
+            }
+            return Ok(());
+        }
+
+        let is_out_of_bounds = self.builder().build_int_compare(
+            inkwell::IntPredicate::UGE,
+            index,
+            self.field_const(crate::eravm_const::AVAILABLE_ACTIVE_POINTERS_NUMBER as u64),
+            "active_pointer_index_is_out_of_bounds",
+        )?;
+        let revert_block = self.active_pointer_revert_block()?;
+        let join_block = self.append_basic_block("active_pointer_index_join_block");
+        self.build_conditional_branch(is_out_of_bounds, revert_block, join_block)?;
+
+        self.set_basic_block(join_block);
+        Ok(())
+    }
+
+    ///
+    /// Returns the active pointer index revert block of the current function.
+    ///
+    /// The block is appended at the first runtime check and shared by the subsequent ones.
+    ///
+    fn active_pointer_revert_block(
+        &self,
+    ) -> anyhow::Result<inkwell::basic_block::BasicBlock<'ctx>> {
+        if let Some(block) = self
+            .current_function()
+            .borrow()
+            .active_pointer_revert_block()
+        {
+            return Ok(block);
+        }
+
+        let current_block = self.basic_block();
+        let revert_block = self.append_basic_block("active_pointer_index_revert_block");
+        self.set_basic_block(revert_block);
+        self.build_empty_revert()?;
+        self.set_basic_block(current_block);
+
+        self.current_function()
+            .borrow_mut()
+            .set_active_pointer_revert_block(revert_block);
+        Ok(revert_block)
+    }
+
     ///
     /// Returns the LLVM intrinsics collection reference.
     ///
@@ -364,6 +971,19 @@ where
         &self.intrinsics
     }
 
+    ///
+    /// Returns the EVM legacy assembly block builder of the current function.
+    ///
+    /// The built data must be set with `set_evmla_data` of the current function.
+    ///
+    pub fn evmla_function_builder(&self, stack_size: usize) -> EVMLAFunctionBuilder<'ctx> {
+        EVMLAFunctionBuilder::new(
+            self.llvm,
+            self.current_function().borrow().declaration().value,
+            stack_size,
+        )
+    }
+
     ///
     /// Returns the LLVM runtime function collection reference.
     ///
@@ -371,6 +991,22 @@ where
         &self.llvm_runtime
     }
 
+    ///
+    /// Registers a runtime function provided by the embedding compiler.
+    ///
+    /// Must be called before lowering the code which uses the function.
+    ///
+    pub fn register_llvm_runtime_function(
+        &mut self,
+        name: &str,
+        r#type: inkwell::types::FunctionType<'ctx>,
+        attributes: Vec<(Attribute, Option<u64>)>,
+        body: Option<&str>,
+    ) -> anyhow::Result<FunctionDeclaration<'ctx>> {
+        self.llvm_runtime
+            .register_external(self.llvm, &self.module, name, r#type, attributes, body)
+    }
+
     ///
     /// Builds an invoke of local call covered with an exception handler.
     ///
@@ -384,6 +1020,66 @@ where
         arguments: Vec<inkwell::values::BasicValueEnum<'ctx>>,
         name: &str,
     ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        let handler = self
+            .functions
+            .get(Function::ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER)
+            .map(|handler| handler.borrow().declaration());
+        self.build_near_call_abi(function, arguments, handler, name)
+    }
+
+    ///
+    /// Builds an invoke of local call covered with the exception handler `handler`.
+    ///
+    /// The handler must be named with the `ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER` prefix, which
+    /// allows front-ends to implement scoped exception handling by selecting the handler for each
+    /// call site. The handler may take the ABI data pointer and the flag captured by the landing pad,
+    /// which allows it to inspect the revert reason.
+    ///
+    pub fn build_invoke_near_call_abi_with_handler(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: Vec<inkwell::values::BasicValueEnum<'ctx>>,
+        handler: &str,
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        if !Function::is_near_call_abi_exception_handler(handler) {
+            anyhow::bail!(
+                "Exception handler `{handler}` must be prefixed with `{}`",
+                Function::ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER
+            );
+        }
+        let handler = self
+            .functions
+            .get(handler)
+            .ok_or_else(|| {
+                suggestion::undeclared(
+                    "exception handler",
+                    handler,
+                    self.functions
+                        .keys()
+                        .map(String::as_str)
+                        .filter(|name| Function::is_near_call_abi_exception_handler(name)),
+                )
+            })?
+            .borrow()
+            .declaration();
+        self.build_near_call_abi(function, arguments, Some(handler), name)
+    }
+
+    ///
+    /// Builds an invoke of local call, covered with `handler` if it is specified.
+    ///
+    fn build_near_call_abi(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: Vec<inkwell::values::BasicValueEnum<'ctx>>,
+        handler: Option<FunctionDeclaration<'ctx>>,
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        if let Some(handler) = handler {
+            LandingPad::check_handler(handler)?;
+        }
+
         let join_block = self.append_basic_block("near_call_join_block");
 
         let return_pointer = if let Some(r#type) = function.r#type.get_return_type() {
@@ -394,32 +1090,19 @@ where
             None
         };
 
-        let call_site_value = if let Some(handler) = self
-            .functions
-            .get(Function::ZKSYNC_NEAR_CALL_ABI_EXCEPTION_HANDLER)
-        {
+        let call_site_value = if let Some(handler) = handler {
             let success_block = self.append_basic_block("near_call_success_block");
             let catch_block = self.append_basic_block("near_call_catch_block");
             let current_block = self.basic_block();
 
             self.set_basic_block(catch_block);
-            let landing_pad_type = self.structure_type(&[
-                self.ptr_type(AddressSpace::Generic.into())
-                    .as_basic_type_enum(),
-                self.integer_type(era_compiler_common::BIT_LENGTH_BOOLEAN)
-                    .as_basic_type_enum(),
-            ]);
-            self.builder.build_landing_pad(
-                landing_pad_type,
-                self.llvm_runtime.personality.value,
-                &[self
-                    .ptr_type(AddressSpace::Stack.into())
-                    .const_zero()
-                    .as_basic_value_enum()],
-                false,
-                "near_call_catch_landing",
+            let landing_pad = LandingPad::build(self, "near_call_catch")?;
+            let handler_arguments = landing_pad.handler_arguments(self, handler)?;
+            self.build_call(
+                handler,
+                handler_arguments.as_slice(),
+                "near_call_catch_call",
             )?;
-            self.build_call(handler.borrow().declaration(), &[], "near_call_catch_call")?;
             self.build_unconditional_branch(join_block)?;
 
             self.set_basic_block(current_block);
@@ -478,50 +1161,31 @@ where
     ///
     /// Builds a memory copy call for the return data.
     ///
-    /// Sets the output length to `min(output_length, return_data_size` and calls the default
+    /// Sets the output length to `min(output_length, return_data_size)` and calls the default
     /// generic page memory copy builder.
     ///
+    /// The `return_data_size` is the one returned by `update_return_data` for the same call
+    /// result, so it is not extracted from the ABI pointer again.
+    ///
     pub fn build_memcpy_return_data(
         &self,
         function: FunctionDeclaration<'ctx>,
         destination: Pointer<'ctx, AddressSpace>,
         source: Pointer<'ctx, AddressSpace>,
         size: inkwell::values::IntValue<'ctx>,
+        return_data_size: inkwell::values::IntValue<'ctx>,
         name: &str,
     ) -> anyhow::Result<()> {
-        let pointer_casted = self.builder.build_ptr_to_int(
-            source.value,
-            self.field_type(),
-            format!("{name}_pointer_casted").as_str(),
-        )?;
-        let return_data_size_shifted = self.builder.build_right_shift(
-            pointer_casted,
-            self.field_const((era_compiler_common::BIT_LENGTH_X32 * 3) as u64),
-            false,
-            format!("{name}_return_data_size_shifted").as_str(),
-        )?;
-        let return_data_size_truncated = self.builder.build_and(
-            return_data_size_shifted,
-            self.field_const(u32::MAX as u64),
-            format!("{name}_return_data_size_truncated").as_str(),
-        )?;
-        let is_return_data_size_lesser = self.builder.build_int_compare(
-            inkwell::IntPredicate::ULT,
-            return_data_size_truncated,
+        self.build_bounded_returndata_copy(
+            function,
+            destination,
+            source,
+            self.field_const(0),
             size,
-            format!("{name}_is_return_data_size_lesser").as_str(),
-        )?;
-        let min_size = self
-            .builder
-            .build_select(
-                is_return_data_size_lesser,
-                return_data_size_truncated,
-                size,
-                format!("{name}_min_size").as_str(),
-            )?
-            .into_int_value();
-
-        self.build_memcpy(function, destination, source, min_size, name)
+            return_data_size,
+            ReturnDataCopyPolicy::Clamp,
+            name,
+        )
     }
 
     ///
@@ -580,16 +1244,14 @@ where
     ///
     /// Writes the ABI data size to the global variable.
     ///
+    /// Returns the written size value.
+    ///
     pub fn write_abi_data_size(
         &mut self,
         pointer: Pointer<'ctx, AddressSpace>,
         global_name: &str,
-    ) -> anyhow::Result<()> {
-        let abi_pointer_value = self.builder().build_ptr_to_int(
-            pointer.value,
-            self.field_type(),
-            "abi_pointer_value",
-        )?;
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        let abi_pointer_value = pointer.to_int(self, "abi_pointer_value")?;
         let abi_pointer_value_shifted = self.builder().build_right_shift(
             abi_pointer_value,
             self.field_const((era_compiler_common::BIT_LENGTH_X32 * 3) as u64),
@@ -607,7 +1269,23 @@ where
             AddressSpace::Stack,
             abi_length_value,
         )?;
-        Ok(())
+        Ok(abi_length_value)
+    }
+
+    ///
+    /// Caches the return data pointer and size of an external call result in the globals.
+    ///
+    /// Must be called once per external call, so the size is extracted from the ABI pointer
+    /// only once and then reused by `returndatasize`, `returndatacopy` and the output copy.
+    ///
+    /// Returns the return data size.
+    ///
+    pub fn update_return_data(
+        &mut self,
+        pointer: Pointer<'ctx, AddressSpace>,
+    ) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        self.write_abi_pointer(pointer, crate::eravm::GLOBAL_RETURN_DATA_POINTER)?;
+        self.write_abi_data_size(pointer, crate::eravm::GLOBAL_RETURN_DATA_SIZE)
     }
 
     ///
@@ -820,11 +1498,171 @@ where
             solidity.immutables_size()
         } else if let Some(vyper) = self.vyper_data.as_ref() {
             vyper.immutables_size()
+        } else if let Some(layout) = self.immutables_deferred.as_ref() {
+            layout.size
         } else {
             panic!("The immutable size data is not available");
         }
     }
 
+    ///
+    /// Enables the deferred immutables mode.
+    ///
+    /// In this mode, the front-end may discover immutables at any point of lowering, e.g. after
+    /// the deploy code `return` is translated. The number of immutables returned by the deploy
+    /// code is read from a constant global, which is resolved in `prepare` after the whole
+    /// contract is lowered. Without the Solidity data, the identifiers are tracked by the context.
+    ///
+    pub fn enable_deferred_immutables(&mut self) -> anyhow::Result<()> {
+        if self
+            .get_global(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)
+            .is_err()
+        {
+            self.set_global(
+                crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED,
+                self.field_type(),
+                AddressSpace::Stack,
+                self.field_const(0),
+            )?;
+        }
+        if self.solidity_data.is_none() {
+            self.immutables_deferred = Some(ImmutablesLayout::default());
+        }
+        self.is_immutables_deferred = true;
+        Ok(())
+    }
+
+    ///
+    /// Whether the deferred immutables mode is enabled.
+    ///
+    pub fn is_immutables_deferred(&self) -> bool {
+        self.is_immutables_deferred
+    }
+
+    ///
+    /// Returns the offset of the immutable with `identifier`, allocating it if it is new.
+    ///
+    /// # Errors
+    /// If neither the Solidity data is set nor the deferred mode is enabled.
+    ///
+    pub fn get_or_allocate_immutable(&mut self, identifier: &str) -> anyhow::Result<usize> {
+        if let Some(solidity) = self.solidity_data.as_mut() {
+            Ok(solidity.get_or_allocate_immutable(identifier))
+        } else if let Some(layout) = self.immutables_deferred.as_mut() {
+            Ok(layout.allocate(identifier))
+        } else {
+            anyhow::bail!(
+                "immutable `{identifier}` cannot be allocated without the Solidity data or the deferred mode"
+            )
+        }
+    }
+
+    ///
+    /// Returns the immutables layout, or `None` if neither Solidity nor Vyper data is set, and
+    /// the deferred mode is disabled.
+    ///
+    pub fn immutables_layout(&self) -> Option<ImmutablesLayout> {
+        if let Some(solidity) = self.solidity_data.as_ref() {
+            Some(solidity.immutables_layout().to_owned())
+        } else if let Some(vyper) = self.vyper_data.as_ref() {
+            Some(ImmutablesLayout::new_anonymous(vyper.immutables_size()))
+        } else {
+            self.immutables_deferred.clone()
+        }
+    }
+
+    ///
+    /// Sets whether the target VM version supports transient storage.
+    ///
+    /// If it does not, the transient storage instructions are reported as errors.
+    ///
+    pub fn set_transient_storage_supported(&mut self, is_supported: bool) {
+        self.is_transient_storage_supported = is_supported;
+    }
+
+    ///
+    /// Whether the target VM version supports transient storage.
+    ///
+    pub fn is_transient_storage_supported(&self) -> bool {
+        self.is_transient_storage_supported
+    }
+
+    ///
+    /// Sets whether the unoptimized LLVM IR text is saved to the build.
+    ///
+    /// Allows attaching reproducible IR to bug reports without the debug config file dumps.
+    ///
+    pub fn set_llvm_ir_unoptimized_kept(&mut self, is_kept: bool) {
+        self.is_llvm_ir_unoptimized_kept = is_kept;
+    }
+
+    ///
+    /// Sets whether the active pointer indexes are checked against the active pointer array size.
+    ///
+    /// Catches the front-end bugs which would otherwise corrupt the adjacent globals.
+    ///
+    /// Constant indexes are checked at compile time for free. Every access with a runtime index
+    /// costs a comparison and a branch, e.g. `swap` adds four checks, as it loads and stores both
+    /// pointers. The revert block is shared by all the checks of a function.
+    ///
+    pub fn set_active_pointer_bound_checked(&mut self, is_checked: bool) {
+        self.is_active_pointer_bound_checked = is_checked;
+    }
+
+    ///
+    /// Whether the active pointer indexes are checked against the active pointer array size.
+    ///
+    pub fn is_active_pointer_bound_checked(&self) -> bool {
+        self.is_active_pointer_bound_checked
+    }
+
+    ///
+    /// Sets the target CPU and features, e.g. to select an EraVM protocol version.
+    ///
+    pub fn set_target_options(&mut self, target_options: TargetOptions) {
+        self.target_options = target_options;
+    }
+
+    ///
+    /// Sets the build cache provided by the embedding compiler.
+    ///
+    pub fn set_build_cache(&mut self, build_cache: Option<Arc<dyn BuildCache>>) {
+        self.build_cache = build_cache;
+    }
+
+    ///
+    /// Sets the assembly post-processing hook provided by the embedding compiler.
+    ///
+    /// If the hook is set, the assembly is always emitted and passed through the hook before
+    /// assembling, and the processed assembly is saved to the build.
+    ///
+    pub fn set_assembly_hook(&mut self, assembly_hook: Option<Arc<dyn AssemblyHook>>) {
+        self.assembly_hook = assembly_hook;
+    }
+
+    ///
+    /// Registers `callback` as the translator of calls to the custom simulation `address`.
+    ///
+    /// Returns an error if the address is already registered.
+    ///
+    pub fn register_simulation<F>(&mut self, address: u16, callback: F) -> anyhow::Result<()>
+    where
+        F: Fn(
+                &mut Self,
+                SimulationArguments<'ctx>,
+            ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+            + 'ctx,
+    {
+        self.simulation_registry.register(address, callback)
+    }
+
+    ///
+    /// Returns the custom simulation registered for `address`.
+    ///
+    pub fn simulation(&self, address: u16) -> Option<SimulationCallback<'ctx, D>> {
+        self.simulation_registry.get(address)
+    }
+
     ///
     /// Whether the EraVM extensions are enabled.
     ///
@@ -868,10 +1706,31 @@ where
         &self.debug_info
     }
 
+    fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics.as_slice()
+    }
+
+    fn push_diagnostic(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn optimizer(&self) -> &Optimizer {
+        &self.optimizer
+    }
+
     fn debug_config(&self) -> Option<&DebugConfig> {
         self.debug_config.as_ref()
     }
 
+    fn intern_constant_bytes_in(
+        &mut self,
+        bytes: &[u8],
+        address_space: Self::AddressSpace,
+    ) -> Pointer<'ctx, Self::AddressSpace> {
+        self.constant_pool
+            .intern(self.llvm, &self.module, bytes, address_space)
+    }
+
     fn set_code_segment(&mut self, code_segment: era_compiler_common::CodeSegment) {
         self.code_segment = Some(code_segment);
     }
@@ -986,9 +1845,10 @@ where
 
     fn set_current_function(&mut self, name: &str) -> anyhow::Result<()> {
         let function = self.functions.get(name).cloned().ok_or_else(|| {
-            anyhow::anyhow!("Failed to activate an undeclared function `{}`", name)
+            suggestion::undeclared("function", name, self.functions.keys().map(String::as_str))
         })?;
         self.current_function = Some(function);
+        self.builder.unset_current_debug_location();
         Ok(())
     }
 
@@ -1012,21 +1872,22 @@ where
         arguments: &[inkwell::values::BasicMetadataValueEnum<'ctx>],
         name: &str,
     ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
-        let call_site_value = self.builder.build_indirect_call(
-            function.r#type,
-            function.value.as_global_value().as_pointer_value(),
-            arguments,
-            name,
-        )?;
-        if self.optimizer.settings().level_middle_end == inkwell::OptimizationLevel::None {
-            call_site_value.add_attribute(
-                inkwell::attributes::AttributeLoc::Function,
-                self.llvm
-                    .create_enum_attribute(Attribute::NoInline as u32, 0),
-            );
-        }
-        self.modify_call_site_value(arguments, call_site_value, function);
-        Ok(call_site_value.try_as_basic_value().left())
+        self.build_call_site(function, arguments, &[], name)
+    }
+
+    fn build_call_with_attributes(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: &[inkwell::values::BasicValueEnum<'ctx>],
+        attributes: &[Attribute],
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        let arguments: Vec<inkwell::values::BasicMetadataValueEnum> = arguments
+            .iter()
+            .copied()
+            .map(inkwell::values::BasicMetadataValueEnum::from)
+            .collect();
+        self.build_call_site(function, arguments.as_slice(), attributes, name)
     }
 
     fn build_invoke(
@@ -1055,22 +1916,7 @@ where
         let current_block = self.basic_block();
 
         self.set_basic_block(catch_block);
-        let landing_pad_type = self.structure_type(&[
-            self.ptr_type(AddressSpace::Generic.into())
-                .as_basic_type_enum(),
-            self.integer_type(era_compiler_common::BIT_LENGTH_BOOLEAN)
-                .as_basic_type_enum(),
-        ]);
-        self.builder.build_landing_pad(
-            landing_pad_type,
-            self.llvm_runtime.personality.value,
-            &[self
-                .ptr_type(AddressSpace::Stack.into())
-                .const_zero()
-                .as_basic_value_enum()],
-            false,
-            "invoke_catch_landing",
-        )?;
+        LandingPad::build(self, "invoke_catch")?;
         crate::eravm::utils::throw(self)?;
 
         self.set_basic_block(current_block);
@@ -1117,6 +1963,14 @@ where
         }
     }
 
+    fn build_empty_revert(&self) -> anyhow::Result<()> {
+        self.build_exit(
+            self.llvm_runtime().revert,
+            self.field_const(0),
+            self.field_const(0),
+        )
+    }
+
     fn set_solidity_data(&mut self, data: Self::SolidityData) {
         self.solidity_data = Some(data);
     }
@@ -0,0 +1,261 @@
+//!
+//! The optimized LLVM IR module.
+//!
+
+use std::time::Instant;
+
+use crate::context::IContext;
+use crate::eravm::Dependency;
+use crate::error::Error;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::settings::size_level::SizeLevel;
+use crate::profiler::Profiler;
+use crate::size_fallback::attempt::Attempt as SizeFallbackAttempt;
+use crate::source_map::SourceMap;
+use crate::target_machine_pool::pooled::Pooled as PooledTargetMachine;
+
+use super::build::Build;
+use super::function::Function;
+use super::metrics::Metrics;
+use super::Context;
+
+///
+/// The optimized LLVM IR module.
+///
+/// Produced by `Context::prepare`, it allows running the emission stages separately, and
+/// processing the module or the emitted buffers between them.
+///
+pub struct OptimizedModule<'ctx, D>
+where
+    D: Dependency,
+{
+    /// The context owning the optimized module.
+    context: Context<'ctx, D>,
+    /// The copy of the module before optimization, used by the size fallback.
+    module_unoptimized: inkwell::module::Module<'ctx>,
+    /// The target machine the module is optimized for.
+    target_machine: PooledTargetMachine<'static>,
+    /// The contract path.
+    contract_path: String,
+    /// The unoptimized LLVM IR text, if requested.
+    llvm_ir_unoptimized: Option<String>,
+    /// The stage durations and instruction counts.
+    metrics: Metrics,
+    /// The start of the build attempt.
+    start: Instant,
+}
+
+impl<'ctx, D> OptimizedModule<'ctx, D>
+where
+    D: Dependency,
+{
+    ///
+    /// A shortcut constructor.
+    ///
+    pub(crate) fn new(
+        context: Context<'ctx, D>,
+        module_unoptimized: inkwell::module::Module<'ctx>,
+        target_machine: PooledTargetMachine<'static>,
+        contract_path: String,
+        llvm_ir_unoptimized: Option<String>,
+        metrics: Metrics,
+        start: Instant,
+    ) -> Self {
+        Self {
+            context,
+            module_unoptimized,
+            target_machine,
+            contract_path,
+            llvm_ir_unoptimized,
+            metrics,
+            start,
+        }
+    }
+
+    ///
+    /// Returns the optimized LLVM IR module reference.
+    ///
+    pub fn module(&self) -> &inkwell::module::Module<'ctx> {
+        self.context.module()
+    }
+
+    ///
+    /// Returns the stage durations and instruction counts collected so far.
+    ///
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    ///
+    /// Emits the text assembly.
+    ///
+    /// The assembly is dumped if the debug config is set.
+    ///
+    pub fn emit_assembly(
+        &mut self,
+        profiler: &mut Profiler,
+    ) -> anyhow::Result<inkwell::memory_buffer::MemoryBuffer> {
+        let run = profiler.start_pipeline_element(
+            self.contract_path.as_str(),
+            self.context.code_segment,
+            "assembly emitting",
+            self.context.optimizer.settings(),
+        );
+        let assembly_buffer = self
+            .target_machine
+            .write_to_memory_buffer(self.module(), inkwell::targets::FileType::Assembly)
+            .map_err(|error| Error::Emitting(format!("assembly emitting: {error}")))?;
+        self.metrics.emitting += profiler.finish_pipeline_element(run);
+
+        if let Some(ref debug_config) = self.context.debug_config {
+            let assembly_text = String::from_utf8_lossy(assembly_buffer.as_slice());
+            debug_config.dump_assembly(
+                self.contract_path.as_str(),
+                None,
+                assembly_text.as_ref(),
+            )?;
+        }
+
+        Ok(assembly_buffer)
+    }
+
+    ///
+    /// Emits the bytecode object.
+    ///
+    /// If `assembly_buffer` is set, it is assembled. Otherwise, the object is emitted from the
+    /// module directly.
+    ///
+    pub fn emit_object(
+        &mut self,
+        assembly_buffer: Option<&inkwell::memory_buffer::MemoryBuffer>,
+        profiler: &mut Profiler,
+    ) -> anyhow::Result<inkwell::memory_buffer::MemoryBuffer> {
+        let run = profiler.start_pipeline_element(
+            self.contract_path.as_str(),
+            self.context.code_segment,
+            match assembly_buffer {
+                Some(_) => "assembling",
+                None => "bytecode emitting",
+            },
+            self.context.optimizer.settings(),
+        );
+        let bytecode_buffer = match assembly_buffer {
+            Some(assembly_buffer) => self
+                .target_machine
+                .assemble(assembly_buffer)
+                .map_err(|error| Error::Assembly(format!("assembling: {error}"))),
+            None => self
+                .target_machine
+                .write_to_memory_buffer(self.module(), inkwell::targets::FileType::Object)
+                .map_err(|error| Error::Emitting(format!("bytecode emitting: {error}"))),
+        }?;
+        let duration = profiler.finish_pipeline_element(run);
+        match assembly_buffer {
+            Some(_) => self.metrics.assembling += duration,
+            None => self.metrics.emitting += duration,
+        }
+        Ok(bytecode_buffer)
+    }
+
+    ///
+    /// Disassembles `bytecode_buffer` with the default options.
+    ///
+    pub fn disassemble(
+        &self,
+        bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    ) -> anyhow::Result<String> {
+        crate::eravm::disassemble(&self.target_machine, bytecode_buffer)
+    }
+
+    ///
+    /// Returns the EraVM bytecode size limit in instructions.
+    ///
+    pub fn size_limit(&self) -> usize {
+        self.context
+            .optimizer
+            .settings()
+            .eravm_bytecode_size_limit()
+    }
+
+    ///
+    /// Whether `bytecode_buffer` with the metadata of `metadata_size` bytes exceeds the size limit.
+    ///
+    pub fn exceeds_size_limit(
+        &self,
+        bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+        metadata_size: usize,
+    ) -> bool {
+        match self.context.optimizer.settings().eravm_bytecode_size_limit {
+            Some(_) => {
+                (bytecode_buffer.as_slice().len() + metadata_size)
+                    .div_ceil(era_compiler_common::BYTE_LENGTH_X64)
+                    > self.size_limit()
+            }
+            None => bytecode_buffer.exceeds_size_limit_eravm(metadata_size),
+        }
+    }
+
+    ///
+    /// Packs the bytecode with the metadata and the auxiliary data into the build.
+    ///
+    /// The size limit is not checked here, so it must be done with `exceeds_size_limit` if needed.
+    ///
+    pub fn finalize(
+        self,
+        bytecode_buffer: inkwell::memory_buffer::MemoryBuffer,
+        assembly_buffer: Option<inkwell::memory_buffer::MemoryBuffer>,
+        metadata: &MetadataBuilder,
+    ) -> anyhow::Result<Build> {
+        let attempt = self.size_fallback_attempt(&bytecode_buffer);
+        let size_limit = self.size_limit();
+
+        let assembly_text = assembly_buffer
+            .map(|assembly_buffer| String::from_utf8_lossy(assembly_buffer.as_slice()).to_string());
+        let source_map = assembly_text.as_deref().and_then(|assembly_text| {
+            SourceMap::from_assembly(assembly_text, |_mnemonic| {
+                era_compiler_common::BYTE_LENGTH_X64
+            })
+        });
+
+        let mut build = crate::eravm::build(bytecode_buffer, metadata, assembly_text)?;
+        build.size_fallback.attempts.push(attempt);
+        build.size_fallback.limit = Some(size_limit * era_compiler_common::BYTE_LENGTH_X64);
+        build.metrics = self.metrics;
+        build.source_map = source_map;
+        build.immutables_layout = self.context.immutables_layout();
+        build.diagnostics = self.context.diagnostics;
+        build.llvm_ir_unoptimized = self.llvm_ir_unoptimized;
+        Ok(build)
+    }
+
+    ///
+    /// Returns the size fallback report entry of the attempt which produced `bytecode_buffer`.
+    ///
+    pub(crate) fn size_fallback_attempt(
+        &self,
+        bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    ) -> SizeFallbackAttempt {
+        SizeFallbackAttempt::new(
+            self.context.optimizer.settings().to_string(),
+            bytecode_buffer.as_slice().len(),
+            self.start.elapsed(),
+        )
+    }
+
+    ///
+    /// Returns the context with the unoptimized module and the settings chosen by the optimizer
+    /// fallback policy, or `None` if there are no attempts left.
+    ///
+    pub(crate) fn into_fallback_context(mut self) -> Option<Context<'ctx, D>> {
+        let fallback_settings = self.context.optimizer.fallback_settings(self.module())?;
+        let is_size_level_set = fallback_settings.level_middle_end_size != SizeLevel::Zero;
+        self.context.optimizer.set_settings(fallback_settings);
+        self.context.module = self.module_unoptimized;
+        if is_size_level_set {
+            for function in self.context.module.get_functions() {
+                Function::set_size_attributes(self.context.llvm, function);
+            }
+        }
+        Some(self.context)
+    }
+}
@@ -0,0 +1,25 @@
+//!
+//! The `selfdestruct` instruction lowering policy.
+//!
+
+///
+/// The `selfdestruct` instruction lowering policy.
+///
+/// EraVM has no native `selfdestruct`, so its lowering is chosen by the front-end.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum SelfdestructPolicy {
+    /// The instruction is reported as an error.
+    #[default]
+    Error,
+    /// The designated system contract is called with the beneficiary address, and the execution
+    /// is stopped.
+    SystemCall {
+        /// The system contract address.
+        address: u16,
+        /// The system contract method signature, e.g. `selfdestruct(address)`.
+        signature: String,
+    },
+    /// The execution is stopped without destroying the contract, and a warning is emitted.
+    Stop,
+}
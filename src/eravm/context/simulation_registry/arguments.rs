@@ -0,0 +1,47 @@
+//!
+//! The custom simulation call arguments.
+//!
+
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+
+///
+/// The custom simulation call arguments.
+///
+/// The operands are passed as they are written in the simulated call, so their meaning is
+/// defined by the simulation itself.
+///
+#[derive(Debug, Clone)]
+pub struct Arguments<'ctx> {
+    /// The call type, e.g. the `far_call` or `static_call` runtime function.
+    pub function: FunctionDeclaration<'ctx>,
+    /// The gas operand.
+    pub gas: inkwell::values::IntValue<'ctx>,
+    /// The address operand, which is the simulation address itself.
+    pub address: inkwell::values::IntValue<'ctx>,
+    /// The value operand, which is only present in non-static calls.
+    pub value: Option<inkwell::values::IntValue<'ctx>>,
+    /// The input offset operand.
+    pub input_offset: inkwell::values::IntValue<'ctx>,
+    /// The input length operand.
+    pub input_length: inkwell::values::IntValue<'ctx>,
+    /// The output offset operand.
+    pub output_offset: inkwell::values::IntValue<'ctx>,
+    /// The output length operand.
+    pub output_length: inkwell::values::IntValue<'ctx>,
+    /// The compile-time known operands, with the simulation address already taken.
+    pub constants: Vec<Option<num::BigUint>>,
+}
+
+impl<'ctx> Arguments<'ctx> {
+    ///
+    /// Returns the value operand required by the simulation `name`.
+    ///
+    /// The operand is absent in static calls, so a simulation with the wrong call type is
+    /// reported as an error.
+    ///
+    pub fn value_operand(&self, name: &str) -> anyhow::Result<inkwell::values::IntValue<'ctx>> {
+        self.value.ok_or_else(|| {
+            anyhow::anyhow!("Simulation `{name}` requires the value operand of a non-static call")
+        })
+    }
+}
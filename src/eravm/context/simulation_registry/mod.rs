@@ -0,0 +1,90 @@
+//!
+//! The custom simulation registry.
+//!
+
+pub mod arguments;
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+use self::arguments::Arguments;
+
+///
+/// The custom simulation callback.
+///
+pub type Callback<'ctx, D> = Rc<
+    dyn Fn(
+            &mut Context<'ctx, D>,
+            Arguments<'ctx>,
+        ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+        + 'ctx,
+>;
+
+///
+/// The custom simulation registry.
+///
+/// Allows the embedding compiler to translate calls to additional simulation addresses, e.g. to
+/// prototype experimental VM extensions. The registered simulations are only checked if the
+/// EraVM extensions are enabled and the address is not one of the built-in simulations, which
+/// always take precedence.
+///
+pub struct SimulationRegistry<'ctx, D>
+where
+    D: Dependency,
+{
+    /// The callbacks indexed by the simulation address.
+    callbacks: BTreeMap<u16, Callback<'ctx, D>>,
+}
+
+impl<'ctx, D> Default for SimulationRegistry<'ctx, D>
+where
+    D: Dependency,
+{
+    fn default() -> Self {
+        Self {
+            callbacks: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'ctx, D> SimulationRegistry<'ctx, D>
+where
+    D: Dependency,
+{
+    ///
+    /// Registers `callback` as the translator of calls to `address`.
+    ///
+    /// Returns an error if the address is already registered.
+    ///
+    pub fn register<F>(&mut self, address: u16, callback: F) -> anyhow::Result<()>
+    where
+        F: Fn(
+                &mut Context<'ctx, D>,
+                Arguments<'ctx>,
+            ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+            + 'ctx,
+    {
+        if self.callbacks.contains_key(&address) {
+            anyhow::bail!("Simulation address 0x{address:04x} is already registered");
+        }
+        self.callbacks.insert(address, Rc::new(callback));
+        Ok(())
+    }
+
+    ///
+    /// Returns the callback registered for `address`.
+    ///
+    pub fn get(&self, address: u16) -> Option<Callback<'ctx, D>> {
+        self.callbacks.get(&address).cloned()
+    }
+
+    ///
+    /// Returns the registered simulation addresses in ascending order.
+    ///
+    pub fn addresses(&self) -> impl Iterator<Item = u16> + '_ {
+        self.callbacks.keys().copied()
+    }
+}
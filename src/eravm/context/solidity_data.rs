@@ -2,7 +2,8 @@
 //! The LLVM IR generator Solidity data.
 //!
 
-use std::collections::BTreeMap;
+use crate::eravm::context::immutables_layout::ImmutablesLayout;
+use crate::eravm::context::selfdestruct_policy::SelfdestructPolicy;
 
 ///
 /// The LLVM IR generator Solidity data.
@@ -11,9 +12,11 @@ use std::collections::BTreeMap;
 ///
 #[derive(Debug, Default)]
 pub struct SolidityData {
-    /// The immutables identifier-to-offset mapping. Is only used by Solidity due to
+    /// The immutables layout. The identifiers are only tracked for Solidity due to
     /// the arbitrariness of its identifiers.
-    immutables: BTreeMap<String, usize>,
+    immutables: ImmutablesLayout,
+    /// The `selfdestruct` instruction lowering policy.
+    selfdestruct_policy: SelfdestructPolicy,
 }
 
 impl SolidityData {
@@ -28,7 +31,14 @@ impl SolidityData {
     /// Returns the current number of immutables values in the contract.
     ///
     pub fn immutables_size(&self) -> usize {
-        self.immutables.len() * era_compiler_common::BYTE_LENGTH_FIELD
+        self.immutables.size
+    }
+
+    ///
+    /// Returns the immutables layout.
+    ///
+    pub fn immutables_layout(&self) -> &ImmutablesLayout {
+        &self.immutables
     }
 
     ///
@@ -37,12 +47,7 @@ impl SolidityData {
     /// If the identifier is already known, just returns its offset.
     ///
     pub fn allocate_immutable(&mut self, identifier: &str) -> usize {
-        let number_of_elements = self.immutables.len();
-        let new_offset = number_of_elements * era_compiler_common::BYTE_LENGTH_FIELD;
-        *self
-            .immutables
-            .entry(identifier.to_owned())
-            .or_insert(new_offset)
+        self.immutables.allocate(identifier)
     }
 
     ///
@@ -51,9 +56,23 @@ impl SolidityData {
     /// If the value is not yet allocated, then it is done forcibly.
     ///
     pub fn get_or_allocate_immutable(&mut self, identifier: &str) -> usize {
-        match self.immutables.get(identifier).copied() {
+        match self.immutables.get(identifier) {
             Some(offset) => offset,
             None => self.allocate_immutable(identifier),
         }
     }
+
+    ///
+    /// Sets the `selfdestruct` instruction lowering policy.
+    ///
+    pub fn set_selfdestruct_policy(&mut self, policy: SelfdestructPolicy) {
+        self.selfdestruct_policy = policy;
+    }
+
+    ///
+    /// Returns the `selfdestruct` instruction lowering policy.
+    ///
+    pub fn selfdestruct_policy(&self) -> &SelfdestructPolicy {
+        &self.selfdestruct_policy
+    }
 }
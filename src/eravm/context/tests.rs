@@ -2,23 +2,75 @@
 //! The LLVM IR generator context tests.
 //!
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use inkwell::values::BasicValue;
+
+use crate::cancellation_handle::CancellationHandle;
 use crate::context::attribute::Attribute;
+use crate::context::function::block::key::Key as BlockKey;
+use crate::context::pointer::Pointer;
+use crate::context::return_data_copy_policy::ReturnDataCopyPolicy;
 use crate::context::IContext;
+use crate::debug_config::DebugConfig;
 use crate::dependency::DummyDependency;
+use crate::eravm::context::active_pointer::ActivePointer;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::assembly_hook::AssemblyHook;
+use crate::eravm::context::emit_request::EmitRequest;
+use crate::eravm::context::entry_abi::EntryAbi;
+use crate::eravm::context::function::runtime::entry::Entry;
+use crate::eravm::context::function::runtime::Runtime;
+use crate::eravm::context::yul_data::YulData;
 use crate::eravm::context::Context;
+use crate::eravm::WriteLLVM;
+use crate::error::Error;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::fallback_policy::FallbackPolicy;
+use crate::optimizer::settings::size_level::SizeLevel;
 use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::optimizer::stage::Stage;
 use crate::optimizer::Optimizer;
+use crate::profiler::Profiler;
+use crate::target_machine::options::Options as TargetOptions;
+use crate::target_machine::TargetMachine;
+use crate::testing::file_check::FileCheck;
 
 pub fn create_context(
     llvm: &inkwell::context::Context,
     optimizer_settings: OptimizerSettings,
 ) -> Context<DummyDependency> {
-    crate::eravm::initialize_target();
-
-    let module = llvm.create_module("test");
-    let optimizer = Optimizer::new(optimizer_settings);
+    crate::testing::create_eravm_context(llvm, optimizer_settings)
+}
 
-    Context::<_>::new(&llvm, module, vec![], optimizer, None)
+pub fn add_void_function(
+    context: &mut Context<DummyDependency>,
+    name: &str,
+    linkage: inkwell::module::Linkage,
+) {
+    let function = context
+        .add_function(
+            name,
+            context.void_type().fn_type(&[], false),
+            0,
+            Some(linkage),
+        )
+        .expect("Failed to add function");
+    let entry_block = function.borrow().entry_block();
+    let return_block = function.borrow().return_block();
+    context.set_basic_block(entry_block);
+    context
+        .build_unconditional_branch(return_block)
+        .expect("Failed to build the branch");
+    context.set_basic_block(return_block);
+    context
+        .builder()
+        .build_return(None)
+        .expect("Failed to build the return");
 }
 
 #[test]
@@ -135,3 +187,1934 @@ pub fn check_attribute_min_size_mode_z() {
         .attributes(inkwell::attributes::AttributeLoc::Function)
         .contains(&llvm.create_enum_attribute(Attribute::MinSize as u32, 0)));
 }
+
+#[test]
+pub fn check_terminators_unterminated_block() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+
+    context
+        .add_function(
+            "test",
+            context
+                .field_type()
+                .fn_type(&[context.field_type().into()], false),
+            1,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    let error = context
+        .check_terminators()
+        .expect_err("Unterminated blocks must be reported");
+    assert!(error.to_string().contains("function `test`"));
+}
+
+#[test]
+pub fn check_field_const_big_overflow() {
+    let llvm = inkwell::context::Context::create();
+    let context = create_context(&llvm, OptimizerSettings::cycles());
+
+    let maximum = (num::BigUint::from(1u8) << era_compiler_common::BIT_LENGTH_FIELD) - 1u8;
+    assert!(context.field_const_big(&maximum).is_ok());
+    assert!(context.field_const_big(&(maximum + 1u8)).is_err());
+}
+
+#[test]
+pub fn check_math_512() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+
+    let function = context
+        .add_function(
+            "test",
+            context.void_type().fn_type(
+                &[
+                    context.field_type().into(),
+                    context.field_type().into(),
+                    context.field_type().into(),
+                ],
+                false,
+            ),
+            0,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    let (value, entry_block, return_block) = {
+        let function = function.borrow();
+        (
+            function.declaration().value,
+            function.entry_block(),
+            function.return_block(),
+        )
+    };
+    let operand_1 = value
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let operand_2 = value
+        .get_nth_param(1)
+        .expect("Always exists")
+        .into_int_value();
+    let operand_3 = value
+        .get_nth_param(2)
+        .expect("Always exists")
+        .into_int_value();
+
+    context.set_basic_block(entry_block);
+    let product = crate::eravm::extensions::math::mul_512(&mut context, operand_1, operand_2)
+        .expect("Failed to build the multiplication");
+    crate::eravm::extensions::math::div_512_by_256(&mut context, product, operand_3)
+        .expect("Failed to build the division");
+    crate::eravm::extensions::math::mulmod_512(&mut context, operand_1, operand_2, operand_3)
+        .expect("Failed to build the modular multiplication");
+    context
+        .build_unconditional_branch(return_block)
+        .expect("Failed to build the branch");
+    context.set_basic_block(return_block);
+    context
+        .builder()
+        .build_return(None)
+        .expect("Failed to build the return");
+
+    context.verify().expect("The module must be valid");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %mul_512_operand_1_extended = zext i256 %0 to i512
+; CHECK-NEXT: %mul_512_operand_2_extended = zext i256 %1 to i512
+; CHECK-NEXT: %mul_512_result_extended = mul i512 %mul_512_operand_1_extended, %mul_512_operand_2_extended
+; CHECK-NEXT: %mul_512_result_high_shifted = lshr i512 %mul_512_result_extended, 256
+; CHECK-NEXT: %mul_512_result_high = trunc i512 %mul_512_result_high_shifted to i256
+; CHECK-NEXT: %mul_512_result_low = trunc i512 %mul_512_result_extended to i256
+; CHECK-NEXT: %div_512_by_256_dividend_high_extended = zext i256 %mul_512_result_high to i512
+; CHECK-NEXT: %div_512_by_256_dividend_low_extended = zext i256 %mul_512_result_low to i512
+; CHECK-NEXT: %div_512_by_256_dividend_high_shifted = shl i512 %div_512_by_256_dividend_high_extended, 256
+; CHECK-NEXT: %div_512_by_256_dividend = or i512 %div_512_by_256_dividend_high_shifted, %div_512_by_256_dividend_low_extended
+; CHECK-NEXT: %div_512_by_256_divisor_is_zero = icmp eq i256 %2, 0
+; CHECK-NEXT: %div_512_by_256_divisor_non_zero = select i1 %div_512_by_256_divisor_is_zero, i256 1, i256 %2
+; CHECK-NEXT: %div_512_by_256_divisor_extended = zext i256 %div_512_by_256_divisor_non_zero to i512
+; CHECK-NEXT: %div_512_by_256_quotient_extended = udiv i512 %div_512_by_256_dividend, %div_512_by_256_divisor_extended
+; CHECK-NEXT: %div_512_by_256_remainder_extended = urem i512 %div_512_by_256_dividend, %div_512_by_256_divisor_extended
+; CHECK-NEXT: %div_512_by_256_quotient = select i1 %div_512_by_256_divisor_is_zero, i512 0, i512 %div_512_by_256_quotient_extended
+; CHECK-NEXT: %div_512_by_256_remainder = select i1 %div_512_by_256_divisor_is_zero, i512 0, i512 %div_512_by_256_remainder_extended
+; CHECK: mul i512
+; CHECK: icmp eq i256 %2, 0
+; CHECK: urem i512
+; CHECK: ret void
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "test", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_const_hash() {
+    assert_eq!(
+        crate::const_hash::keccak256(b"abc"),
+        [
+            0x4e, 0x03, 0x65, 0x7a, 0xea, 0x45, 0xa9, 0x4f, 0xc7, 0xd4, 0x7b, 0xa8, 0x26, 0xc8,
+            0xd6, 0x67, 0xc0, 0xd1, 0xe6, 0xe3, 0x3a, 0x64, 0xa0, 0x36, 0xec, 0x44, 0xf5, 0x8f,
+            0xa1, 0x2d, 0x6c, 0x45,
+        ]
+    );
+    assert_eq!(
+        crate::const_hash::sha256(b"abc"),
+        [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ]
+    );
+}
+
+#[test]
+pub fn check_sha3_empty_input_folding() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+
+    let hash = crate::eravm::evm::crypto::sha3(
+        &mut context,
+        context.field_const(0x80),
+        context.field_const(0),
+    )
+    .expect("Failed to build the hash");
+    assert_eq!(
+        hash.into_int_value(),
+        context.field_const_str_hex(
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        )
+    );
+}
+
+#[test]
+pub fn check_sha3_constant_memory_folding() {
+    let llvm = inkwell::context::Context::create();
+    let mut optimizer_settings = OptimizerSettings::cycles();
+    optimizer_settings.enable_hash_constant_folding();
+    let mut context = create_context(&llvm, optimizer_settings);
+
+    let function = context
+        .add_function(
+            "test",
+            context.void_type().fn_type(&[], false),
+            0,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    let entry_block = function.borrow().entry_block();
+    context.set_basic_block(entry_block);
+
+    crate::eravm::evm::memory::store(&mut context, context.field_const(0), context.field_const(1))
+        .expect("Failed to build the store");
+    crate::eravm::evm::memory::store(
+        &mut context,
+        context.field_const(era_compiler_common::BYTE_LENGTH_FIELD as u64),
+        context.field_const(2),
+    )
+    .expect("Failed to build the store");
+    let hash = crate::eravm::evm::crypto::sha3(
+        &mut context,
+        context.field_const(0),
+        context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 2) as u64),
+    )
+    .expect("Failed to build the hash");
+
+    let mut input = vec![0u8; era_compiler_common::BYTE_LENGTH_FIELD * 2];
+    input[era_compiler_common::BYTE_LENGTH_FIELD - 1] = 1;
+    input[era_compiler_common::BYTE_LENGTH_FIELD * 2 - 1] = 2;
+    let expected = crate::const_hash::keccak256(input.as_slice());
+    assert_eq!(
+        hash.into_int_value(),
+        context
+            .field_const_big(&num::BigUint::from_bytes_be(expected.as_slice()))
+            .expect("Always valid")
+    );
+}
+
+#[test]
+pub fn check_sha3_constant_memory_folding_pointer_offsets() {
+    let llvm = inkwell::context::Context::create();
+    let mut optimizer_settings = OptimizerSettings::cycles();
+    optimizer_settings.enable_hash_constant_folding();
+    let mut context = create_context(&llvm, optimizer_settings);
+    crate::testing::add_function(&mut context, "test", 0, 0).expect("Failed to add function");
+
+    let base = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Heap,
+        context.field_type(),
+        context.field_const(64),
+        "base",
+    )
+    .expect("Always valid");
+    let word = num::BigUint::from(1u8) << 200;
+    context
+        .build_store(base, context.field_const(3))
+        .expect("Failed to build the store");
+    context
+        .build_store(
+            base.with_offset_checked(
+                &context,
+                context.field_const(era_compiler_common::BYTE_LENGTH_FIELD as u64),
+                "next",
+            )
+            .expect("Always valid"),
+            context.field_const_big(&word).expect("Always valid"),
+        )
+        .expect("Failed to build the store");
+    let length = context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 2) as u64);
+    let hash = context
+        .fold_constant_hash(AddressSpace::Heap, context.field_const(64), length)
+        .expect("Must be folded");
+
+    let mut input = vec![0u8; era_compiler_common::BYTE_LENGTH_FIELD];
+    input[era_compiler_common::BYTE_LENGTH_FIELD - 1] = 3;
+    let word = word.to_bytes_be();
+    input.extend(std::iter::repeat(0).take(era_compiler_common::BYTE_LENGTH_FIELD - word.len()));
+    input.extend(word);
+    let expected = crate::const_hash::keccak256(input.as_slice());
+    assert_eq!(
+        hash,
+        context
+            .field_const_big(&num::BigUint::from_bytes_be(expected.as_slice()))
+            .expect("Always valid")
+    );
+
+    context
+        .build_store(
+            base.with_offset_checked(&context, context.field_const(1), "unaligned")
+                .expect("Always valid")
+                .cast(context.byte_type()),
+            context.byte_type().const_int(0xff, false),
+        )
+        .expect("Failed to build the store");
+    assert!(context
+        .fold_constant_hash(AddressSpace::Heap, context.field_const(64), length)
+        .is_none());
+}
+
+#[test]
+pub fn check_declare_intrinsic() {
+    let llvm = inkwell::context::Context::create();
+    let context = create_context(&llvm, OptimizerSettings::cycles());
+
+    let r#type = context
+        .field_type()
+        .fn_type(&[context.field_type().into()], false);
+    let declaration = context
+        .declare_intrinsic("llvm.ctpop", r#type)
+        .expect("Failed to declare the intrinsic");
+    let declaration_cached = context
+        .declare_intrinsic("llvm.ctpop", r#type)
+        .expect("Failed to declare the intrinsic");
+    assert_eq!(declaration.value, declaration_cached.value);
+
+    let invalid_type = context.field_type().fn_type(
+        &[context.field_type().into(), context.field_type().into()],
+        false,
+    );
+    assert!(context
+        .declare_intrinsic("llvm.ctpop", invalid_type)
+        .is_err());
+    assert!(context
+        .declare_intrinsic("llvm.eravm.nonexistent", r#type)
+        .is_err());
+}
+
+#[test]
+pub fn check_llvm_runtime_external_function() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+
+    let r#type = context
+        .field_type()
+        .fn_type(&[context.field_type().into()], false);
+    context
+        .register_llvm_runtime_function(
+            "__custom_declared",
+            r#type,
+            vec![(Attribute::NoFree, None)],
+            None,
+        )
+        .expect("Failed to register the declared function");
+    context
+        .register_llvm_runtime_function(
+            "__custom_defined",
+            r#type,
+            vec![],
+            Some("define i256 @__custom_defined(i256 %value) {\n  ret i256 %value\n}\n"),
+        )
+        .expect("Failed to register the defined function");
+    assert!(context
+        .llvm_runtime()
+        .external("__custom_defined")
+        .is_some_and(|function| function.value.count_basic_blocks() > 0));
+
+    assert!(context
+        .register_llvm_runtime_function("custom", r#type, vec![], None)
+        .is_err());
+    assert!(context
+        .register_llvm_runtime_function("__custom_declared", r#type, vec![], None)
+        .is_err());
+}
+
+#[test]
+pub fn check_bitcode_roundtrip() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    let bitcode = context.write_bitcode();
+
+    let context = Context::<DummyDependency>::from_bitcode(
+        &llvm,
+        &bitcode,
+        vec![],
+        Optimizer::new(OptimizerSettings::cycles()),
+        None,
+    )
+    .expect("Failed to load the bitcode");
+    assert!(context.module().get_function("test").is_some());
+    assert!(context.module().get_function("__div.1").is_none());
+    context.verify().expect("The module must be valid");
+}
+
+#[test]
+pub fn check_build_cache_key() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+
+    let key = context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false);
+    assert!(key.is_some());
+    assert_eq!(
+        key,
+        context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+    );
+    assert_ne!(
+        key,
+        context.build_cache_key(&MetadataBuilder::none(), EmitRequest::new(true), false)
+    );
+    assert_ne!(
+        key,
+        context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), true)
+    );
+
+    context
+        .add_function(
+            "test",
+            context.void_type().fn_type(&[], false),
+            0,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    assert_ne!(
+        key,
+        context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+    );
+}
+
+#[test]
+pub fn check_build_cache_key_options() {
+    struct Hook;
+    impl AssemblyHook for Hook {
+        fn process(&self, _contract_path: &str, assembly: String) -> anyhow::Result<String> {
+            Ok(assembly)
+        }
+    }
+
+    let llvm = inkwell::context::Context::create();
+    let key = |context: &Context<DummyDependency>| {
+        context
+            .build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+            .expect("Always keyed")
+    };
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    let default = key(&context);
+    context.set_symbol_renames(BTreeMap::from([("a".to_owned(), "b".to_owned())]));
+    let renamed = key(&context);
+    assert_ne!(default, renamed);
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    context.set_llvm_ir_unoptimized_kept(true);
+    let kept = key(&context);
+    assert_ne!(default, kept);
+    assert_ne!(renamed, kept);
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    context
+        .register_simulation(0x1234, |context, _arguments| {
+            Ok(context.field_const(0).as_basic_value_enum())
+        })
+        .expect("Always valid");
+    assert_ne!(default, key(&context));
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    context
+        .enable_deferred_immutables()
+        .expect("Failed to enable");
+    let deferred = key(&context);
+    context
+        .get_or_allocate_immutable("a")
+        .expect("Always deferred");
+    assert_ne!(deferred, key(&context));
+
+    let context = Context::<DummyDependency>::new(
+        &llvm,
+        llvm.create_module("test"),
+        vec![],
+        Optimizer::new(OptimizerSettings::cycles()),
+        Some(DebugConfig::new(PathBuf::from("debug"))),
+    );
+    assert!(context
+        .build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+        .is_none());
+
+    let mut optimizer = Optimizer::new(OptimizerSettings::cycles());
+    optimizer.register_callback(
+        "callback",
+        Stage::PreOptimization,
+        Arc::new(|_module: &inkwell::module::Module| -> anyhow::Result<()> { Ok(()) }),
+    );
+    let context =
+        Context::<DummyDependency>::new(&llvm, llvm.create_module("test"), vec![], optimizer, None);
+    assert!(context
+        .build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+        .is_none());
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    context.set_assembly_hook(Some(Arc::new(Hook)));
+    assert!(context
+        .build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+        .is_none());
+}
+
+#[test]
+pub fn check_immutables_layout() {
+    let mut solidity = crate::eravm::context::solidity_data::SolidityData::new();
+    assert_eq!(solidity.allocate_immutable("a"), 0);
+    assert_eq!(
+        solidity.get_or_allocate_immutable("b"),
+        era_compiler_common::BYTE_LENGTH_FIELD
+    );
+    assert_eq!(solidity.allocate_immutable("a"), 0);
+
+    let layout = solidity.immutables_layout();
+    assert_eq!(layout.size, 2 * era_compiler_common::BYTE_LENGTH_FIELD);
+    assert_eq!(
+        layout.get("b"),
+        Some(era_compiler_common::BYTE_LENGTH_FIELD)
+    );
+    assert_eq!(layout.get("c"), None);
+}
+
+#[test]
+pub fn check_deferred_immutables() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    assert!(context.get_or_allocate_immutable("a").is_err());
+
+    context.enable_deferred_immutables().expect("Always valid");
+    assert!(context.is_immutables_deferred());
+    assert_eq!(
+        context
+            .get_or_allocate_immutable("a")
+            .expect("Always valid"),
+        0
+    );
+    assert_eq!(
+        context
+            .get_or_allocate_immutable("b")
+            .expect("Always valid"),
+        era_compiler_common::BYTE_LENGTH_FIELD
+    );
+    assert_eq!(
+        context.immutables_size(),
+        2 * era_compiler_common::BYTE_LENGTH_FIELD
+    );
+    assert!(context
+        .get_global(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)
+        .is_ok());
+}
+
+#[test]
+pub fn check_emit_request() {
+    let request = EmitRequest::new(true) | EmitRequest::LLVM_IR;
+    assert!(request.contains(EmitRequest::OBJECT | EmitRequest::ASSEMBLY));
+    assert!(request.contains(EmitRequest::LLVM_IR));
+    assert!(!request.contains(EmitRequest::BITCODE));
+    assert!(!EmitRequest::object().contains(EmitRequest::ASSEMBLY));
+}
+
+#[test]
+pub fn check_evmla_function_builder() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+
+    let key = BlockKey::new(era_compiler_common::CodeSegment::Runtime, 1_u32.into());
+    let mut builder = context.evmla_function_builder(4);
+    let first = builder.add_block(key.clone(), [1; era_compiler_common::BYTE_LENGTH_FIELD]);
+    let second = builder.add_block(key.clone(), [2; era_compiler_common::BYTE_LENGTH_FIELD]);
+    let duplicate = builder.add_block(key.clone(), [1; era_compiler_common::BYTE_LENGTH_FIELD]);
+    assert_eq!(first.inner(), duplicate.inner());
+    assert_ne!(first.inner(), second.inner());
+    assert_eq!(builder.instances(&key), 2);
+
+    let data = builder.build();
+    assert_eq!(
+        data.find_block(&key, &[2; era_compiler_common::BYTE_LENGTH_FIELD])
+            .expect("Always exists")
+            .inner(),
+        second.inner()
+    );
+
+    let error = data
+        .find_block(&key, &[3; era_compiler_common::BYTE_LENGTH_FIELD])
+        .expect_err("Must not exist")
+        .to_string();
+    assert!(error.contains("Candidates: [0x0101"), "{error}");
+
+    let error = data
+        .find_block(
+            &BlockKey::new(era_compiler_common::CodeSegment::Runtime, 2_u32.into()),
+            &[1; era_compiler_common::BYTE_LENGTH_FIELD],
+        )
+        .expect_err("Must not exist")
+        .to_string();
+    assert!(error.contains("Declared blocks: [rt_1]"), "{error}");
+}
+
+#[test]
+pub fn check_undeclared_suggestions() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "transfer", 0, 0).expect("Failed to add function");
+
+    let error = context
+        .set_current_function("transfre")
+        .expect_err("Must not exist")
+        .to_string();
+    assert!(
+        error.starts_with("function `transfre` is not declared. Did you mean `transfer`?"),
+        "{error}"
+    );
+    assert!(error.contains("`transfer`"), "{error}");
+
+    let error = context
+        .get_global("unknown_global_name")
+        .expect_err("Must not exist")
+        .to_string();
+    assert!(
+        error.ends_with("No global variables are declared."),
+        "{error}"
+    );
+
+    context
+        .set_global(
+            "counter",
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(0),
+        )
+        .expect("Failed to set global");
+    let error = context
+        .get_global("countr")
+        .expect_err("Must not exist")
+        .to_string();
+    assert!(error.contains("Did you mean `counter`?"), "{error}");
+    assert!(
+        error.ends_with("Declared global variables: `counter`"),
+        "{error}"
+    );
+
+    assert_eq!(
+        crate::diagnostic::suggestion::edit_distance("kitten", "sitting"),
+        3
+    );
+}
+
+#[test]
+pub fn check_global_registry() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+
+    context
+        .set_global(
+            "mutable",
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(1),
+        )
+        .expect("Failed to set global");
+    let constant = context
+        .const_global(
+            "constant",
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(2),
+        )
+        .expect("Failed to declare global");
+    assert!(!constant.is_mutable);
+    assert!(constant.value.is_constant());
+    assert_eq!(constant.address_space, AddressSpace::Stack);
+
+    assert!(context
+        .set_global(
+            "constant",
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(3),
+        )
+        .is_err());
+    assert!(context
+        .const_global(
+            "mutable",
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(3),
+        )
+        .is_err());
+
+    let globals: Vec<(&str, bool)> = context
+        .globals()
+        .map(|(name, global)| (name, global.is_mutable))
+        .collect();
+    assert_eq!(globals, vec![("constant", false), ("mutable", true)]);
+}
+
+#[test]
+pub fn check_target_options() {
+    let options = TargetOptions::default()
+        .with_cpu("generic")
+        .with_feature("a", true)
+        .with_feature("b", true)
+        .with_feature("a", false);
+    assert_eq!(options.cpu, "generic");
+    assert_eq!(options.features_string(), "+b,-a");
+
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    let key = context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false);
+    context.set_target_options(options);
+    assert_ne!(
+        key,
+        context.build_cache_key(&MetadataBuilder::none(), EmitRequest::object(), false)
+    );
+}
+
+#[test]
+pub fn check_verbatim() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "eravm_verbatim", 0, 0)
+        .expect("Failed to add function");
+    let error = crate::eravm::evm::verbatim::verbatim(
+        &mut context,
+        &[0x5b],
+        vec![],
+        0,
+        Some(crate::diagnostic::span::Span::new(
+            "test.yul".to_owned(),
+            3,
+            5,
+        )),
+    )
+    .expect_err("Must be unsupported");
+    assert!(error.to_string().ends_with("at test.yul:3:5"), "{error}");
+    let diagnostic = context.diagnostics().last().expect("Always exists");
+    assert_eq!(
+        diagnostic.code,
+        crate::eravm::evm::verbatim::DIAGNOSTIC_CODE
+    );
+    assert_eq!(diagnostic.function.as_deref(), Some("eravm_verbatim"));
+}
+
+#[test]
+pub fn check_pointer_checked_conversions() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+
+    let heap_pointer = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        context.field_const(32),
+        "heap_pointer",
+    )
+    .expect("Always valid");
+    assert!(heap_pointer.to_int(&context, "heap_integer").is_ok());
+    assert!(heap_pointer
+        .with_offset_checked(&context, context.field_const(1), "heap_pointer_next")
+        .is_ok());
+    let error = heap_pointer
+        .with_offset_checked(
+            &context,
+            context.integer_const(era_compiler_common::BIT_LENGTH_X64, 1),
+            "heap_pointer_narrow",
+        )
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(
+        error.contains("expected a 256-bit offset, found 64-bit"),
+        "{error}"
+    );
+
+    let auxiliary_pointer = heap_pointer
+        .cast_address_space(&context, AddressSpace::HeapAuxiliary, "auxiliary_pointer")
+        .expect("Always legal");
+    assert_eq!(auxiliary_pointer.address_space, AddressSpace::HeapAuxiliary);
+    let error = heap_pointer
+        .cast_address_space(&context, AddressSpace::Generic, "generic_pointer")
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(
+        error.contains("cannot be cast from Heap to Generic"),
+        "{error}"
+    );
+
+    let stack_pointer = context
+        .build_alloca(context.field_type(), "stack_pointer")
+        .expect("Failed to allocate");
+    assert!(stack_pointer.to_int(&context, "stack_integer").is_err());
+    assert!(Pointer::new_with_offset(
+        &context,
+        AddressSpace::Stack,
+        context.field_type(),
+        context.field_const(0),
+        "stack_offset_pointer",
+    )
+    .is_err());
+}
+
+#[test]
+pub fn check_constant_pool() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    let first = context.intern_constant_bytes(b"Error");
+    let second = context.intern_constant_bytes(b"Error");
+    let other = context.intern_constant_bytes(b"Other");
+    assert_eq!(first.value, second.value);
+    assert_ne!(first.value, other.value);
+    assert_eq!(first.address_space, AddressSpace::Stack);
+    let code = context.intern_constant_bytes_in(b"Error", AddressSpace::Code);
+    assert_ne!(first.value, code.value);
+}
+
+#[test]
+pub fn check_emit_event() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "eravm_emit_event", 0, 0)
+        .expect("Failed to add function");
+    let heap_pointer = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        context.field_const(0),
+        "event_data_pointer",
+    )
+    .expect("Always valid");
+    crate::eravm::evm::event::emit_event(
+        &mut context,
+        vec![context.field_const(1)],
+        heap_pointer,
+        context.field_const(32),
+    )
+    .expect("Failed to emit");
+    let storage_pointer = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Storage,
+        context.field_type(),
+        context.field_const(0),
+        "event_data_pointer",
+    )
+    .expect("Always valid");
+    let error = crate::eravm::evm::event::emit_event(
+        &mut context,
+        vec![],
+        storage_pointer,
+        context.field_const(32),
+    )
+    .expect_err("Must be rejected");
+    assert!(error.to_string().contains("Storage"), "{error}");
+}
+
+#[test]
+pub fn check_return_data_size_caching() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    let function = crate::testing::add_function(&mut context, "return_data_caching", 1, 0)
+        .expect("Failed to add function");
+    let abi_pointer = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Generic,
+        context.byte_type(),
+        function
+            .get_nth_param(0)
+            .expect("Always exists")
+            .into_int_value(),
+        "abi_pointer",
+    )
+    .expect("Always valid");
+    context
+        .set_global(
+            crate::eravm::GLOBAL_RETURN_DATA_SIZE,
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(0),
+        )
+        .expect("Always valid");
+    let return_data_size = context
+        .update_return_data(abi_pointer)
+        .expect("Failed to update");
+    let destination = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        context.field_const(0),
+        "destination",
+    )
+    .expect("Always valid");
+    for _ in 0..2 {
+        context
+            .build_memcpy_return_data(
+                context.intrinsics().memory_copy_from_generic,
+                destination,
+                abi_pointer,
+                context.field_const(64),
+                return_data_size,
+                "return_data_caching",
+            )
+            .expect("Failed to copy");
+    }
+    let file_check = FileCheck::new()
+        .check("lshr i256")
+        .and_then(|file_check| {
+            file_check.check("store i256 %abi_length_value, ptr @returndatasize")
+        })
+        .and_then(|file_check| file_check.check_not("lshr i256"))
+        .and_then(|file_check| file_check.check_not("load i256, ptr @returndatasize"))
+        .and_then(|file_check| file_check.check("icmp ult i256 %abi_length_value, 64"))
+        .and_then(|file_check| file_check.check("icmp ult i256 %abi_length_value, 64"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "return_data_caching", &file_check)
+        .expect("Must match");
+}
+
+#[test]
+pub fn check_simulation_registry() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    context.set_yul_data(crate::eravm::context::yul_data::YulData::new(
+        true,
+        std::collections::BTreeMap::new(),
+    ));
+    crate::testing::add_function(&mut context, "simulation", 0, 0).expect("Failed to add function");
+    context
+        .register_simulation(0x1234, |context, arguments| {
+            assert_eq!(arguments.constants.len(), 2);
+            Ok(context
+                .builder()
+                .build_int_add(arguments.gas, context.field_const(1), "simulation_result")?
+                .as_basic_value_enum())
+        })
+        .expect("Failed to register");
+    context
+        .register_simulation(0x1234, |context, _arguments| {
+            Ok(context.field_const(0).as_basic_value_enum())
+        })
+        .expect_err("Must be rejected");
+    assert!(context.simulation(0x1234).is_some());
+    assert!(context.simulation(0x1235).is_none());
+
+    let result = crate::eravm::evm::call::default(
+        &mut context,
+        context.llvm_runtime().static_call,
+        context.field_const(41),
+        context.field_const(0x1234),
+        None,
+        context.field_const(0),
+        context.field_const(0),
+        context.field_const(0),
+        context.field_const(0),
+        vec![None, Some(num::BigUint::from(0x1234u32))],
+    )
+    .expect("Failed to translate");
+    assert_eq!(
+        result.into_int_value().get_zero_extended_constant(),
+        Some(42)
+    );
+}
+
+#[test]
+pub fn check_active_pointer() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "active_pointer", 0, 0)
+        .expect("Failed to add function");
+    Entry::initialize_globals(&mut context).expect("Failed to initialize globals");
+    let generic_pointer_type = context.ptr_type(AddressSpace::Generic.into());
+    context
+        .set_global(
+            crate::eravm::GLOBAL_CALLDATA_POINTER,
+            generic_pointer_type,
+            AddressSpace::Stack,
+            generic_pointer_type.const_null(),
+        )
+        .expect("Always valid");
+
+    let primary = ActivePointer::primary(&context);
+    let secondary = ActivePointer::new(context.field_const(1));
+    secondary
+        .load_calldata(&context)
+        .expect("Failed to load calldata");
+    secondary
+        .add(&context, context.field_const(4))
+        .expect("Failed to shift");
+    secondary
+        .shrink(&context, context.field_const(32))
+        .expect("Failed to shrink");
+    secondary
+        .read(&context, context.field_const(0))
+        .expect("Failed to read");
+    secondary.size(&context).expect("Failed to get size");
+    secondary
+        .copy_to_heap(
+            &context,
+            context.field_const(128),
+            context.field_const(0),
+            context.field_const(32),
+        )
+        .expect("Failed to copy");
+    secondary.swap(&context, primary).expect("Failed to swap");
+
+    let heap_pointer = Pointer::new(
+        context.byte_type(),
+        AddressSpace::Heap,
+        context.ptr_type(AddressSpace::Heap.into()).const_null(),
+    );
+    let error = primary
+        .set(&context, heap_pointer)
+        .expect_err("Must be rejected");
+    assert!(error.to_string().contains("generic"), "{error}");
+
+    let file_check = FileCheck::new()
+        .check("load ptr addrspace(3), ptr @ptr_calldata")
+        .expect("Always valid")
+        .check("call ptr addrspace(3) @llvm.eravm.ptr.shrink")
+        .expect("Always valid")
+        .check("@llvm.memcpy.p1.p3.i256")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "active_pointer", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_active_pointer_bound_check() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    let function = crate::testing::add_function(&mut context, "active_pointer_bound_check", 1, 0)
+        .expect("Failed to add function");
+    Entry::initialize_globals(&mut context).expect("Failed to initialize globals");
+    assert!(!context.is_active_pointer_bound_checked());
+
+    let out_of_bounds =
+        context.field_const(crate::eravm_const::AVAILABLE_ACTIVE_POINTERS_NUMBER as u64);
+    context
+        .get_active_pointer(out_of_bounds)
+        .expect("Unchecked without the flag");
+
+    context.set_active_pointer_bound_checked(true);
+    let error = context
+        .get_active_pointer(out_of_bounds)
+        .expect_err("Must be rejected");
+    assert!(error.to_string().contains("out of bounds"), "{error}");
+    context
+        .get_active_pointer(context.field_const(1))
+        .expect("Always in bounds");
+
+    let index = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let pointer = context
+        .get_active_pointer(index)
+        .expect("Failed to get active pointer");
+    context
+        .set_active_pointer(index, pointer)
+        .expect("Failed to set active pointer");
+
+    ActivePointer::new(index)
+        .swap(&context, ActivePointer::new(context.field_const(1)))
+        .expect("Failed to swap");
+
+    let file_check = FileCheck::new()
+        .check("icmp uge i256")
+        .expect("Always valid")
+        .check("active_pointer_index_revert_block:")
+        .expect("Always valid")
+        .check("@__revert")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "active_pointer_bound_check", &file_check)
+        .expect("Must match");
+    let snapshot = crate::testing::snapshot_function(&context, "active_pointer_bound_check")
+        .expect("Always exists");
+    assert_eq!(snapshot.matches("icmp uge i256").count(), 4);
+    assert_eq!(
+        snapshot
+            .matches("label %active_pointer_index_revert_block,")
+            .count(),
+        4
+    );
+    assert_eq!(snapshot.matches("@__revert").count(), 1);
+}
+
+#[test]
+pub fn check_near_call_abi_named_handlers() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "ZKSYNC_NEAR_CALL_callee", 0, 0)
+        .expect("Failed to add function");
+    crate::testing::add_function(&mut context, "ZKSYNC_CATCH_NEAR_CALL_scope", 1, 0)
+        .expect("Failed to add function");
+    crate::testing::add_function(&mut context, "ZKSYNC_CATCH_NEAR_CALL_invalid", 3, 0)
+        .expect("Failed to add function");
+    context
+        .add_function(
+            "ZKSYNC_CATCH_NEAR_CALL_array",
+            context
+                .void_type()
+                .fn_type(&[context.array_type(context.field_type(), 2).into()], false),
+            0,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    crate::testing::add_function(&mut context, "near_call_named_handlers", 0, 0)
+        .expect("Failed to add function");
+
+    let callee = context
+        .get_function("ZKSYNC_NEAR_CALL_callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+    let arguments = vec![
+        callee
+            .value
+            .as_global_value()
+            .as_pointer_value()
+            .as_basic_value_enum(),
+        context.field_const(0).as_basic_value_enum(),
+    ];
+    for (handler, expected) in [
+        ("catch_scope", "must be prefixed"),
+        ("ZKSYNC_CATCH_NEAR_CALL_undeclared", "is not declared"),
+        ("ZKSYNC_CATCH_NEAR_CALL_invalid", "at most 2 arguments"),
+        (
+            "ZKSYNC_CATCH_NEAR_CALL_array",
+            "ABI data as a pointer or an integer",
+        ),
+    ] {
+        let error = context
+            .build_invoke_near_call_abi_with_handler(
+                callee,
+                arguments.clone(),
+                handler,
+                "near_call",
+            )
+            .expect_err("Must be rejected");
+        assert!(error.to_string().contains(expected), "{handler}: {error}");
+    }
+
+    context
+        .build_invoke_near_call_abi_with_handler(
+            callee,
+            arguments,
+            "ZKSYNC_CATCH_NEAR_CALL_scope",
+            "near_call",
+        )
+        .expect("Failed to build near call");
+
+    let file_check = FileCheck::new()
+        .check("@llvm.eravm.nearcall")
+        .expect("Always valid")
+        .check("landingpad")
+        .expect("Always valid")
+        .check("ptrtoint ptr addrspace(3)")
+        .expect("Always valid")
+        .check("call void @ZKSYNC_CATCH_NEAR_CALL_scope(i256")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "near_call_named_handlers", &file_check)
+        .expect("Must match");
+}
+
+#[test]
+pub fn check_landing_pad_handler_arguments() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "ZKSYNC_NEAR_CALL_callee", 0, 0)
+        .expect("Failed to add function");
+    crate::testing::add_function(&mut context, "ZKSYNC_CATCH_NEAR_CALL_revert_reason", 2, 0)
+        .expect("Failed to add function");
+    crate::testing::add_function(&mut context, "landing_pad_handler_arguments", 0, 0)
+        .expect("Failed to add function");
+
+    let callee = context
+        .get_function("ZKSYNC_NEAR_CALL_callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+    let arguments = vec![
+        callee
+            .value
+            .as_global_value()
+            .as_pointer_value()
+            .as_basic_value_enum(),
+        context.field_const(0).as_basic_value_enum(),
+    ];
+    context
+        .build_invoke_near_call_abi_with_handler(
+            callee,
+            arguments,
+            "ZKSYNC_CATCH_NEAR_CALL_revert_reason",
+            "near_call",
+        )
+        .expect("Failed to build near call");
+
+    let file_check = FileCheck::new()
+        .check("landingpad")
+        .expect("Always valid")
+        .check("extractvalue")
+        .expect("Always valid")
+        .check("ptrtoint ptr addrspace(3)")
+        .expect("Always valid")
+        .check("extractvalue")
+        .expect("Always valid")
+        .check("zext i1")
+        .expect("Always valid")
+        .check("call void @ZKSYNC_CATCH_NEAR_CALL_revert_reason(i256")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "landing_pad_handler_arguments", &file_check)
+        .expect("Must match");
+}
+
+#[test]
+pub fn check_call_site_attributes_unoptimized() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+    assert_eq!(
+        context.optimizer().settings().level_middle_end,
+        inkwell::OptimizationLevel::None
+    );
+    crate::testing::add_function(&mut context, "callee", 1, 1).expect("Failed to add function");
+    crate::testing::add_function(&mut context, "caller", 0, 0).expect("Failed to add function");
+    let callee = context
+        .get_function("callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+
+    context
+        .build_call(
+            callee,
+            &[context.field_const(0).as_basic_value_enum()],
+            "default",
+        )
+        .expect("Failed to build call");
+    context
+        .build_call_with_attributes(
+            callee,
+            &[context.field_const(1).as_basic_value_enum()],
+            &[Attribute::AlwaysInline],
+            "inlined",
+        )
+        .expect("Failed to build call");
+
+    let file_check = FileCheck::new()
+        .check("call i256 @callee(i256 0) #")
+        .expect("Always valid")
+        .check("call i256 @callee(i256 1) #")
+        .expect("Always valid")
+        .check("attributes #{{.*}} = { noinline }")
+        .expect("Always valid")
+        .check("attributes #{{.*}} = { alwaysinline }")
+        .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_prepare_profiling() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+
+    let mut profiler = Profiler::new();
+    context
+        .prepare("test.sol:Test", false, &mut profiler, None)
+        .expect("Failed to prepare");
+
+    let elements: Vec<&str> = profiler
+        .timings()
+        .iter()
+        .map(|(run, _duration)| run.element.as_str())
+        .collect();
+    assert_eq!(
+        elements,
+        vec![
+            "unoptimized LLVM IR verification",
+            "unreachable function pruning",
+            "LLVM IR optimization",
+            "optimized LLVM IR verification",
+        ]
+    );
+    assert!(profiler
+        .timings()
+        .iter()
+        .all(|(run, _duration)| run.translation_unit == "test.sol:Test"
+            && run.settings == OptimizerSettings::cycles().to_string()));
+}
+
+#[test]
+pub fn check_build_cancellation() {
+    struct Hook {
+        cancellation_handle: CancellationHandle,
+    }
+    impl AssemblyHook for Hook {
+        fn process(&self, _contract_path: &str, assembly: String) -> anyhow::Result<String> {
+            self.cancellation_handle.cancel();
+            Ok(assembly)
+        }
+    }
+
+    let llvm = inkwell::context::Context::create();
+    let build = |context: Context<DummyDependency>, cancellation_handle: &CancellationHandle| {
+        context
+            .build(
+                "test.sol:Test",
+                &MetadataBuilder::none(),
+                EmitRequest::object(),
+                false,
+                &mut Profiler::new(),
+                Some(cancellation_handle),
+            )
+            .expect_err("Must be cancelled")
+    };
+    let is_cancelled_before = |error: &anyhow::Error, expected: &str| {
+        matches!(
+            error.downcast_ref::<Error>(),
+            Some(Error::Cancelled { stage }) if stage == expected
+        )
+    };
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    let cancellation_handle = CancellationHandle::new();
+    cancellation_handle.cancel();
+    let error = build(context, &cancellation_handle);
+    assert!(is_cancelled_before(&error, "verification"), "{error}");
+
+    let cancellation_handle = CancellationHandle::new();
+    let mut optimizer = Optimizer::new(OptimizerSettings::cycles());
+    let callback_handle = cancellation_handle.clone();
+    optimizer.register_callback(
+        "cancel",
+        Stage::PostOptimization,
+        Arc::new(move |_module| {
+            callback_handle.cancel();
+            Ok(())
+        }),
+    );
+    let mut context =
+        Context::<DummyDependency>::new(&llvm, llvm.create_module("test"), vec![], optimizer, None);
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    let error = build(context, &cancellation_handle);
+    assert!(is_cancelled_before(&error, "emitting"), "{error}");
+
+    let cancellation_handle = CancellationHandle::new();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    context.set_assembly_hook(Some(Arc::new(Hook {
+        cancellation_handle: cancellation_handle.clone(),
+    })));
+    let error = build(context, &cancellation_handle);
+    assert!(is_cancelled_before(&error, "assembling"), "{error}");
+}
+
+#[test]
+pub fn check_unreachable_function_pruning() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    for name in [
+        "ZKSYNC_NEAR_CALL_callee",
+        "ZKSYNC_CATCH_NEAR_CALL",
+        "ZKSYNC_CATCH_NEAR_CALL_unused",
+        "unreferenced",
+    ] {
+        add_void_function(&mut context, name, inkwell::module::Linkage::Private);
+    }
+
+    let function = context
+        .add_function(
+            "entry",
+            context.void_type().fn_type(&[], false),
+            0,
+            Some(inkwell::module::Linkage::External),
+        )
+        .expect("Failed to add function");
+    context
+        .set_current_function("entry")
+        .expect("Always exists");
+    context.set_basic_block(function.borrow().entry_block());
+    let callee = context
+        .get_function("ZKSYNC_NEAR_CALL_callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+    context
+        .build_invoke_near_call_abi(
+            callee,
+            vec![
+                callee
+                    .value
+                    .as_global_value()
+                    .as_pointer_value()
+                    .as_basic_value_enum(),
+                context.field_const(0).as_basic_value_enum(),
+            ],
+            "near_call",
+        )
+        .expect("Failed to build near call");
+    let return_block = function.borrow().return_block();
+    context
+        .build_unconditional_branch(return_block)
+        .expect("Failed to build the branch");
+    context.set_basic_block(return_block);
+    context
+        .builder()
+        .build_return(None)
+        .expect("Failed to build the return");
+
+    let target_machine = TargetMachine::new(
+        era_compiler_common::Target::EraVM,
+        context.optimizer().settings(),
+        &[],
+    )
+    .expect("Failed to create target machine");
+    context
+        .optimizer()
+        .prune_unreachable(&target_machine, context.module())
+        .expect("Failed to prune");
+    context.verify().expect("The module must be valid");
+
+    for name in ["entry", "ZKSYNC_NEAR_CALL_callee", "ZKSYNC_CATCH_NEAR_CALL"] {
+        assert!(context.module().get_function(name).is_some(), "{name}");
+    }
+    for name in ["ZKSYNC_CATCH_NEAR_CALL_unused", "unreferenced"] {
+        assert!(context.module().get_function(name).is_none(), "{name}");
+    }
+}
+
+#[test]
+pub fn check_size_fallback_attempt() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+
+    let optimized_module = context
+        .prepare("test.sol:Test", false, &mut Profiler::new(), None)
+        .expect("Failed to prepare");
+    let bytecode_buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+        &[0u8; 4 * era_compiler_common::BYTE_LENGTH_X64],
+        "bytecode_buffer",
+    );
+    let attempt = optimized_module.size_fallback_attempt(&bytecode_buffer);
+    assert_eq!(attempt.settings, OptimizerSettings::cycles().to_string());
+    assert_eq!(attempt.size, 4 * era_compiler_common::BYTE_LENGTH_X64);
+}
+
+#[test]
+pub fn check_verification_error() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+
+    let error = match context.prepare("test.sol:Test", false, &mut Profiler::new(), None) {
+        Ok(_) => panic!("The unterminated function must be rejected"),
+        Err(error) => error,
+    };
+    match error.downcast_ref::<Error>() {
+        Some(Error::Verification(message)) => assert!(
+            message.starts_with("unoptimized LLVM IR verification: "),
+            "{message}"
+        ),
+        _ => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+pub fn check_bounded_returndata_copy() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::none());
+
+    for (name, policy, checks) in [
+        (
+            "revert_policy",
+            ReturnDataCopyPolicy::Revert,
+            r#"
+; CHECK: %copy_slice_end = add i256 %0, %1
+; CHECK-NEXT: %copy_is_out_of_bounds = icmp ugt i256 %copy_slice_end, %2
+; CHECK-NEXT: br i1 %copy_is_out_of_bounds, label %copy_error_block, label %copy_join_block
+; CHECK: copy_error_block:
+; CHECK-NEXT: @__revert(i256 0, i256 0,
+; CHECK-NEXT: unreachable
+; CHECK: copy_join_block:
+; CHECK-NEXT: call void @llvm.memcpy.p1.p3.i256(ptr addrspace(1) {{.*}}, ptr addrspace(3) {{.*}}, i256 %1, i1 false)
+"#,
+        ),
+        (
+            "clamp_policy",
+            ReturnDataCopyPolicy::Clamp,
+            r#"
+; CHECK-NOT: __revert
+; CHECK: %copy_is_return_data_size_lesser = icmp ult i256 %2, %1
+; CHECK-NEXT: %copy_min_size = select i1 %copy_is_return_data_size_lesser, i256 %2, i256 %1
+; CHECK-NEXT: call void @llvm.memcpy.p1.p3.i256(ptr addrspace(1) {{.*}}, ptr addrspace(3) {{.*}}, i256 %copy_min_size, i1 false)
+; CHECK-NOT: __revert
+"#,
+        ),
+    ] {
+        let function =
+            crate::testing::add_function(&mut context, name, 3, 0).expect("Failed to add function");
+        let operand = |index| {
+            function
+                .get_nth_param(index)
+                .expect("Always exists")
+                .into_int_value()
+        };
+        let destination = Pointer::new_with_offset(
+            &context,
+            AddressSpace::Heap,
+            context.byte_type(),
+            context.field_const(0),
+            "copy_destination",
+        )
+        .expect("Always valid");
+        let source = Pointer::new(
+            context.byte_type(),
+            AddressSpace::Generic,
+            context.ptr_type(AddressSpace::Generic.into()).const_null(),
+        );
+        context
+            .build_bounded_returndata_copy(
+                context.intrinsics().memory_copy_from_generic,
+                destination,
+                source,
+                operand(0),
+                operand(1),
+                operand(2),
+                policy,
+                "copy",
+            )
+            .expect("Failed to build the copy");
+
+        let file_check = FileCheck::parse(checks, FileCheck::DEFAULT_PREFIX).expect("Always valid");
+        crate::testing::check_function(&context, name, &file_check).expect("Must match");
+    }
+}
+
+#[test]
+pub fn check_build_metrics() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    add_void_function(
+        &mut context,
+        "unreferenced",
+        inkwell::module::Linkage::Private,
+    );
+    let instructions_before = context.instructions_count();
+
+    let optimized_module = context
+        .prepare("test.sol:Test", false, &mut Profiler::new(), None)
+        .expect("Failed to prepare");
+    let metrics = optimized_module.metrics();
+    assert_eq!(metrics.instructions_before, instructions_before);
+    assert!(metrics.instructions_after < metrics.instructions_before);
+    assert_eq!(
+        metrics.instructions_after,
+        optimized_module
+            .module()
+            .get_functions()
+            .flat_map(|function| function.get_basic_blocks())
+            .map(|block| block.get_instructions().count())
+            .sum::<usize>()
+    );
+    assert_eq!(metrics.emitting, Duration::ZERO);
+    assert_eq!(metrics.assembling, Duration::ZERO);
+}
+
+#[test]
+pub fn check_bytecode_size_limit() {
+    let llvm = inkwell::context::Context::create();
+    let build = |limit: Option<usize>| {
+        let mut optimizer_settings = OptimizerSettings::cycles();
+        optimizer_settings.set_eravm_bytecode_size_limit(limit);
+        let mut context = create_context(&llvm, optimizer_settings);
+        add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+        context.build(
+            "test.sol:Test",
+            &MetadataBuilder::none(),
+            EmitRequest::object(),
+            false,
+            &mut Profiler::new(),
+            None,
+        )
+    };
+
+    assert_eq!(
+        OptimizerSettings::cycles().eravm_bytecode_size_limit(),
+        crate::eravm::r#const::BYTECODE_SIZE_LIMIT_DEFAULT
+    );
+
+    let build_default = build(None).expect("Failed to build");
+    assert_eq!(
+        build_default.size_fallback.limit,
+        Some(
+            crate::eravm::r#const::BYTECODE_SIZE_LIMIT_DEFAULT
+                * era_compiler_common::BYTE_LENGTH_X64
+        )
+    );
+
+    let error = build(Some(1)).expect_err("Must exceed the limit");
+    match error.downcast_ref::<Error>() {
+        Some(Error::BytecodeSizeExceeded { found, limit }) => {
+            assert_eq!(*limit, 1);
+            assert!(*found > *limit, "{found}");
+        }
+        _ => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+pub fn check_fallback_policy() {
+    let attempts = Arc::new(Mutex::new(vec![]));
+    let attempts_recorder = attempts.clone();
+
+    let llvm = inkwell::context::Context::create();
+    let mut optimizer_settings = OptimizerSettings::cycles();
+    optimizer_settings.set_eravm_bytecode_size_limit(Some(1));
+    let mut optimizer = Optimizer::new(optimizer_settings);
+    optimizer.set_fallback_policy(FallbackPolicy::Custom(Arc::new(move |module, settings| {
+        attempts_recorder
+            .lock()
+            .expect("Sync")
+            .push(settings.clone());
+        FallbackPolicy::Staged.next_settings(module, settings)
+    })));
+    let mut context =
+        Context::<DummyDependency>::new(&llvm, llvm.create_module("test"), vec![], optimizer, None);
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+
+    let error = context
+        .build(
+            "test.sol:Test",
+            &MetadataBuilder::none(),
+            EmitRequest::object(),
+            false,
+            &mut Profiler::new(),
+            None,
+        )
+        .expect_err("Must exceed the limit");
+    match error.downcast_ref::<Error>() {
+        Some(Error::SizeFallback(inner)) => match inner.as_ref() {
+            Error::SizeFallback(inner) => assert!(
+                matches!(inner.as_ref(), Error::BytecodeSizeExceeded { limit: 1, .. }),
+                "{inner}"
+            ),
+            inner => panic!("Unexpected error: {inner}"),
+        },
+        _ => panic!("Unexpected error: {error}"),
+    }
+
+    let size_s = OptimizerSettings::new(
+        inkwell::OptimizationLevel::Default,
+        SizeLevel::S,
+        inkwell::OptimizationLevel::Aggressive,
+    );
+    assert_eq!(
+        attempts.lock().expect("Sync").as_slice(),
+        &[
+            OptimizerSettings::cycles(),
+            size_s,
+            OptimizerSettings::size()
+        ]
+    );
+}
+
+#[test]
+pub fn check_build_size_fallback_report() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+
+    let build = context
+        .build(
+            "test.sol:Test",
+            &MetadataBuilder::none(),
+            EmitRequest::object(),
+            false,
+            &mut Profiler::new(),
+            None,
+        )
+        .expect("Failed to build");
+    let report = &build.size_fallback;
+    let limit =
+        crate::eravm::r#const::BYTECODE_SIZE_LIMIT_DEFAULT * era_compiler_common::BYTE_LENGTH_X64;
+    assert!(!report.is_attempted());
+    assert_eq!(report.limit, Some(limit));
+    assert_eq!(
+        report.settings_used(),
+        Some(OptimizerSettings::cycles().to_string().as_str())
+    );
+    assert_eq!(report.size_before(), Some(build.bytecode.len()));
+    assert_eq!(
+        report.headroom(),
+        Some(limit as isize - build.bytecode.len() as isize)
+    );
+}
+
+#[test]
+pub fn check_check_only() {
+    let output_directory =
+        std::env::temp_dir().join(format!("era_compiler_check_only_{}", std::process::id()));
+    std::fs::create_dir_all(output_directory.as_path()).expect("Failed to create directory");
+
+    let llvm = inkwell::context::Context::create();
+    let mut context = Context::<DummyDependency>::new(
+        &llvm,
+        llvm.create_module("test"),
+        vec![],
+        Optimizer::new(OptimizerSettings::cycles()),
+        Some(DebugConfig::new(output_directory.clone())),
+    );
+    add_void_function(&mut context, "valid", inkwell::module::Linkage::External);
+    context.check("test.sol:Test").expect("Must be valid");
+    assert!(context.module().get_function("valid").is_some());
+    let dumped = std::fs::read_dir(output_directory.as_path())
+        .expect("Failed to read directory")
+        .map(|entry| {
+            entry
+                .expect("Failed to read entry")
+                .file_name()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect::<Vec<String>>();
+    assert_eq!(dumped.len(), 1, "{dumped:?}");
+    assert!(dumped[0].contains("unoptimized"), "{dumped:?}");
+
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    let error = context
+        .check("test.sol:Test")
+        .expect_err("The unterminated function must be rejected");
+    match error.downcast_ref::<Error>() {
+        Some(Error::Verification(message)) => assert!(
+            message.starts_with("unoptimized LLVM IR verification: "),
+            "{message}"
+        ),
+        _ => panic!("Unexpected error: {error}"),
+    }
+
+    std::fs::remove_dir_all(output_directory).expect("Failed to remove directory");
+}
+
+#[test]
+pub fn check_build_diagnostics() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    context.warn("test-warning", "the test warning".to_owned());
+
+    let build = context
+        .build(
+            "test.sol:Test",
+            &MetadataBuilder::none(),
+            EmitRequest::object(),
+            false,
+            &mut Profiler::new(),
+            None,
+        )
+        .expect("Failed to build");
+    assert_eq!(build.diagnostics.len(), 1);
+    assert_eq!(build.diagnostics[0].code, "test-warning");
+}
+
+#[test]
+pub fn check_staged_build() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    let build = context
+        .build(
+            "test.sol:Test",
+            &MetadataBuilder::none(),
+            EmitRequest::ASSEMBLY,
+            false,
+            &mut Profiler::new(),
+            None,
+        )
+        .expect("Failed to build");
+
+    let mut context = create_context(&llvm, OptimizerSettings::cycles());
+    add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+    let mut profiler = Profiler::new();
+    let mut optimized_module = context
+        .prepare("test.sol:Test", false, &mut profiler, None)
+        .expect("Failed to prepare");
+    let assembly_buffer = optimized_module
+        .emit_assembly(&mut profiler)
+        .expect("Failed to emit assembly");
+    let bytecode_buffer = optimized_module
+        .emit_object(Some(&assembly_buffer), &mut profiler)
+        .expect("Failed to assemble");
+    assert!(!optimized_module.exceeds_size_limit(&bytecode_buffer, 0));
+    assert!(optimized_module.metrics().emitting > Duration::ZERO);
+    assert!(optimized_module.metrics().assembling > Duration::ZERO);
+    let staged_build = optimized_module
+        .finalize(
+            bytecode_buffer,
+            Some(assembly_buffer),
+            &MetadataBuilder::none(),
+        )
+        .expect("Failed to finalize");
+
+    assert_eq!(staged_build.bytecode, build.bytecode);
+    assert_eq!(staged_build.assembly, build.assembly);
+    assert_eq!(staged_build.size_fallback.attempts.len(), 1);
+    let elements: Vec<&str> = profiler
+        .timings()
+        .iter()
+        .map(|(run, _duration)| run.element.as_str())
+        .collect();
+    assert_eq!(
+        &elements[elements.len() - 2..],
+        &["assembly emitting", "assembling"]
+    );
+}
+
+#[test]
+pub fn check_llvm_ir_unoptimized_kept() {
+    let llvm = inkwell::context::Context::create();
+    let build = |is_kept: bool| {
+        let mut context = create_context(&llvm, OptimizerSettings::cycles());
+        add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+        add_void_function(
+            &mut context,
+            "unreferenced",
+            inkwell::module::Linkage::Private,
+        );
+        context.set_llvm_ir_unoptimized_kept(is_kept);
+        context
+            .build(
+                "test.sol:Test",
+                &MetadataBuilder::none(),
+                EmitRequest::LLVM_IR,
+                false,
+                &mut Profiler::new(),
+                None,
+            )
+            .expect("Failed to build")
+    };
+
+    assert!(build(false).llvm_ir_unoptimized.is_none());
+
+    let build = build(true);
+    let llvm_ir_unoptimized = build.llvm_ir_unoptimized.expect("Must be kept");
+    assert!(
+        llvm_ir_unoptimized.contains("define private void @unreferenced()"),
+        "{llvm_ir_unoptimized}"
+    );
+    let llvm_ir = build.llvm_ir.expect("Must be emitted");
+    assert!(!llvm_ir.contains("@unreferenced"), "{llvm_ir}");
+}
+
+#[test]
+pub fn check_ergs_estimate() {
+    let llvm = inkwell::context::Context::create();
+    let build = |emit_request: EmitRequest| {
+        let mut context = create_context(&llvm, OptimizerSettings::cycles());
+        add_void_function(&mut context, "test", inkwell::module::Linkage::External);
+        context
+            .build(
+                "test.sol:Test",
+                &MetadataBuilder::none(),
+                emit_request,
+                false,
+                &mut Profiler::new(),
+                None,
+            )
+            .expect("Failed to build")
+    };
+
+    let mut build_without_assembly = build(EmitRequest::object());
+    let error = build_without_assembly
+        .estimate_ergs()
+        .expect_err("Must require the assembly")
+        .to_string();
+    assert!(error.contains("assembly is required"), "{error}");
+    assert!(build_without_assembly.ergs_estimates.is_none());
+
+    let mut build = build(EmitRequest::ASSEMBLY);
+    build.estimate_ergs().expect("Failed to estimate");
+    let estimates = build.ergs_estimates.as_ref().expect("Always exists");
+    assert!(
+        estimates.get("test").is_some_and(|ergs| *ergs > 0),
+        "{estimates:?}"
+    );
+
+    let assembly = build.assembly.as_deref().expect("Always exists");
+    let error = crate::eravm::ergs_estimate::estimate(&[], assembly)
+        .expect_err("Must be out of bounds")
+        .to_string();
+    assert!(error.contains("out of the bytecode bounds"), "{error}");
+}
+
+#[test]
+pub fn check_entry_abi() {
+    let check = |entry_abi: EntryAbi, file_check: &str| {
+        let llvm = inkwell::context::Context::create();
+
+        let mut context = create_context(&llvm, OptimizerSettings::none());
+        let mut yul_data = YulData::new(false, BTreeMap::new());
+        yul_data.set_entry_abi(entry_abi);
+        context.set_yul_data(yul_data);
+        add_void_function(
+            &mut context,
+            Runtime::FUNCTION_DEPLOY_CODE,
+            inkwell::module::Linkage::Private,
+        );
+        add_void_function(
+            &mut context,
+            Runtime::FUNCTION_RUNTIME_CODE,
+            inkwell::module::Linkage::Private,
+        );
+        Entry::default()
+            .declare(&mut context)
+            .expect("Failed to declare");
+        Entry::default()
+            .into_llvm(&mut context)
+            .expect("Failed to lower");
+
+        let file_check =
+            FileCheck::parse(file_check, FileCheck::DEFAULT_PREFIX).expect("Invalid FileCheck");
+        crate::testing::check_function(&context, Runtime::FUNCTION_ENTRY, &file_check)
+            .expect("Must match");
+    };
+
+    check(
+        EntryAbi::Standard,
+        r#"
+; CHECK: store ptr addrspace(3) %{{.*}}, ptr @ptr_calldata
+; CHECK: %abi_length_value = and i256 %abi_pointer_value_shifted, 4294967295
+; CHECK: store i256 %abi_length_value, ptr @calldatasize
+; CHECK: %calldata_end_pointer = getelementptr
+; CHECK: store ptr addrspace(3) %calldata_end_pointer, ptr @ptr_return_data
+; CHECK: store ptr addrspace(3) %calldata_end_pointer, ptr @ptr_decommit
+; CHECK: %active_pointer_pointer = getelementptr {{.*}}@ptr_active
+; CHECK: store i256 %{{.*}}, ptr @call_flags
+; CHECK: %extra_abi_data_array_element_pointer = getelementptr {{.*}}@extra_abi_data
+"#,
+    );
+
+    check(
+        EntryAbi::NoCalldata,
+        r#"
+; CHECK-NOT: %abi_length_value
+; CHECK: store ptr addrspace(3) null, ptr @ptr_calldata
+; CHECK-NOT: store i256 {{.*}}, ptr @calldatasize
+; CHECK: store ptr addrspace(3) %calldata_end_pointer, ptr @ptr_return_data
+; CHECK: %active_pointer_pointer = getelementptr {{.*}}@ptr_active
+; CHECK: store i256 %{{.*}}, ptr @call_flags
+; CHECK: %extra_abi_data_array_element_pointer = getelementptr {{.*}}@extra_abi_data
+"#,
+    );
+
+    check(
+        EntryAbi::Bootstrap,
+        r#"
+; CHECK-NOT: @ptr_return_data
+; CHECK-NOT: @ptr_decommit
+; CHECK-NOT: %active_pointer_pointer
+; CHECK: store i256 %{{.*}}, ptr @call_flags
+; CHECK-NOT: %extra_abi_data_array_element_pointer
+; CHECK: %is_deploy_code_call_flag_truncated = and i256 %{{.*}}, 1
+"#,
+    );
+}
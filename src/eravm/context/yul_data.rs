@@ -6,6 +6,8 @@ use std::collections::BTreeMap;
 
 use num::Zero;
 
+use crate::eravm::context::entry_abi::EntryAbi;
+
 ///
 /// The LLVM IR generator Yul data.
 ///
@@ -21,6 +23,8 @@ pub struct YulData {
     /// The list of constant arrays in the code section.
     /// It is a temporary storage used until the finalization method is called.
     const_arrays: BTreeMap<u8, Vec<num::BigUint>>,
+    /// The entry function ABI.
+    entry_abi: EntryAbi,
 }
 
 impl YulData {
@@ -35,9 +39,24 @@ impl YulData {
             are_eravm_extensions_enabled,
             identifier_paths,
             const_arrays: BTreeMap::new(),
+            entry_abi: EntryAbi::default(),
         }
     }
 
+    ///
+    /// Sets the entry function ABI.
+    ///
+    pub fn set_entry_abi(&mut self, entry_abi: EntryAbi) {
+        self.entry_abi = entry_abi;
+    }
+
+    ///
+    /// Returns the entry function ABI.
+    ///
+    pub fn entry_abi(&self) -> EntryAbi {
+        self.entry_abi
+    }
+
     ///
     /// Whether the EraVM extensions is enabled.
     ///
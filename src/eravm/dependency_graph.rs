@@ -0,0 +1,172 @@
+//!
+//! The EraVM contract dependency graph.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::eravm::context::build::Build;
+use crate::undefined_references::UndefinedReferences;
+
+///
+/// The EraVM contract dependency graph.
+///
+/// Collects the factory dependencies and library linker symbols referenced by unlinked objects,
+/// so the contracts can be compiled and linked in the dependency order.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DependencyGraph {
+    /// The references of the contracts, keyed by the contract path.
+    pub nodes: BTreeMap<String, UndefinedReferences>,
+}
+
+impl DependencyGraph {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Adds the contract at `path` with the references extracted from `bytecode_buffer`.
+    ///
+    /// The self canonical bytecode hash placeholder is not a dependency, so it is skipped.
+    ///
+    pub fn add_object(
+        &mut self,
+        path: &str,
+        bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    ) {
+        let mut references = crate::eravm::get_undefined_references(bytecode_buffer);
+        references
+            .factory_dependencies
+            .remove(crate::eravm::r#const::SELF_CANONICAL_BYTECODE_HASH_SYMBOL);
+        references
+            .linker_symbols
+            .remove(crate::eravm::r#const::SELF_CANONICAL_BYTECODE_HASH_SYMBOL);
+        self.nodes.insert(path.to_owned(), references);
+    }
+
+    ///
+    /// Adds the contract at `path` with the references extracted from the unlinked `build`.
+    ///
+    pub fn add_build(&mut self, path: &str, build: &Build) {
+        let bytecode_buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range(
+            build.bytecode.as_slice(),
+            path,
+            false,
+        );
+        self.add_object(path, &bytecode_buffer);
+    }
+
+    ///
+    /// Returns the library linker symbols referenced by any contract.
+    ///
+    pub fn linker_symbols(&self) -> BTreeSet<&str> {
+        self.nodes
+            .values()
+            .flat_map(|references| references.linker_symbols.iter())
+            .map(|symbol| symbol.as_str())
+            .collect()
+    }
+
+    ///
+    /// Returns the factory dependencies which are not contracts of the graph.
+    ///
+    pub fn external_factory_dependencies(&self) -> BTreeSet<&str> {
+        self.nodes
+            .values()
+            .flat_map(|references| references.factory_dependencies.iter())
+            .map(|dependency| dependency.as_str())
+            .filter(|dependency| !self.nodes.contains_key(*dependency))
+            .collect()
+    }
+
+    ///
+    /// Returns the contract paths ordered so that each contract follows its factory dependencies.
+    ///
+    /// The factory dependencies outside of the graph are ignored.
+    ///
+    /// # Errors
+    /// If the factory dependencies form a cycle.
+    ///
+    pub fn compilation_order(&self) -> anyhow::Result<Vec<String>> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut visited = BTreeSet::new();
+        for path in self.nodes.keys() {
+            self.visit(path.as_str(), &mut visited, &mut Vec::new(), &mut order)?;
+        }
+        Ok(order)
+    }
+
+    ///
+    /// Visits the contract at `path` depth-first, appending it to `order` after its dependencies.
+    ///
+    fn visit<'a>(
+        &'a self,
+        path: &'a str,
+        visited: &mut BTreeSet<&'a str>,
+        stack: &mut Vec<&'a str>,
+        order: &mut Vec<String>,
+    ) -> anyhow::Result<()> {
+        if visited.contains(path) {
+            return Ok(());
+        }
+        if stack.contains(&path) {
+            anyhow::bail!("factory dependency cycle: {} -> {path}", stack.join(" -> "));
+        }
+        let references = match self.nodes.get(path) {
+            Some(references) => references,
+            None => return Ok(()),
+        };
+
+        stack.push(path);
+        for dependency in references.factory_dependencies.iter() {
+            self.visit(dependency.as_str(), visited, stack, order)?;
+        }
+        stack.pop();
+
+        visited.insert(path);
+        order.push(path.to_owned());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::undefined_references::UndefinedReferences;
+
+    use super::DependencyGraph;
+
+    #[test]
+    pub fn check_dependency_graph_order() {
+        let mut graph = DependencyGraph::new();
+        graph.nodes.insert(
+            "A".to_owned(),
+            UndefinedReferences::new(
+                vec!["Library".to_owned()],
+                vec!["B".to_owned(), "External".to_owned()],
+            ),
+        );
+        graph.nodes.insert(
+            "B".to_owned(),
+            UndefinedReferences::new(vec![], vec!["C".to_owned()]),
+        );
+        graph
+            .nodes
+            .insert("C".to_owned(), UndefinedReferences::default());
+        assert_eq!(
+            graph.compilation_order().expect("Always valid"),
+            vec!["C", "B", "A"]
+        );
+        assert_eq!(graph.linker_symbols(), ["Library"].into());
+        assert_eq!(graph.external_factory_dependencies(), ["External"].into());
+
+        graph.nodes.insert(
+            "C".to_owned(),
+            UndefinedReferences::new(vec![], vec!["A".to_owned()]),
+        );
+        assert!(graph.compilation_order().is_err());
+    }
+}
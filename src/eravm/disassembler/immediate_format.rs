@@ -0,0 +1,15 @@
+//!
+//! The EraVM disassembler immediate operand format.
+//!
+
+///
+/// The EraVM disassembler immediate operand format.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ImmediateFormat {
+    /// The immediates are printed as emitted by LLVM.
+    #[default]
+    Decimal,
+    /// The decimal immediates are converted to hexadecimal.
+    Hexadecimal,
+}
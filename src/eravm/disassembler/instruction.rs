@@ -0,0 +1,67 @@
+//!
+//! The EraVM disassembled instruction.
+//!
+
+///
+/// The EraVM disassembled instruction.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Instruction {
+    /// The instruction offset.
+    pub offset: u64,
+    /// The instruction encoding bytes in hexadecimal, if printed by the disassembler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    /// The instruction mnemonic.
+    pub mnemonic: String,
+    /// The instruction operands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operands: Vec<String>,
+}
+
+impl Instruction {
+    ///
+    /// Parses a disassembly line in the `<offset>: [<encoding>] <mnemonic> [<operands>]` form.
+    ///
+    /// The offset is hexadecimal. Returns `None` if the line is not an instruction, e.g. a label.
+    ///
+    pub fn parse(line: &str) -> Option<Self> {
+        let (offset, body) = line.split_once(':')?;
+        let offset = u64::from_str_radix(offset.trim(), 16).ok()?;
+
+        let mut fields = body
+            .split('\t')
+            .map(|field| field.trim())
+            .filter(|field| !field.is_empty())
+            .peekable();
+        let encoding = fields
+            .next_if(|field| {
+                field.split_whitespace().all(|byte| {
+                    byte.len() == 2 && byte.bytes().all(|digit| digit.is_ascii_hexdigit())
+                })
+            })
+            .map(|field| field.split_whitespace().collect::<String>());
+
+        let instruction = fields.collect::<Vec<&str>>().join(" ");
+        let (mnemonic, operands) = match instruction.split_once(char::is_whitespace) {
+            Some((mnemonic, operands)) => (mnemonic, operands.trim()),
+            None => (instruction.as_str(), ""),
+        };
+        if mnemonic.is_empty() {
+            return None;
+        }
+        let operands = operands
+            .split(',')
+            .map(|operand| operand.trim())
+            .filter(|operand| !operand.is_empty())
+            .map(|operand| operand.to_owned())
+            .collect();
+
+        Some(Self {
+            offset,
+            encoding,
+            mnemonic: mnemonic.to_owned(),
+            operands,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+//!
+//! The EraVM disassembler.
+//!
+
+pub mod immediate_format;
+pub mod instruction;
+pub mod options;
+
+#[cfg(test)]
+mod tests;
+
+use crate::target_machine::TargetMachine;
+
+use self::instruction::Instruction;
+use self::options::Options;
+
+///
+/// Disassembles `bytecode_buffer` into the text, formatting the operands according to `options`.
+///
+/// The lines which are not instructions are left as is.
+///
+pub fn disassemble(
+    target_machine: &TargetMachine,
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    options: &Options,
+) -> anyhow::Result<String> {
+    let disassembly_text = disassemble_raw(target_machine, bytecode_buffer, options)?;
+
+    let mut text = String::with_capacity(disassembly_text.len());
+    for line in disassembly_text.lines() {
+        let is_instruction = Instruction::parse(line)
+            .map(|instruction| !instruction.operands.is_empty())
+            .unwrap_or_default();
+        match line.rsplit_once('\t') {
+            Some((head, operands)) if is_instruction => {
+                text.push_str(head);
+                text.push('\t');
+                text.push_str(options.format_operands(operands).as_str());
+            }
+            _ => text.push_str(line),
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+///
+/// Disassembles `bytecode_buffer` into the machine-readable instructions, formatting the operands
+/// according to `options`.
+///
+pub fn disassemble_instructions(
+    target_machine: &TargetMachine,
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    options: &Options,
+) -> anyhow::Result<Vec<Instruction>> {
+    let disassembly_text = disassemble_raw(target_machine, bytecode_buffer, options)?;
+
+    Ok(disassembly_text
+        .lines()
+        .filter_map(Instruction::parse)
+        .map(|mut instruction| {
+            for operand in instruction.operands.iter_mut() {
+                *operand = options.format_operands(operand.as_str());
+            }
+            instruction
+        })
+        .collect())
+}
+
+///
+/// Disassembles `bytecode_buffer` with LLVM.
+///
+fn disassemble_raw(
+    target_machine: &TargetMachine,
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    options: &Options,
+) -> anyhow::Result<String> {
+    let disassembly_buffer = target_machine
+        .disassemble(bytecode_buffer, options)
+        .map_err(|error| anyhow::anyhow!("disassembling: {error}"))?;
+    Ok(String::from_utf8_lossy(disassembly_buffer.as_slice()).to_string())
+}
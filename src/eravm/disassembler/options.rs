@@ -0,0 +1,87 @@
+//!
+//! The EraVM disassembler options.
+//!
+
+use std::collections::BTreeMap;
+
+use super::immediate_format::ImmediateFormat;
+
+///
+/// The EraVM disassembler options.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The offset of the first instruction.
+    pub pc: u64,
+    /// The LLVM disassembler mode flags.
+    pub mode: u64,
+    /// The immediate operand format.
+    pub immediate_format: ImmediateFormat,
+    /// The linked addresses to print as labels, keyed by the linker symbol.
+    pub linker_symbols: BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            pc: 0,
+            mode: crate::eravm::r#const::DISASSEMBLER_DEFAULT_MODE,
+            immediate_format: ImmediateFormat::default(),
+            linker_symbols: BTreeMap::new(),
+        }
+    }
+}
+
+impl Options {
+    ///
+    /// Formats the numeric tokens of `operands` according to the options.
+    ///
+    /// The tokens equal to a linked address are replaced with `@` and the linker symbol.
+    ///
+    pub fn format_operands(&self, operands: &str) -> String {
+        let addresses: BTreeMap<num::BigUint, &str> = self
+            .linker_symbols
+            .iter()
+            .map(|(symbol, address)| (num::BigUint::from_bytes_be(address), symbol.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(operands.len());
+        let mut token = String::new();
+        for character in operands.chars().chain(std::iter::once(' ')) {
+            if character.is_ascii_alphanumeric() || character == '_' {
+                token.push(character);
+                continue;
+            }
+            result.push_str(self.format_token(token.as_str(), &addresses).as_str());
+            token.clear();
+            result.push(character);
+        }
+        result.pop();
+        result
+    }
+
+    ///
+    /// Formats a single operand token.
+    ///
+    fn format_token(&self, token: &str, addresses: &BTreeMap<num::BigUint, &str>) -> String {
+        let (value, is_decimal) = match token.strip_prefix("0x") {
+            Some(hexadecimal) => (num::BigUint::parse_bytes(hexadecimal.as_bytes(), 16), false),
+            None if token.bytes().all(|byte| byte.is_ascii_digit()) => {
+                (num::BigUint::parse_bytes(token.as_bytes(), 10), true)
+            }
+            None => (None, false),
+        };
+        let value = match value {
+            Some(value) => value,
+            None => return token.to_owned(),
+        };
+
+        if let Some(symbol) = addresses.get(&value) {
+            return format!("@{symbol}");
+        }
+        match self.immediate_format {
+            ImmediateFormat::Hexadecimal if is_decimal => format!("0x{value:x}"),
+            _ => token.to_owned(),
+        }
+    }
+}
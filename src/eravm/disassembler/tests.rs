@@ -0,0 +1,31 @@
+//!
+//! The EraVM disassembler tests.
+//!
+
+use crate::eravm::disassembler::immediate_format::ImmediateFormat;
+use crate::eravm::disassembler::instruction::Instruction;
+use crate::eravm::disassembler::options::Options;
+
+#[test]
+pub fn check_eravm_disassembler_formatting() {
+    let instruction =
+        Instruction::parse("      10: 00 00 00 01 00 2a 00 00 \tadd\t42, r1, stack[r2 + 3]")
+            .expect("Always valid");
+    assert_eq!(instruction.offset, 0x10);
+    assert_eq!(instruction.encoding.as_deref(), Some("00000001002a0000"));
+    assert_eq!(instruction.mnemonic, "add");
+    assert_eq!(instruction.operands, vec!["42", "r1", "stack[r2 + 3]"]);
+    assert!(Instruction::parse("foo:").is_none());
+
+    let mut options = Options {
+        immediate_format: ImmediateFormat::Hexadecimal,
+        ..Default::default()
+    };
+    let mut address = [0u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS];
+    address[era_compiler_common::BYTE_LENGTH_ETH_ADDRESS - 1] = 0xff;
+    options.linker_symbols.insert("Library".to_owned(), address);
+    assert_eq!(
+        options.format_operands("42, r1, stack[r2 + 3], code[0xff], 255"),
+        "0x2a, r1, stack[r2 + 0x3], code[@Library], @Library"
+    );
+}
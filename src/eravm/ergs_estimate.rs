@@ -0,0 +1,42 @@
+//!
+//! The EraVM static ergs estimation.
+//!
+
+use std::collections::BTreeMap;
+
+use zkevm_opcode_defs::decoding::VmEncodingMode;
+
+use crate::source_map::SourceMap;
+
+///
+/// Returns the static ergs costs of the functions of `bytecode`, keyed by the function name.
+///
+/// The function boundaries are taken from `assembly`, which `bytecode` must be assembled from.
+/// The cost of each instruction is its base price from the `zkevm_opcode_defs` tables, so the
+/// costs of the memory growth, decommits, and far calls are not counted. Each instruction is
+/// counted once regardless of loops and branches.
+///
+pub fn estimate(bytecode: &[u8], assembly: &str) -> anyhow::Result<BTreeMap<String, u64>> {
+    let (source_map, _) =
+        SourceMap::parse_assembly(assembly, |_mnemonic| era_compiler_common::BYTE_LENGTH_X64);
+
+    let mut estimates = BTreeMap::new();
+    for entry in source_map.entries.iter() {
+        let instruction: [u8; era_compiler_common::BYTE_LENGTH_X64] = bytecode
+            .get(entry.offset..entry.offset + era_compiler_common::BYTE_LENGTH_X64)
+            .and_then(|instruction| instruction.try_into().ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Instruction at offset {} is out of the bytecode bounds",
+                    entry.offset
+                )
+            })?;
+        let (opcode, _) = zkevm_opcode_defs::decoding::EncodingModeProduction::decode(
+            u64::from_be_bytes(instruction),
+        );
+        *estimates
+            .entry(source_map.functions[entry.function].to_owned())
+            .or_insert(0) += opcode.variant.ergs_price() as u64;
+    }
+    Ok(estimates)
+}
@@ -3,20 +3,23 @@
 //!
 
 use inkwell::values::BasicValue;
-use num::ToPrimitive;
 
+use crate::context::const_operand::ConstOperand;
 use crate::context::function::declaration::Declaration as FunctionDeclaration;
 use crate::context::IContext;
 use crate::eravm::context::address_space::AddressSpace;
 use crate::eravm::context::function::runtime::Runtime;
+use crate::eravm::context::simulation_registry::arguments::Arguments as SimulationArguments;
 use crate::eravm::context::Context;
+use crate::eravm::extensions::simulation::Simulation;
 use crate::eravm::Dependency;
 
 ///
 /// Translates a contract call.
 ///
 /// If the `simulation_address` is specified, the call is substituted with another instruction
-/// according to the specification.
+/// according to the specification. The built-in simulations are checked first, and then the
+/// ones registered with `Context::register_simulation`.
 ///
 #[allow(clippy::too_many_arguments)]
 pub fn default<'ctx, D>(
@@ -35,534 +38,29 @@ where
     D: Dependency,
 {
     if context.are_eravm_extensions_enabled() {
-        let simulation_address = constants
-            .get_mut(1)
-            .and_then(|option| option.take())
-            .and_then(|value| value.to_u16());
+        let simulation_address =
+            ConstOperand::take("Simulation address", constants.as_mut_slice(), 1)
+                .to_u16()
+                .ok();
 
-        match simulation_address {
-            Some(era_compiler_common::ERAVM_ADDRESS_TO_L1) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "to_l1",
-                )?;
-
-                let is_first = gas;
-                let in_0 = value.expect("Always exists");
-                let in_1 = input_offset;
-
-                return crate::eravm::extensions::general::to_l1(context, is_first, in_0, in_1);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_PRECOMPILE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "precompile",
-                )?;
-
-                let in_0 = gas;
-                let gas_left = input_offset;
-
-                return crate::eravm::extensions::general::precompile(context, in_0, gas_left);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_DECOMMIT) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "decommit",
-                )?;
-
-                let in_0 = gas;
-                let gas_left = input_offset;
-
-                return crate::eravm::extensions::general::decommit(context, in_0, gas_left);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SET_CONTEXT_VALUE_CALL) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "set_context_value",
-                )?;
-
-                let value = value.expect("Always exists");
-
-                return crate::eravm::extensions::general::set_context_value(context, value);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SET_PUBDATA_PRICE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "set_pubdata_price",
-                )?;
-
-                let price = gas;
-
-                return crate::eravm::extensions::general::set_pubdata_price(context, price);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_INCREMENT_TX_COUNTER) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "increment_tx_counter",
-                )?;
-
-                return crate::eravm::extensions::general::increment_tx_counter(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_CODE_ADDRESS) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "code_address",
-                )?;
-
-                return crate::eravm::extensions::general::code_source(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_META) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "meta",
-                )?;
-
-                return crate::eravm::extensions::general::meta(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_PTR_CALLDATA) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "get_global_ptr_calldata",
-                )?;
-
-                let pointer = context.get_global_value(crate::eravm::GLOBAL_CALLDATA_POINTER)?;
-                let value = context.builder().build_ptr_to_int(
-                    pointer.into_pointer_value(),
-                    context.field_type(),
-                    "calldata_abi_integer",
-                )?;
-                return Ok(value.as_basic_value_enum());
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_CALL_FLAGS) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "get_global_call_flags",
-                )?;
-
-                return context.get_global_value(crate::eravm::GLOBAL_CALL_FLAGS);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_PTR_RETURN_DATA) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "get_global_ptr_return_data",
-                )?;
-
-                let pointer = context.get_global_value(crate::eravm::GLOBAL_RETURN_DATA_POINTER)?;
-                let value = context.builder().build_ptr_to_int(
-                    pointer.into_pointer_value(),
-                    context.field_type(),
-                    "return_data_abi_integer",
-                )?;
-                return Ok(value.as_basic_value_enum());
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_EXTRA_ABI_DATA) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "get_global_extra_abi_data",
-                )?;
-
-                let index = gas;
-
-                return crate::eravm::extensions::abi::get_extra_abi_data(context, index);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_MULTIPLICATION_HIGH_REGISTER) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "multiplication_high_register",
-                )?;
-
-                let operand_1 = gas;
-                let operand_2 = input_offset;
-
-                return crate::eravm::extensions::math::multiplication_512(
-                    context, operand_1, operand_2,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_EVENT_INITIALIZE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "event_initialize",
-                )?;
-
-                let operand_1 = gas;
-                let operand_2 = value.expect("Always exists");
-
-                return crate::eravm::extensions::general::event(
-                    context, operand_1, operand_2, true,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_EVENT_WRITE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "event_initialize",
-                )?;
-
-                let operand_1 = gas;
-                let operand_2 = value.expect("Always exists");
-
-                return crate::eravm::extensions::general::event(
-                    context, operand_1, operand_2, false,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_MIMIC_CALL) => {
-                let address = gas;
-                let abi_data = input_offset;
-                let mimic = input_length;
-
-                return crate::eravm::extensions::call::mimic(
-                    context,
-                    context.llvm_runtime().mimic_call,
-                    address,
-                    mimic,
-                    abi_data.as_basic_value_enum(),
-                    vec![],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_MIMIC_CALL_BYREF) => {
-                let address = gas;
-                let mimic = input_length;
-                let abi_data = context.get_active_pointer(context.field_const(0))?;
-
-                return crate::eravm::extensions::call::mimic(
-                    context,
-                    context.llvm_runtime().mimic_call_byref,
-                    address,
-                    mimic,
-                    abi_data.as_basic_value_enum(),
-                    vec![],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SYSTEM_MIMIC_CALL) => {
-                let address = gas;
-                let abi_data = input_offset;
-                let mimic = input_length;
-                let extra_value_1 = output_offset;
-                let extra_value_2 = output_length;
-
-                return crate::eravm::extensions::call::mimic(
-                    context,
-                    context.llvm_runtime().mimic_call,
-                    address,
-                    mimic,
-                    abi_data.as_basic_value_enum(),
-                    vec![extra_value_1, extra_value_2],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SYSTEM_MIMIC_CALL_BYREF) => {
-                let address = gas;
-                let mimic = input_length;
-                let abi_data = context.get_active_pointer(context.field_const(0))?;
-                let extra_value_1 = output_offset;
-                let extra_value_2 = output_length;
-
-                return crate::eravm::extensions::call::mimic(
-                    context,
-                    context.llvm_runtime().mimic_call_byref,
-                    address,
-                    mimic,
-                    abi_data.as_basic_value_enum(),
-                    vec![extra_value_1, extra_value_2],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_RAW_FAR_CALL) => {
-                let address = gas;
-                let abi_data = input_length;
-
-                return crate::eravm::extensions::call::raw_far(
-                    context,
-                    context.llvm_runtime().modify(function, false),
-                    address,
-                    abi_data.as_basic_value_enum(),
-                    output_offset,
-                    output_length,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_RAW_FAR_CALL_BYREF) => {
-                let address = gas;
-                let abi_data = context.get_active_pointer(context.field_const(0))?;
-
-                return crate::eravm::extensions::call::raw_far(
-                    context,
-                    context.llvm_runtime().modify(function, true),
-                    address,
-                    abi_data.as_basic_value_enum(),
-                    output_offset,
-                    output_length,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SYSTEM_CALL) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "system_call",
-                )?;
-
-                let address = gas;
-                let abi_data = input_length;
-                let extra_value_1 = value.expect("Always exists");
-                let extra_value_2 = input_offset;
-                let extra_value_3 = output_offset;
-                let extra_value_4 = output_length;
-
-                return crate::eravm::extensions::call::system(
-                    context,
-                    context.llvm_runtime().modify(function, false),
-                    address,
-                    abi_data.as_basic_value_enum(),
-                    context.field_const(0),
-                    context.field_const(0),
-                    vec![extra_value_1, extra_value_2, extra_value_3, extra_value_4],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_SYSTEM_CALL_BYREF) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().far_call,
-                    function,
-                    "system_call_by_ref",
-                )?;
-
-                let address = gas;
-                let abi_data = context.get_active_pointer(context.field_const(0))?;
-                let extra_value_1 = value.expect("Always exists");
-                let extra_value_2 = input_offset;
-                let extra_value_3 = output_offset;
-                let extra_value_4 = output_length;
-
-                return crate::eravm::extensions::call::system(
-                    context,
-                    context.llvm_runtime().modify(function, true),
-                    address,
-                    abi_data.as_basic_value_enum(),
-                    context.field_const(0),
-                    context.field_const(0),
-                    vec![extra_value_1, extra_value_2, extra_value_3, extra_value_4],
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_CALLDATA) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_load_calldata",
-                )?;
-
-                return crate::eravm::extensions::abi::calldata_ptr_to_active(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_RETURN_DATA) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_load_return_data",
-                )?;
-
-                return crate::eravm::extensions::abi::return_data_ptr_to_active(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_DECOMMIT) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_load_decommit",
-                )?;
-
-                return crate::eravm::extensions::abi::decommit_ptr_to_active(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_ADD) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_add",
-                )?;
-
-                let offset = gas;
-
-                return crate::eravm::extensions::abi::active_ptr_add_assign(context, offset);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_SHRINK) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_shrink",
-                )?;
-
-                let offset = gas;
-
-                return crate::eravm::extensions::abi::active_ptr_shrink_assign(context, offset);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_PACK) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_pack",
-                )?;
-
-                let data = gas;
-
-                return crate::eravm::extensions::abi::active_ptr_pack_assign(context, data);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_LOAD) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_data_load",
-                )?;
-
-                let offset = gas;
-
-                return crate::eravm::extensions::abi::active_ptr_data_load(context, offset);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_COPY) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_data_copy",
-                )?;
-
-                let destination_offset = gas;
-                let source_offset = input_offset;
-                let size = input_length;
-
-                return crate::eravm::extensions::abi::active_ptr_data_copy(
-                    context,
-                    destination_offset,
-                    source_offset,
-                    size,
-                );
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_SIZE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_data_size",
-                )?;
-
-                return crate::eravm::extensions::abi::active_ptr_data_size(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_SWAP) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "active_ptr_swap",
-                )?;
-
-                let index_1 = gas;
-                let index_2 = input_offset;
-
-                return crate::eravm::extensions::abi::active_ptr_swap(context, index_1, index_2);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_RETURN_FORWARD) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "return_forward",
-                )?;
-
-                return crate::eravm::extensions::abi::active_ptr_return_forward(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_REVERT_FORWARD) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "revert_forward",
-                )?;
-
-                return crate::eravm::extensions::abi::active_ptr_revert_forward(context);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_DECLARE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "const_array_declare",
-                )?;
-
-                let index = constants
-                    .get_mut(0)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array index is missing"))?
-                    .to_u8()
-                    .ok_or_else(|| anyhow::anyhow!("Const array index must fit into 8 bits"))?;
-                let size = constants
-                    .get_mut(2)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array size is missing"))?
-                    .to_u16()
-                    .ok_or_else(|| anyhow::anyhow!("Const array size must fit into 16 bits"))?;
-
-                return crate::eravm::extensions::const_array::declare(context, index, size);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_SET) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "const_array_set",
-                )?;
-
-                let index = constants
-                    .get_mut(0)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array index is missing"))?
-                    .to_u8()
-                    .ok_or_else(|| anyhow::anyhow!("Const array index must fit into 8 bits"))?;
-                let offset = constants
-                    .get_mut(2)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array offset is missing"))?
-                    .to_u16()
-                    .ok_or_else(|| anyhow::anyhow!("Const array offset must fit into 16 bits"))?;
-                let value = constants
-                    .get_mut(4)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array assigned value is missing"))?;
-
-                return crate::eravm::extensions::const_array::set(context, index, offset, value);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_FINALIZE) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "const_array_finalize",
-                )?;
-
-                let index = constants
-                    .get_mut(0)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array index is missing"))?
-                    .to_u8()
-                    .ok_or_else(|| anyhow::anyhow!("Const array index must fit into 8 bits"))?;
-
-                return crate::eravm::extensions::const_array::finalize(context, index);
-            }
-            Some(era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_GET) => {
-                crate::eravm::extensions::call::validate_call_type(
-                    context.llvm_runtime().static_call,
-                    function,
-                    "const_array_get",
-                )?;
-
-                let index = constants
-                    .get_mut(0)
-                    .and_then(|option| option.take())
-                    .ok_or_else(|| anyhow::anyhow!("Const array index is missing"))?
-                    .to_u8()
-                    .ok_or_else(|| anyhow::anyhow!("Const array index must fit into 8 bits"))?;
-                let offset = input_offset;
-
-                return crate::eravm::extensions::const_array::get(context, index, offset);
+        if let Some(simulation_address) = simulation_address {
+            let arguments = SimulationArguments {
+                function,
+                gas,
+                address,
+                value,
+                input_offset,
+                input_length,
+                output_offset,
+                output_length,
+                constants,
+            };
+            if let Some(simulation) = Simulation::from_address(simulation_address) {
+                return simulation.translate(context, arguments);
+            }
+            if let Some(simulation) = context.simulation(simulation_address) {
+                return simulation(context, arguments);
             }
-            _ => {}
         }
     }
 
@@ -625,14 +123,15 @@ where
 pub fn request<'ctx, D>(
     context: &mut Context<'ctx, D>,
     address: inkwell::values::IntValue<'ctx>,
-    signature: &'static str,
+    signature: &str,
     arguments: Vec<inkwell::values::IntValue<'ctx>>,
 ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
 where
     D: Dependency,
 {
     let signature_hash = era_compiler_common::Hash::keccak256(signature.as_bytes());
-    let signature_hash_value = context.field_const_str_hex(signature_hash.to_string().as_str());
+    let signature_hash_value =
+        context.try_field_const_str_hex(signature_hash.to_string().as_str())?;
 
     let calldata_size = context.field_const(
         (era_compiler_common::BYTE_LENGTH_X32
@@ -687,11 +186,7 @@ where
 {
     let value_zero_block = context.append_basic_block("contract_call_value_zero_block");
     let value_non_zero_block = context.append_basic_block("contract_call_value_non_zero_block");
-    let value_join_block = context.append_basic_block("contract_call_value_join_block");
 
-    let result_pointer =
-        context.build_alloca(context.field_type(), "contract_call_address_result_pointer")?;
-    context.build_store(result_pointer, context.field_const(0))?;
     let is_value_zero = context.builder().build_int_compare(
         inkwell::IntPredicate::EQ,
         value,
@@ -722,8 +217,7 @@ where
             context.field_const(u64::from(crate::eravm::r#const::NO_SYSTEM_CALL_BIT)),
         ],
     )?;
-    context.build_store(result_pointer, result)?;
-    context.build_unconditional_branch(value_join_block)?;
+    let value_non_zero_result = (result, context.basic_block());
 
     context.set_basic_block(value_zero_block);
     let function = Runtime::default_call(context, function);
@@ -741,10 +235,11 @@ where
             "default_call",
         )?
         .expect("Always exists");
-    context.build_store(result_pointer, result)?;
-    context.build_unconditional_branch(value_join_block)?;
+    let value_zero_result = (result, context.basic_block());
 
-    context.set_basic_block(value_join_block);
-    let result = context.build_load(result_pointer, "contract_call_address_result")?;
-    Ok(result)
+    context.merge_values(
+        context.field_type(),
+        &[value_non_zero_result, value_zero_result],
+        "contract_call_value_result",
+    )
 }
@@ -29,7 +29,8 @@ where
 {
     let signature_hash =
         era_compiler_common::Hash::keccak256(crate::eravm::DEPLOYER_SIGNATURE_CREATE.as_bytes());
-    let signature_hash_value = context.field_const_str_hex(signature_hash.to_string().as_str());
+    let signature_hash_value =
+        context.try_field_const_str_hex(signature_hash.to_string().as_str())?;
 
     let salt = context.field_const(0);
 
@@ -69,7 +70,8 @@ where
 {
     let signature_hash =
         era_compiler_common::Hash::keccak256(crate::eravm::DEPLOYER_SIGNATURE_CREATE2.as_bytes());
-    let signature_hash_value = context.field_const_str_hex(signature_hash.to_string().as_str());
+    let signature_hash_value =
+        context.try_field_const_str_hex(signature_hash.to_string().as_str())?;
 
     let salt = salt.unwrap_or_else(|| context.field_const(0));
 
@@ -148,6 +150,35 @@ where
     Ok(Value::new(value))
 }
 
+///
+/// Translates the reference to the canonical hash of the contract's own bytecode.
+///
+/// The placeholder is resolved by the linker after the rest of the contract is linked. The value
+/// is the hash of the bytecode with the placeholders zeroed, which is not the deployed code hash,
+/// so it must not be compared with `extcodehash` or used to deploy the contract itself.
+///
+pub fn self_canonical_bytecode_hash<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+) -> anyhow::Result<Value<'ctx>>
+where
+    D: Dependency,
+{
+    let value = context
+        .build_call_metadata(
+            context.intrinsics().factory_dependency,
+            &[context
+                .llvm()
+                .metadata_node(&[context
+                    .llvm()
+                    .metadata_string(crate::eravm::r#const::SELF_CANONICAL_BYTECODE_HASH_SYMBOL)
+                    .into()])
+                .into()],
+            "self_canonical_bytecode_hash",
+        )?
+        .expect("Always exists");
+    Ok(Value::new(value))
+}
+
 ///
 /// Translates the deployer call header size instruction, Usually, the header consists of:
 /// - the deployer contract method signature
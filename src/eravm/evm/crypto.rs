@@ -4,6 +4,7 @@
 
 use inkwell::values::BasicValue;
 
+use crate::context::pointer::Pointer;
 use crate::context::IContext;
 use crate::eravm::context::address_space::AddressSpace;
 use crate::eravm::context::function::Function as EraVMFunction;
@@ -13,6 +14,10 @@ use crate::eravm::Dependency;
 ///
 /// Translates the `sha3` instruction.
 ///
+/// If `length` is a compile-time zero, the result is folded to the hash of the empty input, as
+/// no memory is read. Other constant inputs are folded only if the hash constant folding is
+/// enabled, and the memory range has been written with constants in the current basic block.
+///
 pub fn sha3<'ctx, D>(
     context: &mut Context<'ctx, D>,
     offset: inkwell::values::IntValue<'ctx>,
@@ -21,9 +26,21 @@ pub fn sha3<'ctx, D>(
 where
     D: Dependency,
 {
-    let offset_pointer = context.builder().build_int_to_ptr(
+    if length.get_zero_extended_constant() == Some(0) {
+        let hash = crate::const_hash::keccak256(&[]);
+        return Ok(context
+            .field_const_big(&num::BigUint::from_bytes_be(hash.as_slice()))?
+            .as_basic_value_enum());
+    }
+    if let Some(hash) = context.fold_constant_hash(AddressSpace::Heap, offset, length) {
+        return Ok(hash.as_basic_value_enum());
+    }
+
+    let offset_pointer = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.byte_type(),
         offset,
-        context.ptr_type(AddressSpace::Heap.into()),
         "sha3_offset_pointer",
     )?;
 
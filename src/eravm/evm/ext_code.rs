@@ -41,3 +41,23 @@ where
         vec![address],
     )
 }
+
+///
+/// Translates the `extcodecopy` instruction.
+///
+/// The code is requested from the code oracle system contract.
+/// As on EVM, the `size` bytes at `destination_offset` are always written: the bytes beyond the
+/// end of the code, or the whole range if the account has no code, are zero-filled.
+///
+pub fn copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    address: inkwell::values::IntValue<'ctx>,
+    destination_offset: inkwell::values::IntValue<'ctx>,
+    source_offset: inkwell::values::IntValue<'ctx>,
+    size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::code::copy(context, address, destination_offset, source_offset, size)
+}
@@ -68,6 +68,23 @@ where
     }
 }
 
+///
+/// Translates the contract immutable load by `identifier`.
+///
+/// The immutable is allocated on its first use, so the front-end does not have to know all
+/// immutables up front. Requires the Solidity data or the deferred immutables mode.
+///
+pub fn load_by_identifier<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    identifier: &str,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let offset = context.get_or_allocate_immutable(identifier)?;
+    load(context, context.field_const(offset as u64))
+}
+
 ///
 /// Translates the contract immutable store.
 ///
@@ -132,3 +149,21 @@ where
         }
     }
 }
+
+///
+/// Translates the contract immutable store by `identifier`.
+///
+/// The immutable is allocated on its first use, so the front-end does not have to know all
+/// immutables up front. Requires the Solidity data or the deferred immutables mode.
+///
+pub fn store_by_identifier<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    identifier: &str,
+    value: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let offset = context.get_or_allocate_immutable(identifier)?;
+    store(context, context.field_const(offset as u64), value)
+}
@@ -19,3 +19,7 @@ pub mod memory;
 pub mod r#return;
 pub mod return_data;
 pub mod storage;
+pub mod verbatim;
+
+#[cfg(test)]
+mod tests;
@@ -5,6 +5,7 @@
 use crate::context::pointer::Pointer;
 use crate::context::IContext;
 use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::selfdestruct_policy::SelfdestructPolicy;
 use crate::eravm::context::Context;
 use crate::eravm::Dependency;
 
@@ -48,16 +49,41 @@ where
                 ),
                 "immutables_number_pointer",
             )?;
-            let immutable_values_size = context.immutables_size();
-            context.build_store(
-                immutables_number_pointer,
+            let immutables_number = if context.is_immutables_deferred() {
+                context
+                    .get_global_value(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)?
+                    .into_int_value()
+            } else {
                 context.field_const(
-                    (immutable_values_size / era_compiler_common::BYTE_LENGTH_FIELD) as u64,
-                ),
-            )?;
+                    (context.immutables_size() / era_compiler_common::BYTE_LENGTH_FIELD) as u64,
+                )
+            };
+            if !context.is_immutables_deferred() {
+                if let Ok(immutables_number_expected) =
+                    context.get_global_value(crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED)
+                {
+                    let is_mismatch = context.builder().build_int_compare(
+                        inkwell::IntPredicate::NE,
+                        immutables_number,
+                        immutables_number_expected.into_int_value(),
+                        "immutables_number_is_mismatch",
+                    )?;
+                    let mismatch_block =
+                        context.append_basic_block("immutables_number_mismatch_block");
+                    let join_block = context.append_basic_block("immutables_number_join_block");
+                    context.build_conditional_branch(is_mismatch, mismatch_block, join_block)?;
+
+                    context.set_basic_block(mismatch_block);
+                    context.build_call(context.intrinsics().trap, &[], "immutables_number_trap")?;
+                    context.build_unreachable()?;
+
+                    context.set_basic_block(join_block);
+                }
+            }
+            context.build_store(immutables_number_pointer, immutables_number)?;
             let immutables_size = context.builder().build_int_mul(
-                context.field_const(immutable_values_size as u64),
-                context.field_const(2),
+                immutables_number,
+                context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 2) as u64),
                 "immutables_size",
             )?;
             let return_data_length = context.builder().build_int_add(
@@ -107,6 +133,44 @@ where
     r#return(context, context.field_const(0), context.field_const(0))
 }
 
+///
+/// Translates the `selfdestruct` instruction according to the policy set in the Solidity data.
+///
+/// Without the Solidity data, the instruction is reported as an error.
+///
+pub fn selfdestruct<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    beneficiary: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let policy = context
+        .solidity()
+        .map(|data| data.selfdestruct_policy().to_owned())
+        .unwrap_or_default();
+    match policy {
+        SelfdestructPolicy::Error => {
+            anyhow::bail!("The `SELFDESTRUCT` instruction is not supported on EraVM")
+        }
+        SelfdestructPolicy::SystemCall { address, signature } => {
+            crate::eravm::evm::call::request(
+                context,
+                context.field_const(address.into()),
+                signature.as_str(),
+                vec![beneficiary],
+            )?;
+        }
+        SelfdestructPolicy::Stop => {
+            context.warn(
+                "selfdestruct",
+                "The `SELFDESTRUCT` instruction only stops the execution on EraVM, and the contract is not destroyed".to_owned(),
+            );
+        }
+    }
+    stop(context)
+}
+
 ///
 /// Translates the `invalid` instruction.
 ///
@@ -6,6 +6,7 @@ use inkwell::types::BasicType;
 use inkwell::values::BasicValue;
 
 use crate::context::pointer::Pointer;
+use crate::context::return_data_copy_policy::ReturnDataCopyPolicy;
 use crate::context::IContext;
 use crate::eravm::context::address_space::AddressSpace;
 use crate::eravm::context::Context;
@@ -38,26 +39,8 @@ pub fn copy<'ctx, D>(
 where
     D: Dependency,
 {
-    let error_block = context.append_basic_block("return_data_copy_error_block");
-    let join_block = context.append_basic_block("return_data_copy_join_block");
-
     let return_data_size = self::size(context)?.into_int_value();
-    let copy_slice_end =
-        context
-            .builder()
-            .build_int_add(source_offset, size, "return_data_copy_slice_end")?;
-    let is_copy_out_of_bounds = context.builder().build_int_compare(
-        inkwell::IntPredicate::UGT,
-        copy_slice_end,
-        return_data_size,
-        "return_data_copy_is_out_of_bounds",
-    )?;
-    context.build_conditional_branch(is_copy_out_of_bounds, error_block, join_block)?;
 
-    context.set_basic_block(error_block);
-    crate::eravm::evm::r#return::revert(context, context.field_const(0), context.field_const(0))?;
-
-    context.set_basic_block(join_block);
     let destination = Pointer::<AddressSpace>::new_with_offset(
         context,
         AddressSpace::Heap,
@@ -82,11 +65,14 @@ where
         "return_data_source_pointer",
     )?;
 
-    context.build_memcpy(
+    context.build_bounded_returndata_copy(
         context.intrinsics().memory_copy_from_generic,
         destination,
         source,
+        source_offset,
         size,
+        return_data_size,
+        ReturnDataCopyPolicy::Revert,
         "return_data_copy_memcpy_from_return_data",
     )?;
     Ok(())
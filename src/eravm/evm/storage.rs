@@ -54,6 +54,8 @@ where
 ///
 /// Translates the transient storage load.
 ///
+/// Returns an error if the target VM version does not support transient storage.
+///
 pub fn transient_load<'ctx, D>(
     context: &mut Context<'ctx, D>,
     position: inkwell::values::IntValue<'ctx>,
@@ -61,6 +63,10 @@ pub fn transient_load<'ctx, D>(
 where
     D: Dependency,
 {
+    if !context.is_transient_storage_supported() {
+        anyhow::bail!("The `tload` instruction is not supported by the target EraVM version");
+    }
+
     let position_pointer = Pointer::new_with_offset(
         context,
         AddressSpace::TransientStorage,
@@ -75,6 +81,8 @@ where
 ///
 /// Translates the transient storage store.
 ///
+/// Returns an error if the target VM version does not support transient storage.
+///
 pub fn transient_store<'ctx, D>(
     context: &mut Context<'ctx, D>,
     position: inkwell::values::IntValue<'ctx>,
@@ -83,6 +91,10 @@ pub fn transient_store<'ctx, D>(
 where
     D: Dependency,
 {
+    if !context.is_transient_storage_supported() {
+        anyhow::bail!("The `tstore` instruction is not supported by the target EraVM version");
+    }
+
     let position_pointer = Pointer::new_with_offset(
         context,
         AddressSpace::TransientStorage,
@@ -0,0 +1,266 @@
+//!
+//! The EVM instructions translation tests.
+//!
+
+use crate::context::IContext;
+use crate::dependency::DummyDependency;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::function::runtime::entry::Entry;
+use crate::eravm::context::function::runtime::Runtime;
+use crate::eravm::context::selfdestruct_policy::SelfdestructPolicy;
+use crate::eravm::context::solidity_data::SolidityData;
+use crate::eravm::context::Context;
+use crate::eravm::WriteLLVM;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::testing::file_check::FileCheck;
+
+///
+/// Creates a context with the current function `name` taking `arguments_length` field arguments.
+///
+fn create_context<'ctx>(
+    llvm: &'ctx inkwell::context::Context,
+    name: &str,
+    arguments_length: usize,
+) -> Context<'ctx, DummyDependency> {
+    let mut context = crate::testing::create_eravm_context(llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, name, arguments_length, 0)
+        .expect("Failed to add function");
+    context
+}
+
+#[test]
+pub fn check_self_canonical_bytecode_hash() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, "self_hash", 0);
+    super::create::self_canonical_bytecode_hash(&mut context).expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %self_canonical_bytecode_hash = call i256 @llvm.eravm.factorydependency(metadata !{{.*}})
+; CHECK: = !{!"$self_canonical_bytecode_hash$"}
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_transient_storage() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, "transient", 2);
+    let function = context
+        .module()
+        .get_function("transient")
+        .expect("Always exists");
+    let position = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let value = function
+        .get_nth_param(1)
+        .expect("Always exists")
+        .into_int_value();
+
+    assert!(context.is_transient_storage_supported());
+    super::storage::transient_load(&mut context, position).expect("Failed to translate");
+    super::storage::transient_store(&mut context, position, value).expect("Failed to translate");
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %transient_storage_load_position_pointer = inttoptr i256 %0 to ptr addrspace(6)
+; CHECK-NEXT: %transient_storage_load_value = load i256, ptr addrspace(6) %transient_storage_load_position_pointer
+; CHECK-NEXT: %transient_storage_store_position_pointer = inttoptr i256 %0 to ptr addrspace(6)
+; CHECK-NEXT: store i256 %1, ptr addrspace(6) %transient_storage_store_position_pointer
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "transient", &file_check).expect("Must match");
+
+    context.set_transient_storage_supported(false);
+    let error = super::storage::transient_load(&mut context, position)
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(error.contains("`tload`"), "{error}");
+    let error = super::storage::transient_store(&mut context, position, value)
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(error.contains("`tstore`"), "{error}");
+}
+
+#[test]
+pub fn check_value_call() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    Runtime::default()
+        .declare(&mut context)
+        .expect("Failed to declare runtime");
+    let function = crate::testing::add_function(&mut context, "value_call", 3, 0)
+        .expect("Failed to add function");
+    Entry::initialize_globals(&mut context).expect("Failed to initialize globals");
+    let generic_pointer_type = context.ptr_type(AddressSpace::Generic.into());
+    context
+        .set_global(
+            crate::eravm::GLOBAL_RETURN_DATA_POINTER,
+            generic_pointer_type,
+            AddressSpace::Stack,
+            generic_pointer_type.const_null(),
+        )
+        .expect("Always valid");
+    let operand = |index| {
+        function
+            .get_nth_param(index)
+            .expect("Always exists")
+            .into_int_value()
+    };
+
+    super::call::default(
+        &mut context,
+        context.llvm_runtime().far_call,
+        operand(0),
+        operand(2),
+        Some(operand(1)),
+        context.field_const(0),
+        context.field_const(0),
+        context.field_const(0),
+        context.field_const(0),
+        vec![None; 7],
+    )
+    .expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %contract_call_is_value_zero = icmp eq i256 %1, 0
+; CHECK-NEXT: br i1 %contract_call_is_value_zero, label %contract_call_value_zero_block, label %contract_call_value_non_zero_block
+; CHECK: contract_call_value_zero_block:
+; CHECK-NEXT: %default_call = call i256 {{.*}}(i256 %0, i256 %2, i256 0, i256 0, i256 0, i256 0)
+; CHECK-NEXT: br label %contract_call_value_result_join_block
+; CHECK: contract_call_value_result_join_block:
+; CHECK-NEXT: %contract_call_value_result = phi i256 [ {{.*}} ], [ %default_call, %contract_call_value_zero_block ]
+; CHECK-NOT: contract_call_address_result_pointer
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "value_call", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_selfdestruct() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, "selfdestruct_error", 1);
+    context.set_code_segment(era_compiler_common::CodeSegment::Runtime);
+    let beneficiary = |context: &Context<DummyDependency>, name: &str| {
+        context
+            .module()
+            .get_function(name)
+            .expect("Always exists")
+            .get_nth_param(0)
+            .expect("Always exists")
+            .into_int_value()
+    };
+
+    let error =
+        super::r#return::selfdestruct(&mut context, beneficiary(&context, "selfdestruct_error"))
+            .expect_err("Must be rejected")
+            .to_string();
+    assert!(error.contains("`SELFDESTRUCT`"), "{error}");
+
+    let mut solidity_data = SolidityData::new();
+    solidity_data.set_selfdestruct_policy(SelfdestructPolicy::Stop);
+    context.set_solidity_data(solidity_data);
+    crate::testing::add_function(&mut context, "selfdestruct_stop", 1, 0)
+        .expect("Failed to add function");
+    super::r#return::selfdestruct(&mut context, beneficiary(&context, "selfdestruct_stop"))
+        .expect("Failed to translate");
+    assert_eq!(context.diagnostics().len(), 1);
+    assert_eq!(context.diagnostics()[0].code, "selfdestruct");
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK-NOT: @__system_request
+; CHECK: call void @__return(i256 0, i256 0, i256 {{.*}})
+; CHECK-NEXT: unreachable
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "selfdestruct_stop", &file_check).expect("Must match");
+
+    context
+        .solidity_mut()
+        .expect("Always exists")
+        .set_selfdestruct_policy(SelfdestructPolicy::SystemCall {
+            address: 0x8010,
+            signature: "selfdestruct(address)".to_owned(),
+        });
+    crate::testing::add_function(&mut context, "selfdestruct_system_call", 1, 0)
+        .expect("Failed to add function");
+    super::r#return::selfdestruct(
+        &mut context,
+        beneficiary(&context, "selfdestruct_system_call"),
+    )
+    .expect("Failed to translate");
+    assert_eq!(context.diagnostics().len(), 1);
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: @__system_request(i256 32784,
+; CHECK: call void @__return(i256 0, i256 0, i256 {{.*}})
+; CHECK-NEXT: unreachable
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "selfdestruct_system_call", &file_check)
+        .expect("Must match");
+}
+
+#[test]
+pub fn check_immutables_number_check() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm, "deploy_return_unchecked", 0);
+    context.set_code_segment(era_compiler_common::CodeSegment::Deploy);
+    context.set_solidity_data(SolidityData::new());
+    super::r#return::r#return(&mut context, context.field_const(0), context.field_const(0))
+        .expect("Failed to translate");
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK-NOT: immutables_number_is_mismatch
+; CHECK: call void @__return(i256 {{.*}}, i256 64, i256 {{.*}})
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "deploy_return_unchecked", &file_check)
+        .expect("Must match");
+
+    context
+        .set_global(
+            crate::eravm::GLOBAL_IMMUTABLES_NUMBER_EXPECTED,
+            context.field_type(),
+            AddressSpace::Stack,
+            context.field_const(0),
+        )
+        .expect("Always valid");
+    crate::testing::add_function(&mut context, "deploy_return_checked", 0, 0)
+        .expect("Failed to add function");
+    super::r#return::r#return(&mut context, context.field_const(0), context.field_const(0))
+        .expect("Failed to translate");
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %immutables_number_expected = load i256, ptr @immutables_number_expected
+; CHECK-NEXT: %immutables_number_is_mismatch = icmp ne i256 0, %immutables_number_expected
+; CHECK-NEXT: br i1 %immutables_number_is_mismatch, label %immutables_number_mismatch_block, label %immutables_number_join_block
+; CHECK: immutables_number_mismatch_block:
+; CHECK-NEXT: call void @llvm.trap()
+; CHECK-NEXT: unreachable
+; CHECK: immutables_number_join_block:
+; CHECK-NEXT: store i256 0, ptr addrspace(2) {{.*}}
+; CHECK-NEXT: call void @__return(i256 {{.*}}, i256 64, i256 {{.*}})
+; CHECK-NEXT: unreachable
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "deploy_return_checked", &file_check)
+        .expect("Must match");
+}
@@ -0,0 +1,55 @@
+//!
+//! Translates the Yul `verbatim` instructions.
+//!
+
+use crate::context::IContext;
+use crate::diagnostic::severity::Severity as DiagnosticSeverity;
+use crate::diagnostic::span::Span;
+use crate::diagnostic::Diagnostic;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+/// The diagnostic code of the unsupported `verbatim` instruction.
+pub const DIAGNOSTIC_CODE: &str = "unsupported-verbatim";
+
+///
+/// Translates the `verbatim_<N>i_<M>o` instruction.
+///
+/// EraVM cannot execute raw EVM bytes, so an error diagnostic with `span` is reported and
+/// returned as the error. The EraVM extensions using the `verbatim` syntax, e.g. the
+/// `get_global::` getters, are dispatched by the front-end by their identifiers instead.
+///
+/// The signature matches the EVM counterpart, so the front-end can call either one.
+///
+pub fn verbatim<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    data: &[u8],
+    input_values: Vec<inkwell::values::IntValue<'ctx>>,
+    output_size: usize,
+    span: Option<Span>,
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    let function = context
+        .builder()
+        .get_insert_block()
+        .and_then(|block| block.get_parent())
+        .map(|function| function.get_name().to_string_lossy().to_string());
+    let mut diagnostic = Diagnostic::new(
+        DiagnosticSeverity::Error,
+        DIAGNOSTIC_CODE,
+        format!(
+            "`verbatim_{}i_{output_size}o` with {} raw bytes is not supported on EraVM",
+            input_values.len(),
+            data.len(),
+        ),
+    )
+    .with_function(function);
+    if let Some(span) = span {
+        diagnostic = diagnostic.with_span(span);
+    }
+
+    context.push_diagnostic(diagnostic.clone());
+    anyhow::bail!("{diagnostic}")
+}
@@ -0,0 +1,179 @@
+//!
+//! Translates the account abstraction patterns of the EraVM system contracts.
+//!
+
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+///
+/// Returns the left-aligned 32-byte word of the account validation success magic.
+///
+pub fn account_validation_success_magic<'ctx, D>(
+    context: &Context<'ctx, D>,
+) -> inkwell::values::IntValue<'ctx>
+where
+    D: Dependency,
+{
+    context.field_const_str_hex(
+        format!(
+            "{:0<64}",
+            crate::eravm::r#const::ACCOUNT_VALIDATION_SUCCESS_MAGIC
+        )
+        .as_str(),
+    )
+}
+
+///
+/// Returns the left-aligned 32-byte word of the paymaster validation success magic.
+///
+pub fn paymaster_validation_success_magic<'ctx, D>(
+    context: &Context<'ctx, D>,
+) -> inkwell::values::IntValue<'ctx>
+where
+    D: Dependency,
+{
+    context.field_const_str_hex(
+        format!(
+            "{:0<64}",
+            crate::eravm::r#const::PAYMASTER_VALIDATION_SUCCESS_MAGIC
+        )
+        .as_str(),
+    )
+}
+
+///
+/// Returns from the account `validateTransaction` entrypoint with the success magic.
+///
+/// The magic is written to the beginning of the heap, as the function never returns control.
+///
+pub fn return_account_validation_success<D>(context: &mut Context<D>) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let magic = account_validation_success_magic(context);
+    crate::eravm::evm::memory::store(context, context.field_const(0), magic)?;
+    crate::eravm::evm::r#return::r#return(
+        context,
+        context.field_const(0),
+        context.field_const(era_compiler_common::BYTE_LENGTH_FIELD as u64),
+    )
+}
+
+///
+/// Returns from the paymaster `validateAndPayForPaymasterTransaction` entrypoint with the success
+/// magic and an empty context.
+///
+/// The return data is ABI-encoded as `(bytes4, bytes)`, that is, the magic, the context offset,
+/// and the zero context length.
+///
+pub fn return_paymaster_validation_success<D>(context: &mut Context<D>) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let magic = paymaster_validation_success_magic(context);
+    crate::eravm::evm::memory::store(context, context.field_const(0), magic)?;
+    crate::eravm::evm::memory::store(
+        context,
+        context.field_const(era_compiler_common::BYTE_LENGTH_FIELD as u64),
+        context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 2) as u64),
+    )?;
+    crate::eravm::evm::memory::store(
+        context,
+        context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 2) as u64),
+        context.field_const(0),
+    )?;
+    crate::eravm::evm::r#return::r#return(
+        context,
+        context.field_const(0),
+        context.field_const((era_compiler_common::BYTE_LENGTH_FIELD * 3) as u64),
+    )
+}
+
+///
+/// Requests the minimal nonce of the `address` from the nonce holder system contract.
+///
+pub fn get_min_nonce<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    address: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::evm::call::request(
+        context,
+        context.field_const(zkevm_opcode_defs::ADDRESS_NONCE_HOLDER.into()),
+        crate::eravm::r#const::NONCE_HOLDER_SIGNATURE_GET_MIN_NONCE,
+        vec![address],
+    )
+}
+
+///
+/// Increments the minimal nonce of the current account, if it is equal to `expected_nonce`.
+///
+/// The calldata is written to the external call area of the auxiliary heap, and the nonce holder
+/// is called with the system call bit set, as its mutating methods are only available to system
+/// calls. Returns the call status code.
+///
+pub fn increment_min_nonce_if_equals<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    expected_nonce: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let signature_hash = era_compiler_common::Hash::keccak256(
+        crate::eravm::r#const::NONCE_HOLDER_SIGNATURE_INCREMENT_MIN_NONCE_IF_EQUALS.as_bytes(),
+    );
+    let signature_hash_value =
+        context.try_field_const_str_hex(signature_hash.to_string().as_str())?;
+
+    let input_offset = context.field_const(crate::eravm::HEAP_AUX_OFFSET_EXTERNAL_CALL);
+    let input_length = context.field_const(
+        (era_compiler_common::BYTE_LENGTH_X32 + era_compiler_common::BYTE_LENGTH_FIELD) as u64,
+    );
+
+    let signature_pointer = Pointer::new_with_offset(
+        context,
+        AddressSpace::HeapAuxiliary,
+        context.field_type(),
+        input_offset,
+        "increment_min_nonce_signature_pointer",
+    )?;
+    context.build_store(signature_pointer, signature_hash_value)?;
+
+    let expected_nonce_offset = context.builder().build_int_add(
+        input_offset,
+        context.field_const(era_compiler_common::BYTE_LENGTH_X32 as u64),
+        "increment_min_nonce_expected_nonce_offset",
+    )?;
+    let expected_nonce_pointer = Pointer::new_with_offset(
+        context,
+        AddressSpace::HeapAuxiliary,
+        context.field_type(),
+        expected_nonce_offset,
+        "increment_min_nonce_expected_nonce_pointer",
+    )?;
+    context.build_store(expected_nonce_pointer, expected_nonce)?;
+
+    let abi_data = crate::eravm::utils::abi_data(
+        context,
+        input_offset,
+        input_length,
+        None,
+        AddressSpace::HeapAuxiliary,
+        true,
+    )?;
+
+    crate::eravm::extensions::call::system(
+        context,
+        context.llvm_runtime().far_call,
+        context.field_const(zkevm_opcode_defs::ADDRESS_NONCE_HOLDER.into()),
+        abi_data,
+        context.field_const(0),
+        context.field_const(0),
+        vec![],
+    )
+}
@@ -5,9 +5,8 @@
 use inkwell::types::BasicType;
 use inkwell::values::BasicValue;
 
-use crate::context::pointer::Pointer;
 use crate::context::IContext;
-use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::active_pointer::ActivePointer;
 use crate::eravm::context::Context;
 use crate::eravm::Dependency;
 
@@ -45,11 +44,7 @@ pub fn calldata_ptr_to_active<'ctx, D>(
 where
     D: Dependency,
 {
-    let calldata_pointer = context.get_global_value(crate::eravm::GLOBAL_CALLDATA_POINTER)?;
-    context.set_active_pointer(
-        context.field_const(0),
-        calldata_pointer.into_pointer_value(),
-    )?;
+    ActivePointer::primary(context).load_calldata(context)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -62,11 +57,7 @@ pub fn return_data_ptr_to_active<'ctx, D>(
 where
     D: Dependency,
 {
-    let return_data_pointer = context.get_global_value(crate::eravm::GLOBAL_RETURN_DATA_POINTER)?;
-    context.set_active_pointer(
-        context.field_const(0),
-        return_data_pointer.into_pointer_value(),
-    )?;
+    ActivePointer::primary(context).load_return_data(context)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -79,11 +70,7 @@ pub fn decommit_ptr_to_active<'ctx, D>(
 where
     D: Dependency,
 {
-    let decommit_pointer = context.get_global_value(crate::eravm::GLOBAL_DECOMMIT_POINTER)?;
-    context.set_active_pointer(
-        context.field_const(0),
-        decommit_pointer.into_pointer_value(),
-    )?;
+    ActivePointer::primary(context).load_decommit(context)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -97,14 +84,7 @@ pub fn active_ptr_add_assign<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let active_pointer_shifted = context.build_gep(
-        Pointer::new(context.byte_type(), AddressSpace::Generic, active_pointer),
-        &[offset],
-        context.byte_type().as_basic_type_enum(),
-        "active_pointer_shifted",
-    )?;
-    context.set_active_pointer(context.field_const(0), active_pointer_shifted.value)?;
+    ActivePointer::primary(context).add(context, offset)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -118,21 +98,7 @@ pub fn active_ptr_shrink_assign<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let active_pointer_shrunken = context
-        .build_call(
-            context.intrinsics().pointer_shrink,
-            &[
-                active_pointer.as_basic_value_enum(),
-                offset.as_basic_value_enum(),
-            ],
-            "active_pointer_shrunken",
-        )?
-        .expect("Always returns a pointer");
-    context.set_active_pointer(
-        context.field_const(0),
-        active_pointer_shrunken.into_pointer_value(),
-    )?;
+    ActivePointer::primary(context).shrink(context, offset)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -146,21 +112,7 @@ pub fn active_ptr_pack_assign<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let active_pointer_packed = context
-        .build_call(
-            context.intrinsics().pointer_pack,
-            &[
-                active_pointer.as_basic_value_enum(),
-                data.as_basic_value_enum(),
-            ],
-            "active_pointer_packed",
-        )?
-        .expect("Always returns a pointer");
-    context.set_active_pointer(
-        context.field_const(0),
-        active_pointer_packed.into_pointer_value(),
-    )?;
+    ActivePointer::primary(context).pack(context, data)?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
 
@@ -174,15 +126,8 @@ pub fn active_ptr_data_load<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let active_pointer = context.build_gep(
-        Pointer::new(context.byte_type(), AddressSpace::Generic, active_pointer),
-        &[offset],
-        context.field_type().as_basic_type_enum(),
-        "active_pointer_with_offset",
-    )?;
-    let value = context.build_load(active_pointer, "active_pointer_value")?;
-    Ok(value)
+    let value = ActivePointer::primary(context).read(context, offset)?;
+    Ok(value.as_basic_value_enum())
 }
 
 ///
@@ -194,24 +139,8 @@ pub fn active_ptr_data_size<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let active_pointer_value = context.builder().build_ptr_to_int(
-        active_pointer,
-        context.field_type(),
-        "active_pointer_value",
-    )?;
-    let active_pointer_value_shifted = context.builder().build_right_shift(
-        active_pointer_value,
-        context.field_const((era_compiler_common::BIT_LENGTH_X32 * 3) as u64),
-        false,
-        "active_pointer_value_shifted",
-    )?;
-    let active_pointer_length = context.builder().build_and(
-        active_pointer_value_shifted,
-        context.field_const(u32::MAX as u64),
-        "active_pointer_length",
-    )?;
-    Ok(active_pointer_length.as_basic_value_enum())
+    let size = ActivePointer::primary(context).size(context)?;
+    Ok(size.as_basic_value_enum())
 }
 
 ///
@@ -226,28 +155,11 @@ pub fn active_ptr_data_copy<'ctx, D>(
 where
     D: Dependency,
 {
-    let destination = Pointer::new_with_offset(
+    ActivePointer::primary(context).copy_to_heap(
         context,
-        AddressSpace::Heap,
-        context.byte_type(),
         destination_offset,
-        "active_pointer_data_copy_destination_pointer",
-    )?;
-
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
-    let source = context.build_gep(
-        Pointer::new(context.byte_type(), AddressSpace::Generic, active_pointer),
-        &[source_offset],
-        context.byte_type().as_basic_type_enum(),
-        "active_pointer_data_copy_source_pointer",
-    )?;
-
-    context.build_memcpy(
-        context.intrinsics().memory_copy_from_generic,
-        destination,
-        source,
+        source_offset,
         size,
-        "active_pointer_data_copy_memcpy_from_child",
     )?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
@@ -261,10 +173,10 @@ pub fn active_ptr_return_forward<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
+    let active_pointer = ActivePointer::primary(context).get(context)?;
     context.build_call(
         context.llvm_runtime().return_forward,
-        &[active_pointer.as_basic_value_enum()],
+        &[active_pointer.value.as_basic_value_enum()],
         "active_pointer_return_forward",
     )?;
     context.build_unreachable()?;
@@ -280,10 +192,10 @@ pub fn active_ptr_revert_forward<'ctx, D>(
 where
     D: Dependency,
 {
-    let active_pointer = context.get_active_pointer(context.field_const(0))?;
+    let active_pointer = ActivePointer::primary(context).get(context)?;
     context.build_call(
         context.llvm_runtime().revert_forward,
-        &[active_pointer.as_basic_value_enum()],
+        &[active_pointer.value.as_basic_value_enum()],
         "active_pointer_revert_forward",
     )?;
     context.build_unreachable()?;
@@ -301,11 +213,6 @@ pub fn active_ptr_swap<'ctx, D>(
 where
     D: Dependency,
 {
-    let pointer_1 = context.get_active_pointer(index_1)?;
-    let pointer_2 = context.get_active_pointer(index_2)?;
-
-    context.set_active_pointer(index_1, pointer_2)?;
-    context.set_active_pointer(index_2, pointer_1)?;
-
+    ActivePointer::new(index_1).swap(context, ActivePointer::new(index_2))?;
     Ok(context.field_const(1).as_basic_value_enum())
 }
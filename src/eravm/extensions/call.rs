@@ -71,14 +71,7 @@ where
     )?;
     context.build_store(status_code_result_pointer, result_status_code)?;
 
-    context.write_abi_pointer(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-    )?;
-    context.write_abi_data_size(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_SIZE,
-    )?;
+    context.update_return_data(result_abi_data_pointer)?;
 
     let status_code_result =
         context.build_load(status_code_result_pointer, "mimic_call_status_code")?;
@@ -139,6 +132,8 @@ where
     )?;
     context.build_store(status_code_result_pointer, result_status_code)?;
 
+    let return_data_size = context.update_return_data(result_abi_data_pointer)?;
+
     let source = result_abi_data_pointer;
 
     let destination = Pointer::<AddressSpace>::new_with_offset(
@@ -154,18 +149,10 @@ where
         destination,
         source,
         output_length,
+        return_data_size,
         "system_far_call_memcpy_from_child",
     )?;
 
-    context.write_abi_pointer(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-    )?;
-    context.write_abi_data_size(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_SIZE,
-    )?;
-
     let status_code_result =
         context.build_load(status_code_result_pointer, "system_call_status_code")?;
     Ok(status_code_result)
@@ -233,6 +220,8 @@ where
     )?;
     context.build_store(status_code_result_pointer, result_status_code)?;
 
+    let return_data_size = context.update_return_data(result_abi_data_pointer)?;
+
     let source = result_abi_data_pointer;
 
     let destination = Pointer::<AddressSpace>::new_with_offset(
@@ -248,18 +237,10 @@ where
         destination,
         source,
         output_length,
+        return_data_size,
         "system_far_call_memcpy_from_child",
     )?;
 
-    context.write_abi_pointer(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_POINTER,
-    )?;
-    context.write_abi_data_size(
-        result_abi_data_pointer,
-        crate::eravm::GLOBAL_RETURN_DATA_SIZE,
-    )?;
-
     let status_code_result =
         context.build_load(status_code_result_pointer, "system_call_status_code")?;
     Ok(status_code_result)
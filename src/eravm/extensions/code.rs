@@ -0,0 +1,145 @@
+//!
+//! Translates the external code access via the code oracle.
+//!
+
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+///
+/// Requests the code with `versioned_hash` from the code oracle system contract.
+///
+/// The code is decommitted by the oracle and becomes the return data. Returns the call success
+/// flag, which is zero if the code is unknown.
+///
+/// The calldata is passed via the heap scratch space, which is restored after the call.
+///
+pub fn decommit_by_hash<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    versioned_hash: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let scratch_pointer = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.field_type(),
+        context.field_const(0),
+        "code_oracle_scratch_pointer",
+    )?;
+    let scratch_value = context.build_load(scratch_pointer, "code_oracle_scratch_value")?;
+    context.build_store(scratch_pointer, versioned_hash)?;
+
+    let gas = crate::eravm::evm::ether_gas::gas(context)?.into_int_value();
+    let is_success = crate::eravm::evm::call::default(
+        context,
+        context.llvm_runtime().static_call,
+        gas,
+        context.field_const(crate::eravm::ADDRESS_CODE_ORACLE.into()),
+        None,
+        context.field_const(0),
+        context.field_const(era_compiler_common::BYTE_LENGTH_FIELD as u64),
+        context.field_const(0),
+        context.field_const(0),
+        vec![],
+    )?
+    .into_int_value();
+
+    context.build_store(scratch_pointer, scratch_value)?;
+    Ok(is_success)
+}
+
+///
+/// Copies the code of the contract at `address` to the heap with the `extcodecopy` semantics.
+///
+/// The bytes beyond the code, as well as the whole range if the account has no code, are
+/// zero-filled.
+///
+pub fn copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    address: inkwell::values::IntValue<'ctx>,
+    destination_offset: inkwell::values::IntValue<'ctx>,
+    source_offset: inkwell::values::IntValue<'ctx>,
+    size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let destination = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        destination_offset,
+        "extcodecopy_destination_pointer",
+    )?;
+    context
+        .builder()
+        .build_memset(destination.value, 1, context.byte_type().const_zero(), size)
+        .map_err(|error| anyhow::anyhow!("extcodecopy zero-filling: {error}"))?;
+
+    let decommit_block = context.append_basic_block("extcodecopy_decommit_block");
+    let copy_block = context.append_basic_block("extcodecopy_copy_block");
+    let join_block = context.append_basic_block("extcodecopy_join_block");
+
+    let versioned_hash = crate::eravm::evm::ext_code::hash(context, address)?.into_int_value();
+    let is_code_present = context.builder().build_int_compare(
+        inkwell::IntPredicate::NE,
+        versioned_hash,
+        context.field_const(0),
+        "extcodecopy_is_code_present",
+    )?;
+    context.build_conditional_branch(is_code_present, decommit_block, join_block)?;
+
+    context.set_basic_block(decommit_block);
+    let is_success = decommit_by_hash(context, versioned_hash)?;
+    let is_success = context.builder().build_int_compare(
+        inkwell::IntPredicate::NE,
+        is_success,
+        context.field_const(0),
+        "extcodecopy_is_success",
+    )?;
+    context.build_conditional_branch(is_success, copy_block, join_block)?;
+
+    context.set_basic_block(copy_block);
+    let code_size = crate::eravm::evm::return_data::size(context)?.into_int_value();
+    let source_offset = context.builder().build_select(
+        context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            source_offset,
+            code_size,
+            "extcodecopy_is_source_offset_in_bounds",
+        )?,
+        source_offset,
+        code_size,
+        "extcodecopy_source_offset",
+    )?;
+    let available_size = context.builder().build_int_sub(
+        code_size,
+        source_offset.into_int_value(),
+        "extcodecopy_available_size",
+    )?;
+    let copy_size = context.builder().build_select(
+        context.builder().build_int_compare(
+            inkwell::IntPredicate::ULT,
+            size,
+            available_size,
+            "extcodecopy_is_size_available",
+        )?,
+        size,
+        available_size,
+        "extcodecopy_copy_size",
+    )?;
+    crate::eravm::evm::return_data::copy(
+        context,
+        destination_offset,
+        source_offset.into_int_value(),
+        copy_size.into_int_value(),
+    )?;
+    context.build_unconditional_branch(join_block)?;
+
+    context.set_basic_block(join_block);
+    Ok(())
+}
@@ -65,13 +65,11 @@ where
         .expect("Always exists")
         .const_array_take(index)?;
     let array_type = context.field_type().array_type(const_array.len() as u32);
-    let array_value = context.field_type().const_array(
-        const_array
-            .into_iter()
-            .map(|value| context.field_const_str_dec(value.to_string().as_str()))
-            .collect::<Vec<inkwell::values::IntValue<'ctx>>>()
-            .as_slice(),
-    );
+    let array_values = const_array
+        .into_iter()
+        .map(|value| context.field_const_big(&value))
+        .collect::<anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>>()?;
+    let array_value = context.field_type().const_array(array_values.as_slice());
 
     context.set_global(
         format!(
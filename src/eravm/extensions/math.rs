@@ -1,6 +1,9 @@
 //!
 //! Translates the math instructions of the EraVM Yul extension.
 //!
+//! The 512-bit building blocks are also available to front-ends directly, e.g. for precompile
+//! implementations. The 512-bit values are passed as pairs of the higher and lower 256-bit halves.
+//!
 
 use inkwell::values::BasicValue;
 
@@ -19,35 +22,244 @@ pub fn multiplication_512<'ctx, D>(
 where
     D: Dependency,
 {
-    let operand_1_extended = context.builder().build_int_z_extend_or_bit_cast(
-        operand_1,
-        context.integer_type(era_compiler_common::BIT_LENGTH_FIELD * 2),
-        "multiplication_512_operand_1_extended",
-    )?;
-    let operand_2_extended = context.builder().build_int_z_extend_or_bit_cast(
-        operand_2,
-        context.integer_type(era_compiler_common::BIT_LENGTH_FIELD * 2),
-        "multiplication_512_operand_2_extended",
-    )?;
+    let (high, _low) = mul_512(context, operand_1, operand_2)?;
+    Ok(high.as_basic_value_enum())
+}
+
+///
+/// Performs a full 256-bit multiplication, returning the higher and lower halves of the product.
+///
+pub fn mul_512<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operand_1: inkwell::values::IntValue<'ctx>,
+    operand_2: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<(
+    inkwell::values::IntValue<'ctx>,
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    let operand_1_extended = extend(context, operand_1, "mul_512_operand_1_extended")?;
+    let operand_2_extended = extend(context, operand_2, "mul_512_operand_2_extended")?;
     let result_extended = context.builder().build_int_mul(
         operand_1_extended,
         operand_2_extended,
-        "multiplication_512_result_extended",
+        "mul_512_result_extended",
+    )?;
+    split(context, result_extended, "mul_512_result")
+}
+
+///
+/// Divides the 512-bit `dividend` by the 256-bit `divisor`, returning the 512-bit quotient and the
+/// 256-bit remainder.
+///
+/// As in EVM, the division by zero results in zero quotient and remainder.
+///
+pub fn div_512_by_256<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    dividend: (
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    ),
+    divisor: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<(
+    (
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    ),
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    let dividend_extended = join(context, dividend, "div_512_by_256_dividend")?;
+    let (divisor_extended, is_divisor_zero) =
+        extend_non_zero(context, divisor, "div_512_by_256_divisor")?;
+
+    let quotient = context.builder().build_int_unsigned_div(
+        dividend_extended,
+        divisor_extended,
+        "div_512_by_256_quotient_extended",
+    )?;
+    let remainder = context.builder().build_int_unsigned_rem(
+        dividend_extended,
+        divisor_extended,
+        "div_512_by_256_remainder_extended",
+    )?;
+    let quotient = select_zero(
+        context,
+        is_divisor_zero,
+        quotient,
+        "div_512_by_256_quotient",
+    )?;
+    let remainder = select_zero(
+        context,
+        is_divisor_zero,
+        remainder,
+        "div_512_by_256_remainder",
+    )?;
+
+    let quotient = split(context, quotient, "div_512_by_256_quotient")?;
+    let (_remainder_high, remainder) = split(context, remainder, "div_512_by_256_remainder")?;
+    Ok((quotient, remainder))
+}
+
+///
+/// Computes `(operand_1 * operand_2) % modulus` without the intermediate product overflow.
+///
+/// As in EVM, the zero modulus results in zero.
+///
+pub fn mulmod_512<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    operand_1: inkwell::values::IntValue<'ctx>,
+    operand_2: inkwell::values::IntValue<'ctx>,
+    modulus: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let product = mul_512(context, operand_1, operand_2)?;
+    let (_quotient, remainder) = div_512_by_256(context, product, modulus)?;
+    Ok(remainder)
+}
+
+///
+/// Zero-extends the 256-bit `value` to 512 bits.
+///
+fn extend<'ctx, D>(
+    context: &Context<'ctx, D>,
+    value: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    Ok(context.builder().build_int_z_extend_or_bit_cast(
+        value,
+        context.integer_type(era_compiler_common::BIT_LENGTH_FIELD * 2),
+        name,
+    )?)
+}
+
+///
+/// Zero-extends the 256-bit `value` to 512 bits, replacing zero with one to avoid the undefined
+/// behavior of the division by zero. Returns the extended value and whether it was zero.
+///
+fn extend_non_zero<'ctx, D>(
+    context: &Context<'ctx, D>,
+    value: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<(
+    inkwell::values::IntValue<'ctx>,
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    let is_zero = context.builder().build_int_compare(
+        inkwell::IntPredicate::EQ,
+        value,
+        context.field_const(0),
+        format!("{name}_is_zero").as_str(),
     )?;
-    let result_shifted = context.builder().build_right_shift(
-        result_extended,
+    let value_non_zero = context
+        .builder()
+        .build_select(
+            is_zero,
+            context.field_const(1),
+            value,
+            format!("{name}_non_zero").as_str(),
+        )?
+        .into_int_value();
+    let value_extended = extend(context, value_non_zero, format!("{name}_extended").as_str())?;
+    Ok((value_extended, is_zero))
+}
+
+///
+/// Returns zero if `condition` is set, and the 512-bit `value` otherwise.
+///
+fn select_zero<'ctx, D>(
+    context: &Context<'ctx, D>,
+    condition: inkwell::values::IntValue<'ctx>,
+    value: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    Ok(context
+        .builder()
+        .build_select(
+            condition,
+            context.integer_const(era_compiler_common::BIT_LENGTH_FIELD * 2, 0),
+            value,
+            name,
+        )?
+        .into_int_value())
+}
+
+///
+/// Joins the higher and lower 256-bit halves into a 512-bit value.
+///
+fn join<'ctx, D>(
+    context: &Context<'ctx, D>,
+    (high, low): (
+        inkwell::values::IntValue<'ctx>,
+        inkwell::values::IntValue<'ctx>,
+    ),
+    name: &str,
+) -> anyhow::Result<inkwell::values::IntValue<'ctx>>
+where
+    D: Dependency,
+{
+    let high_extended = extend(context, high, format!("{name}_high_extended").as_str())?;
+    let low_extended = extend(context, low, format!("{name}_low_extended").as_str())?;
+    let high_shifted = context.builder().build_left_shift(
+        high_extended,
+        context.integer_const(
+            era_compiler_common::BIT_LENGTH_FIELD * 2,
+            era_compiler_common::BIT_LENGTH_FIELD as u64,
+        ),
+        format!("{name}_high_shifted").as_str(),
+    )?;
+    Ok(context
+        .builder()
+        .build_or(high_shifted, low_extended, name)?)
+}
+
+///
+/// Splits the 512-bit `value` into the higher and lower 256-bit halves.
+///
+fn split<'ctx, D>(
+    context: &Context<'ctx, D>,
+    value: inkwell::values::IntValue<'ctx>,
+    name: &str,
+) -> anyhow::Result<(
+    inkwell::values::IntValue<'ctx>,
+    inkwell::values::IntValue<'ctx>,
+)>
+where
+    D: Dependency,
+{
+    let high_shifted = context.builder().build_right_shift(
+        value,
         context.integer_const(
             era_compiler_common::BIT_LENGTH_FIELD * 2,
             era_compiler_common::BIT_LENGTH_FIELD as u64,
         ),
         false,
-        "multiplication_512_result_shifted",
+        format!("{name}_high_shifted").as_str(),
     )?;
-    let result = context.builder().build_int_truncate_or_bit_cast(
-        result_shifted,
+    let high = context.builder().build_int_truncate_or_bit_cast(
+        high_shifted,
         context.field_type(),
-        "multiplication_512_result",
+        format!("{name}_high").as_str(),
     )?;
-
-    Ok(result.as_basic_value_enum())
+    let low = context.builder().build_int_truncate_or_bit_cast(
+        value,
+        context.field_type(),
+        format!("{name}_low").as_str(),
+    )?;
+    Ok((high, low))
 }
@@ -2,8 +2,14 @@
 //! The EraVM instructions translation utils.
 //!
 
+pub mod aa;
 pub mod abi;
 pub mod call;
+pub mod code;
 pub mod const_array;
 pub mod general;
 pub mod math;
+pub mod simulation;
+
+#[cfg(test)]
+mod tests;
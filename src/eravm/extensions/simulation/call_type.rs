@@ -0,0 +1,29 @@
+//!
+//! The simulation call type.
+//!
+
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::eravm::context::function::llvm_runtime::LLVMRuntime;
+
+///
+/// The call type a simulation must be written with.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallType {
+    /// The `call` instruction.
+    Far,
+    /// The `staticcall` instruction.
+    Static,
+}
+
+impl CallType {
+    ///
+    /// Returns the runtime function the call type is translated to.
+    ///
+    pub fn declaration<'ctx>(self, llvm_runtime: &LLVMRuntime<'ctx>) -> FunctionDeclaration<'ctx> {
+        match self {
+            Self::Far => llvm_runtime.far_call,
+            Self::Static => llvm_runtime.static_call,
+        }
+    }
+}
@@ -0,0 +1,700 @@
+//!
+//! The simulation handlers.
+//!
+//! Each handler maps the operands of the simulated call to its extension instruction. The call
+//! type is validated by the caller before the handler is invoked.
+//!
+
+use inkwell::values::BasicValue;
+
+use crate::context::const_operand::ConstOperand;
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::eravm::context::active_pointer::ActivePointer;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::simulation_registry::arguments::Arguments;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+///
+/// Translates the `to_l1` simulation.
+///
+pub fn to_l1<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let is_first = arguments.gas;
+    let in_0 = arguments.value_operand("to_l1")?;
+    let in_1 = arguments.input_offset;
+
+    crate::eravm::extensions::general::to_l1(context, is_first, in_0, in_1)
+}
+
+///
+/// Translates the `precompile` simulation.
+///
+pub fn precompile<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let in_0 = arguments.gas;
+    let gas_left = arguments.input_offset;
+
+    crate::eravm::extensions::general::precompile(context, in_0, gas_left)
+}
+
+///
+/// Translates the `decommit` simulation.
+///
+pub fn decommit<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let in_0 = arguments.gas;
+    let gas_left = arguments.input_offset;
+
+    crate::eravm::extensions::general::decommit(context, in_0, gas_left)
+}
+
+///
+/// Translates the `set_context_value` simulation.
+///
+pub fn set_context_value<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let value = arguments.value_operand("set_context_value")?;
+
+    crate::eravm::extensions::general::set_context_value(context, value)
+}
+
+///
+/// Translates the `set_pubdata_price` simulation.
+///
+pub fn set_pubdata_price<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let price = arguments.gas;
+
+    crate::eravm::extensions::general::set_pubdata_price(context, price)
+}
+
+///
+/// Translates the `increment_tx_counter` simulation.
+///
+pub fn increment_tx_counter<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::general::increment_tx_counter(context)
+}
+
+///
+/// Translates the `code_address` simulation.
+///
+pub fn code_address<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::general::code_source(context)
+}
+
+///
+/// Translates the `meta` simulation.
+///
+pub fn meta<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::general::meta(context)
+}
+
+///
+/// Translates the `get_global_ptr_calldata` simulation.
+///
+pub fn get_global_ptr_calldata<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let pointer = Pointer::new(
+        context.byte_type(),
+        AddressSpace::Generic,
+        context
+            .get_global_value(crate::eravm::GLOBAL_CALLDATA_POINTER)?
+            .into_pointer_value(),
+    );
+    let value = pointer.to_int(context, "calldata_abi_integer")?;
+    Ok(value.as_basic_value_enum())
+}
+
+///
+/// Translates the `get_global_call_flags` simulation.
+///
+pub fn get_global_call_flags<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    context.get_global_value(crate::eravm::GLOBAL_CALL_FLAGS)
+}
+
+///
+/// Translates the `get_global_ptr_return_data` simulation.
+///
+pub fn get_global_ptr_return_data<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let pointer = Pointer::new(
+        context.byte_type(),
+        AddressSpace::Generic,
+        context
+            .get_global_value(crate::eravm::GLOBAL_RETURN_DATA_POINTER)?
+            .into_pointer_value(),
+    );
+    let value = pointer.to_int(context, "return_data_abi_integer")?;
+    Ok(value.as_basic_value_enum())
+}
+
+///
+/// Translates the `get_global_extra_abi_data` simulation.
+///
+pub fn get_global_extra_abi_data<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let index = arguments.gas;
+
+    crate::eravm::extensions::abi::get_extra_abi_data(context, index)
+}
+
+///
+/// Translates the `multiplication_high_register` simulation.
+///
+pub fn multiplication_high_register<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let operand_1 = arguments.gas;
+    let operand_2 = arguments.input_offset;
+
+    crate::eravm::extensions::math::multiplication_512(context, operand_1, operand_2)
+}
+
+///
+/// Translates the `event_initialize` simulation.
+///
+pub fn event_initialize<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let operand_1 = arguments.gas;
+    let operand_2 = arguments.value_operand("event_initialize")?;
+
+    crate::eravm::extensions::general::event(context, operand_1, operand_2, true)
+}
+
+///
+/// Translates the `event_write` simulation.
+///
+pub fn event_write<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let operand_1 = arguments.gas;
+    let operand_2 = arguments.value_operand("event_write")?;
+
+    crate::eravm::extensions::general::event(context, operand_1, operand_2, false)
+}
+
+///
+/// Translates the `mimic_call` simulation.
+///
+pub fn mimic_call<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = arguments.input_offset;
+    let mimic = arguments.input_length;
+
+    crate::eravm::extensions::call::mimic(
+        context,
+        context.llvm_runtime().mimic_call,
+        address,
+        mimic,
+        abi_data.as_basic_value_enum(),
+        vec![],
+    )
+}
+
+///
+/// Translates the `mimic_call_byref` simulation.
+///
+pub fn mimic_call_byref<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let mimic = arguments.input_length;
+    let abi_data = ActivePointer::primary(context).get(context)?.value;
+
+    crate::eravm::extensions::call::mimic(
+        context,
+        context.llvm_runtime().mimic_call_byref,
+        address,
+        mimic,
+        abi_data.as_basic_value_enum(),
+        vec![],
+    )
+}
+
+///
+/// Translates the `system_mimic_call` simulation.
+///
+pub fn system_mimic_call<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = arguments.input_offset;
+    let mimic = arguments.input_length;
+    let extra_value_1 = arguments.output_offset;
+    let extra_value_2 = arguments.output_length;
+
+    crate::eravm::extensions::call::mimic(
+        context,
+        context.llvm_runtime().mimic_call,
+        address,
+        mimic,
+        abi_data.as_basic_value_enum(),
+        vec![extra_value_1, extra_value_2],
+    )
+}
+
+///
+/// Translates the `system_mimic_call_byref` simulation.
+///
+pub fn system_mimic_call_byref<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let mimic = arguments.input_length;
+    let abi_data = ActivePointer::primary(context).get(context)?.value;
+    let extra_value_1 = arguments.output_offset;
+    let extra_value_2 = arguments.output_length;
+
+    crate::eravm::extensions::call::mimic(
+        context,
+        context.llvm_runtime().mimic_call_byref,
+        address,
+        mimic,
+        abi_data.as_basic_value_enum(),
+        vec![extra_value_1, extra_value_2],
+    )
+}
+
+///
+/// Translates the `raw_far_call` simulation.
+///
+pub fn raw_far_call<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = arguments.input_length;
+
+    crate::eravm::extensions::call::raw_far(
+        context,
+        context.llvm_runtime().modify(arguments.function, false),
+        address,
+        abi_data.as_basic_value_enum(),
+        arguments.output_offset,
+        arguments.output_length,
+    )
+}
+
+///
+/// Translates the `raw_far_call_byref` simulation.
+///
+pub fn raw_far_call_byref<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = ActivePointer::primary(context).get(context)?.value;
+
+    crate::eravm::extensions::call::raw_far(
+        context,
+        context.llvm_runtime().modify(arguments.function, true),
+        address,
+        abi_data.as_basic_value_enum(),
+        arguments.output_offset,
+        arguments.output_length,
+    )
+}
+
+///
+/// Translates the `system_call` simulation.
+///
+pub fn system_call<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = arguments.input_length;
+    let extra_value_1 = arguments.value_operand("system_call")?;
+    let extra_value_2 = arguments.input_offset;
+    let extra_value_3 = arguments.output_offset;
+    let extra_value_4 = arguments.output_length;
+
+    crate::eravm::extensions::call::system(
+        context,
+        context.llvm_runtime().modify(arguments.function, false),
+        address,
+        abi_data.as_basic_value_enum(),
+        context.field_const(0),
+        context.field_const(0),
+        vec![extra_value_1, extra_value_2, extra_value_3, extra_value_4],
+    )
+}
+
+///
+/// Translates the `system_call_byref` simulation.
+///
+pub fn system_call_byref<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let address = arguments.gas;
+    let abi_data = ActivePointer::primary(context).get(context)?.value;
+    let extra_value_1 = arguments.value_operand("system_call_byref")?;
+    let extra_value_2 = arguments.input_offset;
+    let extra_value_3 = arguments.output_offset;
+    let extra_value_4 = arguments.output_length;
+
+    crate::eravm::extensions::call::system(
+        context,
+        context.llvm_runtime().modify(arguments.function, true),
+        address,
+        abi_data.as_basic_value_enum(),
+        context.field_const(0),
+        context.field_const(0),
+        vec![extra_value_1, extra_value_2, extra_value_3, extra_value_4],
+    )
+}
+
+///
+/// Translates the `active_ptr_load_calldata` simulation.
+///
+pub fn active_ptr_load_calldata<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::calldata_ptr_to_active(context)
+}
+
+///
+/// Translates the `active_ptr_load_return_data` simulation.
+///
+pub fn active_ptr_load_return_data<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::return_data_ptr_to_active(context)
+}
+
+///
+/// Translates the `active_ptr_load_decommit` simulation.
+///
+pub fn active_ptr_load_decommit<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::decommit_ptr_to_active(context)
+}
+
+///
+/// Translates the `active_ptr_add` simulation.
+///
+pub fn active_ptr_add<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let offset = arguments.gas;
+
+    crate::eravm::extensions::abi::active_ptr_add_assign(context, offset)
+}
+
+///
+/// Translates the `active_ptr_shrink` simulation.
+///
+pub fn active_ptr_shrink<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let offset = arguments.gas;
+
+    crate::eravm::extensions::abi::active_ptr_shrink_assign(context, offset)
+}
+
+///
+/// Translates the `active_ptr_pack` simulation.
+///
+pub fn active_ptr_pack<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let data = arguments.gas;
+
+    crate::eravm::extensions::abi::active_ptr_pack_assign(context, data)
+}
+
+///
+/// Translates the `active_ptr_data_load` simulation.
+///
+pub fn active_ptr_data_load<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let offset = arguments.gas;
+
+    crate::eravm::extensions::abi::active_ptr_data_load(context, offset)
+}
+
+///
+/// Translates the `active_ptr_data_copy` simulation.
+///
+pub fn active_ptr_data_copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let destination_offset = arguments.gas;
+    let source_offset = arguments.input_offset;
+    let size = arguments.input_length;
+
+    crate::eravm::extensions::abi::active_ptr_data_copy(
+        context,
+        destination_offset,
+        source_offset,
+        size,
+    )
+}
+
+///
+/// Translates the `active_ptr_data_size` simulation.
+///
+pub fn active_ptr_data_size<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::active_ptr_data_size(context)
+}
+
+///
+/// Translates the `active_ptr_swap` simulation.
+///
+pub fn active_ptr_swap<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let index_1 = arguments.gas;
+    let index_2 = arguments.input_offset;
+
+    crate::eravm::extensions::abi::active_ptr_swap(context, index_1, index_2)
+}
+
+///
+/// Translates the `return_forward` simulation.
+///
+pub fn return_forward<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::active_ptr_return_forward(context)
+}
+
+///
+/// Translates the `revert_forward` simulation.
+///
+pub fn revert_forward<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    _arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    crate::eravm::extensions::abi::active_ptr_revert_forward(context)
+}
+
+///
+/// Translates the `const_array_declare` simulation.
+///
+pub fn const_array_declare<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let mut constants = arguments.constants;
+    let index = ConstOperand::take("Const array index", constants.as_mut_slice(), 0).to_u8()?;
+    let size = ConstOperand::take("Const array size", constants.as_mut_slice(), 2).to_u16()?;
+
+    crate::eravm::extensions::const_array::declare(context, index, size)
+}
+
+///
+/// Translates the `const_array_set` simulation.
+///
+pub fn const_array_set<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let mut constants = arguments.constants;
+    let index = ConstOperand::take("Const array index", constants.as_mut_slice(), 0).to_u8()?;
+    let offset = ConstOperand::take("Const array offset", constants.as_mut_slice(), 2).to_u16()?;
+    let value =
+        ConstOperand::take("Const array assigned value", constants.as_mut_slice(), 4).into_big()?;
+
+    crate::eravm::extensions::const_array::set(context, index, offset, value)
+}
+
+///
+/// Translates the `const_array_finalize` simulation.
+///
+pub fn const_array_finalize<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let mut constants = arguments.constants;
+    let index = ConstOperand::take("Const array index", constants.as_mut_slice(), 0).to_u8()?;
+
+    crate::eravm::extensions::const_array::finalize(context, index)
+}
+
+///
+/// Translates the `const_array_get` simulation.
+///
+pub fn const_array_get<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    arguments: Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    let mut constants = arguments.constants;
+    let index = ConstOperand::take("Const array index", constants.as_mut_slice(), 0).to_u8()?;
+    let offset = arguments.input_offset;
+
+    crate::eravm::extensions::const_array::get(context, index, offset)
+}
@@ -0,0 +1,465 @@
+//!
+//! The EraVM call simulations.
+//!
+
+pub mod call_type;
+pub mod handler;
+
+#[cfg(test)]
+mod tests;
+
+use crate::eravm::context::simulation_registry::arguments::Arguments;
+use crate::eravm::context::Context;
+use crate::eravm::Dependency;
+
+use self::call_type::CallType;
+
+///
+/// The simulation handler.
+///
+pub type Handler<'ctx, D> = fn(
+    &mut Context<'ctx, D>,
+    Arguments<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>;
+
+///
+/// The built-in EraVM call simulations.
+///
+/// A call to a simulation address is substituted with the corresponding extension instruction
+/// if the EraVM extensions are enabled.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Simulation {
+    /// The L1 message sending.
+    ToL1,
+    /// The precompile call.
+    Precompile,
+    /// The code decommit.
+    Decommit,
+    /// The context value setter.
+    SetContextValue,
+    /// The public data price setter.
+    SetPubdataPrice,
+    /// The transaction counter incrementor.
+    IncrementTxCounter,
+    /// The code source address getter.
+    CodeAddress,
+    /// The VM state getter.
+    Meta,
+    /// The calldata pointer getter.
+    GetGlobalPtrCalldata,
+    /// The call flags getter.
+    GetGlobalCallFlags,
+    /// The return data pointer getter.
+    GetGlobalPtrReturnData,
+    /// The extra ABI data getter.
+    GetGlobalExtraAbiData,
+    /// The high register of the 512-bit multiplication.
+    MultiplicationHighRegister,
+    /// The event initialization.
+    EventInitialize,
+    /// The event continuation.
+    EventWrite,
+    /// The mimic call.
+    MimicCall,
+    /// The mimic call with the active pointer as ABI data.
+    MimicCallByref,
+    /// The system mimic call.
+    SystemMimicCall,
+    /// The system mimic call with the active pointer as ABI data.
+    SystemMimicCallByref,
+    /// The raw far call.
+    RawFarCall,
+    /// The raw far call with the active pointer as ABI data.
+    RawFarCallByref,
+    /// The system call.
+    SystemCall,
+    /// The system call with the active pointer as ABI data.
+    SystemCallByref,
+    /// The active pointer load from the calldata pointer.
+    ActivePtrLoadCalldata,
+    /// The active pointer load from the return data pointer.
+    ActivePtrLoadReturnData,
+    /// The active pointer load from the decommit pointer.
+    ActivePtrLoadDecommit,
+    /// The active pointer offset increment.
+    ActivePtrAdd,
+    /// The active pointer shrink.
+    ActivePtrShrink,
+    /// The active pointer pack.
+    ActivePtrPack,
+    /// The active pointer data load.
+    ActivePtrDataLoad,
+    /// The active pointer data copy.
+    ActivePtrDataCopy,
+    /// The active pointer data size getter.
+    ActivePtrDataSize,
+    /// The active pointers swap.
+    ActivePtrSwap,
+    /// The return with the active pointer forwarding.
+    ReturnForward,
+    /// The revert with the active pointer forwarding.
+    RevertForward,
+    /// The constant array declaration.
+    ConstArrayDeclare,
+    /// The constant array element setter.
+    ConstArraySet,
+    /// The constant array finalization.
+    ConstArrayFinalize,
+    /// The constant array element getter.
+    ConstArrayGet,
+}
+
+impl Simulation {
+    /// All the simulations in the dispatch order.
+    pub const ALL: [Self; 39] = [
+        Self::ToL1,
+        Self::Precompile,
+        Self::Decommit,
+        Self::SetContextValue,
+        Self::SetPubdataPrice,
+        Self::IncrementTxCounter,
+        Self::CodeAddress,
+        Self::Meta,
+        Self::GetGlobalPtrCalldata,
+        Self::GetGlobalCallFlags,
+        Self::GetGlobalPtrReturnData,
+        Self::GetGlobalExtraAbiData,
+        Self::MultiplicationHighRegister,
+        Self::EventInitialize,
+        Self::EventWrite,
+        Self::MimicCall,
+        Self::MimicCallByref,
+        Self::SystemMimicCall,
+        Self::SystemMimicCallByref,
+        Self::RawFarCall,
+        Self::RawFarCallByref,
+        Self::SystemCall,
+        Self::SystemCallByref,
+        Self::ActivePtrLoadCalldata,
+        Self::ActivePtrLoadReturnData,
+        Self::ActivePtrLoadDecommit,
+        Self::ActivePtrAdd,
+        Self::ActivePtrShrink,
+        Self::ActivePtrPack,
+        Self::ActivePtrDataLoad,
+        Self::ActivePtrDataCopy,
+        Self::ActivePtrDataSize,
+        Self::ActivePtrSwap,
+        Self::ReturnForward,
+        Self::RevertForward,
+        Self::ConstArrayDeclare,
+        Self::ConstArraySet,
+        Self::ConstArrayFinalize,
+        Self::ConstArrayGet,
+    ];
+
+    ///
+    /// Returns the simulation at `address`, if any.
+    ///
+    pub fn from_address(address: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|simulation| simulation.address() == address)
+    }
+
+    ///
+    /// Returns the simulation address.
+    ///
+    pub fn address(self) -> u16 {
+        self.entry().0
+    }
+
+    ///
+    /// Returns the simulation name used in diagnostics.
+    ///
+    pub fn name(self) -> &'static str {
+        self.entry().1
+    }
+
+    ///
+    /// Returns the call type the simulation must be written with.
+    ///
+    /// The mimic and raw far calls accept any call type, since they forward it to the VM.
+    ///
+    pub fn call_type(self) -> Option<CallType> {
+        self.entry().2
+    }
+
+    ///
+    /// Returns the simulation handler.
+    ///
+    pub fn handler<'ctx, D>(self) -> Handler<'ctx, D>
+    where
+        D: Dependency,
+    {
+        match self {
+            Self::ToL1 => handler::to_l1,
+            Self::Precompile => handler::precompile,
+            Self::Decommit => handler::decommit,
+            Self::SetContextValue => handler::set_context_value,
+            Self::SetPubdataPrice => handler::set_pubdata_price,
+            Self::IncrementTxCounter => handler::increment_tx_counter,
+            Self::CodeAddress => handler::code_address,
+            Self::Meta => handler::meta,
+            Self::GetGlobalPtrCalldata => handler::get_global_ptr_calldata,
+            Self::GetGlobalCallFlags => handler::get_global_call_flags,
+            Self::GetGlobalPtrReturnData => handler::get_global_ptr_return_data,
+            Self::GetGlobalExtraAbiData => handler::get_global_extra_abi_data,
+            Self::MultiplicationHighRegister => handler::multiplication_high_register,
+            Self::EventInitialize => handler::event_initialize,
+            Self::EventWrite => handler::event_write,
+            Self::MimicCall => handler::mimic_call,
+            Self::MimicCallByref => handler::mimic_call_byref,
+            Self::SystemMimicCall => handler::system_mimic_call,
+            Self::SystemMimicCallByref => handler::system_mimic_call_byref,
+            Self::RawFarCall => handler::raw_far_call,
+            Self::RawFarCallByref => handler::raw_far_call_byref,
+            Self::SystemCall => handler::system_call,
+            Self::SystemCallByref => handler::system_call_byref,
+            Self::ActivePtrLoadCalldata => handler::active_ptr_load_calldata,
+            Self::ActivePtrLoadReturnData => handler::active_ptr_load_return_data,
+            Self::ActivePtrLoadDecommit => handler::active_ptr_load_decommit,
+            Self::ActivePtrAdd => handler::active_ptr_add,
+            Self::ActivePtrShrink => handler::active_ptr_shrink,
+            Self::ActivePtrPack => handler::active_ptr_pack,
+            Self::ActivePtrDataLoad => handler::active_ptr_data_load,
+            Self::ActivePtrDataCopy => handler::active_ptr_data_copy,
+            Self::ActivePtrDataSize => handler::active_ptr_data_size,
+            Self::ActivePtrSwap => handler::active_ptr_swap,
+            Self::ReturnForward => handler::return_forward,
+            Self::RevertForward => handler::revert_forward,
+            Self::ConstArrayDeclare => handler::const_array_declare,
+            Self::ConstArraySet => handler::const_array_set,
+            Self::ConstArrayFinalize => handler::const_array_finalize,
+            Self::ConstArrayGet => handler::const_array_get,
+        }
+    }
+
+    ///
+    /// Translates the simulated call.
+    ///
+    /// Returns an error if the call type does not match the simulation.
+    ///
+    pub fn translate<'ctx, D>(
+        self,
+        context: &mut Context<'ctx, D>,
+        arguments: Arguments<'ctx>,
+    ) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+    where
+        D: Dependency,
+    {
+        if let Some(call_type) = self.call_type() {
+            crate::eravm::extensions::call::validate_call_type(
+                call_type.declaration(context.llvm_runtime()),
+                arguments.function,
+                self.name(),
+            )?;
+        }
+
+        (self.handler())(context, arguments)
+    }
+
+    ///
+    /// Returns the simulation address, name, and call type.
+    ///
+    fn entry(self) -> (u16, &'static str, Option<CallType>) {
+        match self {
+            Self::ToL1 => (
+                era_compiler_common::ERAVM_ADDRESS_TO_L1,
+                "to_l1",
+                Some(CallType::Far),
+            ),
+            Self::Precompile => (
+                era_compiler_common::ERAVM_ADDRESS_PRECOMPILE,
+                "precompile",
+                Some(CallType::Static),
+            ),
+            Self::Decommit => (
+                era_compiler_common::ERAVM_ADDRESS_DECOMMIT,
+                "decommit",
+                Some(CallType::Static),
+            ),
+            Self::SetContextValue => (
+                era_compiler_common::ERAVM_ADDRESS_SET_CONTEXT_VALUE_CALL,
+                "set_context_value",
+                Some(CallType::Far),
+            ),
+            Self::SetPubdataPrice => (
+                era_compiler_common::ERAVM_ADDRESS_SET_PUBDATA_PRICE,
+                "set_pubdata_price",
+                Some(CallType::Far),
+            ),
+            Self::IncrementTxCounter => (
+                era_compiler_common::ERAVM_ADDRESS_INCREMENT_TX_COUNTER,
+                "increment_tx_counter",
+                Some(CallType::Far),
+            ),
+            Self::CodeAddress => (
+                era_compiler_common::ERAVM_ADDRESS_CODE_ADDRESS,
+                "code_address",
+                Some(CallType::Static),
+            ),
+            Self::Meta => (
+                era_compiler_common::ERAVM_ADDRESS_META,
+                "meta",
+                Some(CallType::Static),
+            ),
+            Self::GetGlobalPtrCalldata => (
+                era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_PTR_CALLDATA,
+                "get_global_ptr_calldata",
+                Some(CallType::Static),
+            ),
+            Self::GetGlobalCallFlags => (
+                era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_CALL_FLAGS,
+                "get_global_call_flags",
+                Some(CallType::Static),
+            ),
+            Self::GetGlobalPtrReturnData => (
+                era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_PTR_RETURN_DATA,
+                "get_global_ptr_return_data",
+                Some(CallType::Static),
+            ),
+            Self::GetGlobalExtraAbiData => (
+                era_compiler_common::ERAVM_ADDRESS_GET_GLOBAL_EXTRA_ABI_DATA,
+                "get_global_extra_abi_data",
+                Some(CallType::Static),
+            ),
+            Self::MultiplicationHighRegister => (
+                era_compiler_common::ERAVM_ADDRESS_MULTIPLICATION_HIGH_REGISTER,
+                "multiplication_high_register",
+                Some(CallType::Static),
+            ),
+            Self::EventInitialize => (
+                era_compiler_common::ERAVM_ADDRESS_EVENT_INITIALIZE,
+                "event_initialize",
+                Some(CallType::Far),
+            ),
+            Self::EventWrite => (
+                era_compiler_common::ERAVM_ADDRESS_EVENT_WRITE,
+                "event_write",
+                Some(CallType::Far),
+            ),
+            Self::MimicCall => (
+                era_compiler_common::ERAVM_ADDRESS_MIMIC_CALL,
+                "mimic_call",
+                None,
+            ),
+            Self::MimicCallByref => (
+                era_compiler_common::ERAVM_ADDRESS_MIMIC_CALL_BYREF,
+                "mimic_call_byref",
+                None,
+            ),
+            Self::SystemMimicCall => (
+                era_compiler_common::ERAVM_ADDRESS_SYSTEM_MIMIC_CALL,
+                "system_mimic_call",
+                None,
+            ),
+            Self::SystemMimicCallByref => (
+                era_compiler_common::ERAVM_ADDRESS_SYSTEM_MIMIC_CALL_BYREF,
+                "system_mimic_call_byref",
+                None,
+            ),
+            Self::RawFarCall => (
+                era_compiler_common::ERAVM_ADDRESS_RAW_FAR_CALL,
+                "raw_far_call",
+                None,
+            ),
+            Self::RawFarCallByref => (
+                era_compiler_common::ERAVM_ADDRESS_RAW_FAR_CALL_BYREF,
+                "raw_far_call_byref",
+                None,
+            ),
+            Self::SystemCall => (
+                era_compiler_common::ERAVM_ADDRESS_SYSTEM_CALL,
+                "system_call",
+                Some(CallType::Far),
+            ),
+            Self::SystemCallByref => (
+                era_compiler_common::ERAVM_ADDRESS_SYSTEM_CALL_BYREF,
+                "system_call_by_ref",
+                Some(CallType::Far),
+            ),
+            Self::ActivePtrLoadCalldata => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_CALLDATA,
+                "active_ptr_load_calldata",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrLoadReturnData => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_RETURN_DATA,
+                "active_ptr_load_return_data",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrLoadDecommit => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_LOAD_DECOMMIT,
+                "active_ptr_load_decommit",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrAdd => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_ADD,
+                "active_ptr_add",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrShrink => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_SHRINK,
+                "active_ptr_shrink",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrPack => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_PACK,
+                "active_ptr_pack",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrDataLoad => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_LOAD,
+                "active_ptr_data_load",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrDataCopy => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_COPY,
+                "active_ptr_data_copy",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrDataSize => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_DATA_SIZE,
+                "active_ptr_data_size",
+                Some(CallType::Static),
+            ),
+            Self::ActivePtrSwap => (
+                era_compiler_common::ERAVM_ADDRESS_ACTIVE_PTR_SWAP,
+                "active_ptr_swap",
+                Some(CallType::Static),
+            ),
+            Self::ReturnForward => (
+                era_compiler_common::ERAVM_ADDRESS_RETURN_FORWARD,
+                "return_forward",
+                Some(CallType::Static),
+            ),
+            Self::RevertForward => (
+                era_compiler_common::ERAVM_ADDRESS_REVERT_FORWARD,
+                "revert_forward",
+                Some(CallType::Static),
+            ),
+            Self::ConstArrayDeclare => (
+                era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_DECLARE,
+                "const_array_declare",
+                Some(CallType::Static),
+            ),
+            Self::ConstArraySet => (
+                era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_SET,
+                "const_array_set",
+                Some(CallType::Static),
+            ),
+            Self::ConstArrayFinalize => (
+                era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_FINALIZE,
+                "const_array_finalize",
+                Some(CallType::Static),
+            ),
+            Self::ConstArrayGet => (
+                era_compiler_common::ERAVM_ADDRESS_CONST_ARRAY_GET,
+                "const_array_get",
+                Some(CallType::Static),
+            ),
+        }
+    }
+}
@@ -0,0 +1,461 @@
+//!
+//! The EraVM call simulation tests.
+//!
+
+use crate::context::IContext;
+use crate::dependency::DummyDependency;
+use crate::eravm::context::simulation_registry::arguments::Arguments;
+use crate::eravm::context::Context;
+use crate::testing::file_check::FileCheck;
+
+use super::call_type::CallType;
+use super::Simulation;
+
+///
+/// Adds the function `name` whose seven parameters are passed as the simulation operands.
+///
+/// The operands are `gas`, `address`, `value`, `input_offset`, `input_length`, `output_offset`,
+/// and `output_length`, so they are printed as `%0` to `%6` in the order of the call operands.
+///
+fn add_function<'ctx>(
+    context: &mut Context<'ctx, DummyDependency>,
+    name: &str,
+) -> inkwell::values::FunctionValue<'ctx> {
+    crate::testing::add_function(context, name, 7, 0).expect("Failed to add function")
+}
+
+///
+/// Translates `simulation` with the parameters of `function` as the operands.
+///
+/// The call type is the expected one, or the far call for the simulations accepting any.
+///
+fn translate<'ctx>(
+    context: &mut Context<'ctx, DummyDependency>,
+    function: inkwell::values::FunctionValue<'ctx>,
+    simulation: Simulation,
+    constants: Vec<Option<num::BigUint>>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>> {
+    let operand = |index| {
+        function
+            .get_nth_param(index)
+            .expect("Always exists")
+            .into_int_value()
+    };
+    let call_type = simulation.call_type().unwrap_or(CallType::Far);
+    let arguments = Arguments {
+        function: call_type.declaration(context.llvm_runtime()),
+        gas: operand(0),
+        address: operand(1),
+        value: Some(operand(2)),
+        input_offset: operand(3),
+        input_length: operand(4),
+        output_offset: operand(5),
+        output_length: operand(6),
+        constants,
+    };
+    simulation.translate(context, arguments)
+}
+
+///
+/// Translates `simulations` into the function `name` and matches its IR against `checks`.
+///
+fn check_simulations(simulations: &[Simulation], name: &str, checks: &str) {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::eravm::extensions::tests::create_context(&llvm);
+    let function = add_function(&mut context, name);
+    for simulation in simulations.iter() {
+        translate(&mut context, function, *simulation, vec![])
+            .unwrap_or_else(|error| panic!("{simulation:?}: {error}"));
+    }
+    let file_check = FileCheck::parse(checks, FileCheck::DEFAULT_PREFIX).expect("Always valid");
+    crate::testing::check_function(&context, name, &file_check).expect("Must match");
+}
+
+///
+/// Returns the compile-time known operands, with `None` for the runtime ones.
+///
+fn constants(values: &[Option<u64>]) -> Vec<Option<num::BigUint>> {
+    values
+        .iter()
+        .map(|value| value.map(num::BigUint::from))
+        .collect()
+}
+
+#[test]
+pub fn check_simulation_table() {
+    let mut addresses = std::collections::BTreeSet::new();
+    let mut names = std::collections::BTreeSet::new();
+    for simulation in Simulation::ALL.into_iter() {
+        assert!(addresses.insert(simulation.address()), "{simulation:?}");
+        assert!(names.insert(simulation.name()), "{simulation:?}");
+        assert_eq!(
+            Simulation::from_address(simulation.address()),
+            Some(simulation)
+        );
+    }
+    assert_eq!(Simulation::from_address(0x1234), None);
+
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::eravm::extensions::tests::create_context(&llvm);
+    crate::testing::add_function(&mut context, "simulation_table", 0, 0)
+        .expect("Failed to add function");
+    let zero = context.field_const(0);
+    let arguments = move |function| Arguments {
+        function,
+        gas: zero,
+        address: zero,
+        value: Some(zero),
+        input_offset: zero,
+        input_length: zero,
+        output_offset: zero,
+        output_length: zero,
+        constants: vec![],
+    };
+
+    for simulation in Simulation::ALL.into_iter() {
+        let wrong_call_type = match simulation.call_type() {
+            Some(CallType::Far) => context.llvm_runtime().static_call,
+            Some(CallType::Static) => context.llvm_runtime().far_call,
+            None => continue,
+        };
+        let error = simulation
+            .translate(&mut context, arguments(wrong_call_type))
+            .expect_err("Must be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains(format!("`{}`", simulation.name()).as_str()),
+            "{simulation:?}: {error}"
+        );
+    }
+
+    let static_call = context.llvm_runtime().static_call;
+    Simulation::GetGlobalCallFlags
+        .translate(&mut context, arguments(static_call))
+        .expect("Failed to translate");
+    Simulation::MultiplicationHighRegister
+        .translate(&mut context, arguments(static_call))
+        .expect("Failed to translate");
+    let file_check = FileCheck::new()
+        .check("load i256, ptr @call_flags")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "simulation_table", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_value_operand() {
+    check_simulations(
+        &[
+            Simulation::ToL1,
+            Simulation::SetContextValue,
+            Simulation::EventInitialize,
+            Simulation::EventWrite,
+        ],
+        "value_operand",
+        r#"
+; CHECK: icmp eq i256 %0, 0
+; CHECK: contract_call_toL1_join_block:
+; CHECK-NEXT: call void @llvm.eravm.setu128(i256 %2)
+; CHECK-NEXT: call void @llvm.eravm.event(i256 %0, i256 %2, i256 1)
+; CHECK-NEXT: call void @llvm.eravm.event(i256 %0, i256 %2, i256 0)
+; CHECK: contract_call_toL1_is_first_block:
+; CHECK-NEXT: call void @llvm.eravm.tol1(i256 %2, i256 %3, i256 1)
+; CHECK: contract_call_toL1_is_not_first_block:
+; CHECK-NEXT: call void @llvm.eravm.tol1(i256 %2, i256 %3, i256 0)
+"#,
+    );
+}
+
+#[test]
+pub fn check_missing_value_operand() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::eravm::extensions::tests::create_context(&llvm);
+    let function = add_function(&mut context, "missing_value_operand");
+    for simulation in [
+        Simulation::ToL1,
+        Simulation::SetContextValue,
+        Simulation::EventInitialize,
+        Simulation::EventWrite,
+        Simulation::SystemCall,
+        Simulation::SystemCallByref,
+    ] {
+        let operand = function
+            .get_first_param()
+            .expect("Always exists")
+            .into_int_value();
+        let call_type = simulation.call_type().unwrap_or(CallType::Far);
+        let arguments = Arguments {
+            function: call_type.declaration(context.llvm_runtime()),
+            gas: operand,
+            address: operand,
+            value: None,
+            input_offset: operand,
+            input_length: operand,
+            output_offset: operand,
+            output_length: operand,
+            constants: vec![],
+        };
+        let error = simulation
+            .translate(&mut context, arguments)
+            .expect_err("Must be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains(format!("`{}` requires the value operand", simulation.name()).as_str()),
+            "{simulation:?}: {error}"
+        );
+    }
+}
+
+#[test]
+pub fn check_gas_operand() {
+    check_simulations(
+        &[
+            Simulation::Precompile,
+            Simulation::Decommit,
+            Simulation::SetPubdataPrice,
+        ],
+        "gas_operand",
+        r#"
+; CHECK: call i256 @llvm.eravm.precompile(i256 %0, i256 %3)
+; CHECK: %contract_call_simulation_decommit = call ptr addrspace(3) @llvm.eravm.decommit(i256 %0, i256 %3)
+; CHECK-NEXT: store ptr addrspace(3) %contract_call_simulation_decommit, ptr @ptr_decommit
+; CHECK: call void @llvm.eravm.setpubdataprice(i256 %0)
+"#,
+    );
+}
+
+#[test]
+pub fn check_no_operands() {
+    check_simulations(
+        &[
+            Simulation::IncrementTxCounter,
+            Simulation::CodeAddress,
+            Simulation::Meta,
+        ],
+        "no_operands",
+        r#"
+; CHECK: call void @llvm.eravm.inctx()
+; CHECK: call i256 @llvm.eravm.codesource()
+; CHECK: call i256 @llvm.eravm.meta()
+"#,
+    );
+}
+
+#[test]
+pub fn check_global_getters() {
+    check_simulations(
+        &[
+            Simulation::GetGlobalPtrCalldata,
+            Simulation::GetGlobalCallFlags,
+            Simulation::GetGlobalPtrReturnData,
+            Simulation::GetGlobalExtraAbiData,
+        ],
+        "global_getters",
+        r#"
+; CHECK: load ptr addrspace(3), ptr @ptr_calldata
+; CHECK: %calldata_abi_integer = ptrtoint ptr addrspace(3)
+; CHECK: load i256, ptr @call_flags
+; CHECK: load ptr addrspace(3), ptr @ptr_return_data
+; CHECK: %return_data_abi_integer = ptrtoint ptr addrspace(3)
+; CHECK: %extra_active_data_element_pointer = getelementptr {{.*}}@extra_abi_data, i256 0, i256 %0
+; CHECK-NEXT: load i256, ptr %extra_active_data_element_pointer
+"#,
+    );
+}
+
+#[test]
+pub fn check_multiplication_high_register() {
+    check_simulations(
+        &[Simulation::MultiplicationHighRegister],
+        "multiplication_high_register",
+        r#"
+; CHECK: zext i256 %0 to i512
+; CHECK: zext i256 %3 to i512
+; CHECK: %mul_512_result_extended = mul i512
+; CHECK: lshr i512 %mul_512_result_extended, 256
+"#,
+    );
+}
+
+#[test]
+pub fn check_mimic_calls() {
+    check_simulations(
+        &[
+            Simulation::MimicCall,
+            Simulation::MimicCallByref,
+            Simulation::SystemMimicCall,
+            Simulation::SystemMimicCallByref,
+        ],
+        "mimic_calls",
+        r#"
+; CHECK: @__mimiccall(i256 %3, i256 %0, i256 undef{{.*}}, i256 %4)
+; CHECK: load ptr addrspace(3), ptr {{.*}}@ptr_active
+; CHECK: @__mimiccall_byref(ptr addrspace(3) %{{.*}}, i256 %0, i256 undef{{.*}}, i256 %4)
+; CHECK: @__mimiccall(i256 %3, i256 %0, i256 %5, i256 %6, i256 undef{{.*}}, i256 %4)
+; CHECK: @__mimiccall_byref(ptr addrspace(3) %{{.*}}, i256 %0, i256 %5, i256 %6, i256 undef{{.*}}, i256 %4)
+"#,
+    );
+}
+
+#[test]
+pub fn check_far_calls() {
+    check_simulations(
+        &[
+            Simulation::RawFarCall,
+            Simulation::RawFarCallByref,
+            Simulation::SystemCall,
+            Simulation::SystemCallByref,
+        ],
+        "far_calls",
+        r#"
+; CHECK: @__farcall(i256 %4, i256 %0, i256 undef
+; CHECK: @llvm.memcpy.p1.p3.i256(
+; CHECK: @__farcall_byref(ptr addrspace(3) %{{.*}}, i256 %0, i256 undef
+; CHECK: @llvm.memcpy.p1.p3.i256(
+; CHECK: @__farcall(i256 %4, i256 %0, i256 %2, i256 %3, i256 %5, i256 %6, i256 undef
+; CHECK: @llvm.memcpy.p1.p3.i256(
+; CHECK: @__farcall_byref(ptr addrspace(3) %{{.*}}, i256 %0, i256 %2, i256 %3, i256 %5, i256 %6, i256 undef
+"#,
+    );
+}
+
+#[test]
+pub fn check_active_pointer_loads() {
+    check_simulations(
+        &[
+            Simulation::ActivePtrLoadCalldata,
+            Simulation::ActivePtrLoadReturnData,
+            Simulation::ActivePtrLoadDecommit,
+        ],
+        "active_pointer_loads",
+        r#"
+; CHECK: load ptr addrspace(3), ptr @ptr_calldata
+; CHECK: store ptr addrspace(3) %{{.*}}, ptr {{.*}}@ptr_active
+; CHECK: load ptr addrspace(3), ptr @ptr_return_data
+; CHECK: store ptr addrspace(3) %{{.*}}, ptr {{.*}}@ptr_active
+; CHECK: load ptr addrspace(3), ptr @ptr_decommit
+; CHECK: store ptr addrspace(3) %{{.*}}, ptr {{.*}}@ptr_active
+"#,
+    );
+}
+
+#[test]
+pub fn check_active_pointer_modifiers() {
+    check_simulations(
+        &[
+            Simulation::ActivePtrAdd,
+            Simulation::ActivePtrShrink,
+            Simulation::ActivePtrPack,
+        ],
+        "active_pointer_modifiers",
+        r#"
+; CHECK: %active_pointer_shifted = getelementptr {{.*}}i256 %0
+; CHECK: call ptr addrspace(3) @llvm.eravm.ptr.shrink(ptr addrspace(3) %{{.*}}, i256 %0)
+; CHECK: call ptr addrspace(3) @llvm.eravm.ptr.pack(ptr addrspace(3) %{{.*}}, i256 %0)
+"#,
+    );
+}
+
+#[test]
+pub fn check_active_pointer_data() {
+    check_simulations(
+        &[
+            Simulation::ActivePtrDataLoad,
+            Simulation::ActivePtrDataSize,
+            Simulation::ActivePtrDataCopy,
+            Simulation::ActivePtrSwap,
+        ],
+        "active_pointer_data",
+        r#"
+; CHECK: %active_pointer_with_offset = getelementptr {{.*}}i256 %0
+; CHECK-NEXT: load i256, ptr addrspace(3) %active_pointer_with_offset
+; CHECK: %active_pointer_length =
+; CHECK: @llvm.memcpy.p1.p3.i256(ptr addrspace(1) {{.*}}, ptr addrspace(3) {{.*}}, i256 %4
+; CHECK: @ptr_active, i256 0, i256 %0
+; CHECK: @ptr_active, i256 0, i256 %3
+; CHECK: store ptr addrspace(3)
+; CHECK: store ptr addrspace(3)
+"#,
+    );
+}
+
+#[test]
+pub fn check_forwards() {
+    check_simulations(
+        &[Simulation::ReturnForward],
+        "return_forward",
+        r#"
+; CHECK: call void @__return_forward(ptr addrspace(3) %{{.*}})
+; CHECK-NEXT: unreachable
+"#,
+    );
+    check_simulations(
+        &[Simulation::RevertForward],
+        "revert_forward",
+        r#"
+; CHECK: call void @__revert_forward(ptr addrspace(3) %{{.*}})
+; CHECK-NEXT: unreachable
+"#,
+    );
+}
+
+#[test]
+pub fn check_const_array() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::eravm::extensions::tests::create_context(&llvm);
+    let function = add_function(&mut context, "const_array");
+    translate(
+        &mut context,
+        function,
+        Simulation::ConstArrayDeclare,
+        constants(&[Some(0), None, Some(2)]),
+    )
+    .expect("Failed to declare");
+    for (offset, value) in [(0, 42), (1, 7)] {
+        translate(
+            &mut context,
+            function,
+            Simulation::ConstArraySet,
+            constants(&[Some(0), None, Some(offset), None, Some(value)]),
+        )
+        .expect("Failed to set");
+    }
+    translate(
+        &mut context,
+        function,
+        Simulation::ConstArrayFinalize,
+        constants(&[Some(0)]),
+    )
+    .expect("Failed to finalize");
+    translate(
+        &mut context,
+        function,
+        Simulation::ConstArrayGet,
+        constants(&[Some(0)]),
+    )
+    .expect("Failed to get");
+
+    let file_check = FileCheck::new()
+        .check("@const_array_000 = {{.*}}addrspace(4) constant [2 x i256] [i256 42, i256 7]")
+        .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+    let file_check = FileCheck::new()
+        .check("%const_array_000_pointer = getelementptr {{.*}}@const_array_000, i256 0, i256 %3")
+        .and_then(|file_check| {
+            file_check.check_next(
+                "%const_array_000_value = load i256, ptr addrspace(4) %const_array_000_pointer",
+            )
+        })
+        .expect("Always valid");
+    crate::testing::check_function(&context, "const_array", &file_check).expect("Must match");
+
+    let error = translate(
+        &mut context,
+        function,
+        Simulation::ConstArrayGet,
+        constants(&[None]),
+    )
+    .expect_err("Must be rejected");
+    assert!(error.to_string().contains("Const array index"), "{error}");
+}
@@ -0,0 +1,173 @@
+//!
+//! The EraVM extensions tests.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::context::IContext;
+use crate::dependency::DummyDependency;
+use crate::eravm::context::address_space::AddressSpace;
+use crate::eravm::context::function::runtime::entry::Entry;
+use crate::eravm::context::function::runtime::Runtime;
+use crate::eravm::context::yul_data::YulData;
+use crate::eravm::context::Context;
+use crate::eravm::WriteLLVM;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::testing::file_check::FileCheck;
+
+/// The account validation success magic, left-aligned in a field.
+const ACCOUNT_VALIDATION_SUCCESS_MAGIC: &str =
+    "14551399762452420340928884176894323592566315527181406485860923357520346480640";
+
+/// The paymaster validation success magic, left-aligned in a field.
+const PAYMASTER_VALIDATION_SUCCESS_MAGIC: &str =
+    "1601016972027674087219096584282962507946555294275460347601787516721897144320";
+
+///
+/// Creates a context with the Yul data and all the globals the extensions rely on.
+///
+pub fn create_context(llvm: &inkwell::context::Context) -> Context<DummyDependency> {
+    let mut context = crate::testing::create_eravm_context(llvm, OptimizerSettings::none());
+    context.set_yul_data(YulData::new(true, BTreeMap::new()));
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    Entry::initialize_globals(&mut context).expect("Failed to initialize globals");
+    let generic_pointer_type = context.ptr_type(AddressSpace::Generic.into());
+    for name in [
+        crate::eravm::GLOBAL_CALLDATA_POINTER,
+        crate::eravm::GLOBAL_RETURN_DATA_POINTER,
+        crate::eravm::GLOBAL_DECOMMIT_POINTER,
+    ] {
+        context
+            .set_global(
+                name,
+                generic_pointer_type,
+                AddressSpace::Stack,
+                generic_pointer_type.const_null(),
+            )
+            .expect("Always valid");
+    }
+    context
+}
+
+#[test]
+pub fn check_aa_validation_success_magic() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm);
+    assert_eq!(
+        super::aa::account_validation_success_magic(&context)
+            .print_to_string()
+            .to_string(),
+        format!("i256 {ACCOUNT_VALIDATION_SUCCESS_MAGIC}")
+    );
+    assert_eq!(
+        super::aa::paymaster_validation_success_magic(&context)
+            .print_to_string()
+            .to_string(),
+        format!("i256 {PAYMASTER_VALIDATION_SUCCESS_MAGIC}")
+    );
+    context.set_code_segment(era_compiler_common::CodeSegment::Runtime);
+
+    crate::testing::add_function(&mut context, "account", 0, 0).expect("Failed to add function");
+    super::aa::return_account_validation_success(&mut context).expect("Failed to translate");
+    let file_check = FileCheck::new()
+        .check(format!("store i256 {ACCOUNT_VALIDATION_SUCCESS_MAGIC}, ptr addrspace(1)").as_str())
+        .and_then(|file_check| file_check.check("@__return(i256 0, i256 32, i256 {{.*}})"))
+        .and_then(|file_check| file_check.check_next("unreachable"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "account", &file_check).expect("Must match");
+
+    crate::testing::add_function(&mut context, "paymaster", 0, 0).expect("Failed to add function");
+    super::aa::return_paymaster_validation_success(&mut context).expect("Failed to translate");
+    let file_check = FileCheck::new()
+        .check(
+            format!("store i256 {PAYMASTER_VALIDATION_SUCCESS_MAGIC}, ptr addrspace(1)").as_str(),
+        )
+        .and_then(|file_check| file_check.check("store i256 64, ptr addrspace(1)"))
+        .and_then(|file_check| file_check.check("store i256 0, ptr addrspace(1)"))
+        .and_then(|file_check| file_check.check("@__return(i256 0, i256 96, i256 {{.*}})"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "paymaster", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_aa_nonce_holder_calls() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm);
+
+    let function =
+        crate::testing::add_function(&mut context, "get", 1, 0).expect("Failed to add function");
+    let address = function
+        .get_first_param()
+        .expect("Always exists")
+        .into_int_value();
+    super::aa::get_min_nonce(&mut context, address).expect("Failed to translate");
+    let file_check = FileCheck::new()
+        .check("store i256 %0, ptr")
+        .and_then(|file_check| {
+            file_check.check("@__system_request(i256 32771, i256 {{.*}}, i256 36, ptr")
+        })
+        .expect("Always valid");
+    crate::testing::check_function(&context, "get", &file_check).expect("Must match");
+
+    let function = crate::testing::add_function(&mut context, "increment", 1, 0)
+        .expect("Failed to add function");
+    let expected_nonce = function
+        .get_first_param()
+        .expect("Always exists")
+        .into_int_value();
+    super::aa::increment_min_nonce_if_equals(&mut context, expected_nonce)
+        .expect("Failed to translate");
+    let file_check = FileCheck::new()
+        .check("store i256 {{.*}}, ptr addrspace(2)")
+        .and_then(|file_check| file_check.check("store i256 %0, ptr addrspace(2)"))
+        .and_then(|file_check| file_check.check("@__farcall({{.*}}, i256 32771,"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "increment", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_extcodecopy() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = create_context(&llvm);
+    Runtime::default()
+        .declare(&mut context)
+        .expect("Failed to declare runtime");
+    let function = crate::testing::add_function(&mut context, "extcodecopy", 4, 0)
+        .expect("Failed to add function");
+    let operand = |index| {
+        function
+            .get_nth_param(index)
+            .expect("Always exists")
+            .into_int_value()
+    };
+    super::code::copy(&mut context, operand(0), operand(1), operand(2), operand(3))
+        .expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: call void @llvm.memset.p1.i256(ptr addrspace(1) {{.*}}%extcodecopy_destination_pointer, i8 0, i256 %3, i1 false)
+; CHECK: @__system_request(i256 32770,
+; CHECK: %extcodecopy_is_code_present = icmp ne i256 {{.*}}, 0
+; CHECK-NEXT: br i1 %extcodecopy_is_code_present, label %extcodecopy_decommit_block, label %extcodecopy_join_block
+; CHECK: extcodecopy_decommit_block:
+; CHECK-NEXT: %code_oracle_scratch_value = load i256, ptr addrspace(1) {{.*}}
+; CHECK-NEXT: store i256 {{.*}}, ptr addrspace(1) {{.*}}
+; CHECK: i256 32786
+; CHECK: store i256 %code_oracle_scratch_value, ptr addrspace(1) {{.*}}
+; CHECK-NEXT: %extcodecopy_is_success = icmp ne i256 {{.*}}, 0
+; CHECK-NEXT: br i1 %extcodecopy_is_success, label %extcodecopy_copy_block, label %extcodecopy_join_block
+; CHECK: extcodecopy_copy_block:
+; CHECK: %extcodecopy_is_source_offset_in_bounds = icmp ult i256 %2, {{.*}}
+; CHECK-NEXT: %extcodecopy_source_offset = select i1 %extcodecopy_is_source_offset_in_bounds, i256 %2, i256 {{.*}}
+; CHECK-NEXT: %extcodecopy_available_size = sub i256 {{.*}}, %extcodecopy_source_offset
+; CHECK-NEXT: %extcodecopy_is_size_available = icmp ult i256 %3, %extcodecopy_available_size
+; CHECK-NEXT: %extcodecopy_copy_size = select i1 %extcodecopy_is_size_available, i256 %3, i256 %extcodecopy_available_size
+; CHECK: extcodecopy_join_block:
+; CHECK: call void @llvm.memcpy.p1.p3.i256(ptr addrspace(1) {{.*}}, ptr addrspace(3) {{.*}}, i256 %extcodecopy_copy_size, i1 false)
+; CHECK-NEXT: br label %extcodecopy_join_block
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "extcodecopy", &file_check).expect("Must match");
+}
@@ -0,0 +1,100 @@
+//!
+//! The EraVM linker reconciliation report.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+///
+/// The EraVM linker reconciliation report.
+///
+/// Compares the placeholders referenced by an unlinked ELF object with the linker symbols and
+/// factory dependencies supplied by the caller.
+///
+/// The self canonical bytecode hash placeholder is resolved by the linker, so it is never
+/// unresolved.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LinkReport {
+    /// The placeholders referenced by the object, but not supplied by the caller.
+    pub unresolved: BTreeSet<String>,
+    /// The factory dependencies supplied by the caller, but not referenced by the object.
+    pub unreferenced_factory_dependencies: BTreeSet<String>,
+    /// The linker symbols supplied by the caller, but not referenced by the object.
+    pub unreferenced_linker_symbols: BTreeSet<String>,
+}
+
+impl LinkReport {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        undefined_references: &[String],
+        linker_symbols: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
+        factory_dependencies: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_FIELD]>,
+    ) -> Self {
+        let referenced: BTreeSet<&str> = undefined_references
+            .iter()
+            .map(|reference| reference.as_str())
+            .collect();
+
+        let unresolved = referenced
+            .iter()
+            .filter(|reference| {
+                **reference != crate::eravm::r#const::SELF_CANONICAL_BYTECODE_HASH_SYMBOL
+                    && !linker_symbols.contains_key(**reference)
+                    && !factory_dependencies.contains_key(**reference)
+            })
+            .map(|reference| reference.to_string())
+            .collect();
+        let unreferenced_factory_dependencies = factory_dependencies
+            .keys()
+            .filter(|identifier| !referenced.contains(identifier.as_str()))
+            .cloned()
+            .collect();
+        let unreferenced_linker_symbols = linker_symbols
+            .keys()
+            .filter(|identifier| !referenced.contains(identifier.as_str()))
+            .cloned()
+            .collect();
+
+        Self {
+            unresolved,
+            unreferenced_factory_dependencies,
+            unreferenced_linker_symbols,
+        }
+    }
+
+    ///
+    /// Whether every placeholder of the object has been supplied by the caller.
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+
+    ///
+    /// Whether the supplied symbols and the object placeholders match exactly.
+    ///
+    pub fn is_exact(&self) -> bool {
+        self.is_complete()
+            && self.unreferenced_factory_dependencies.is_empty()
+            && self.unreferenced_linker_symbols.is_empty()
+    }
+
+    ///
+    /// Returns an error describing the unresolved placeholders, if there are any.
+    ///
+    pub fn check_complete(&self) -> anyhow::Result<()> {
+        if !self.is_complete() {
+            anyhow::bail!(
+                "unresolved linker placeholders: {}",
+                self.unresolved
+                    .iter()
+                    .map(|reference| format!("`{reference}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+        }
+        Ok(())
+    }
+}
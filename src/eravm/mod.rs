@@ -2,12 +2,20 @@
 //! The LLVM context library.
 //!
 
+pub mod assembly;
 pub mod r#const;
 pub mod context;
+pub mod dependency_graph;
+pub mod disassembler;
+pub mod ergs_estimate;
 pub mod evm;
 pub mod extensions;
+pub mod link_report;
 pub mod utils;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::r#const::*;
 
 use std::collections::BTreeMap;
@@ -15,9 +23,15 @@ use std::collections::BTreeMap;
 use crate::debug_config::DebugConfig;
 use crate::dependency::Dependency;
 use crate::eravm::context::build::Build;
+use crate::error::Error;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::settings::Settings as OptimizerSettings;
 use crate::target_machine::TargetMachine;
+use crate::undefined_references::UndefinedReferences;
 
 use self::context::Context;
+use self::disassembler::options::Options as DisassemblerOptions;
+use self::link_report::LinkReport;
 
 ///
 /// Initializes the EraVM target machine.
@@ -47,28 +61,35 @@ pub fn assemble(
 
     let bytecode_buffer = target_machine
         .assemble(&assembly_buffer)
-        .map_err(|error| anyhow::anyhow!("assembling: {error}"))?;
+        .map_err(|error| Error::Assembly(format!("assembling: {error}")))?;
     Ok(bytecode_buffer)
 }
 
 ///
 /// Disassembles `bytecode`, returning textual representation.
 ///
+/// See `disassembler::disassemble` for the formatting options.
+///
 pub fn disassemble(
     target_machine: &TargetMachine,
     bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
 ) -> anyhow::Result<String> {
-    let disassembly_buffer = target_machine
-        .disassemble(bytecode_buffer, 0, DISASSEMBLER_DEFAULT_MODE)
-        .map_err(|error| anyhow::anyhow!("disassembling: {error}"))?;
-
-    let disassembly_text = String::from_utf8_lossy(disassembly_buffer.as_slice());
-    Ok(disassembly_text.to_string())
+    self::disassembler::disassemble(
+        target_machine,
+        bytecode_buffer,
+        &DisassemblerOptions::default(),
+    )
 }
 
 ///
 /// Links `bytecode_buffer` with `linker_symbols` and `factory_dependencies`.
 ///
+/// If the object references its own canonical bytecode hash, the placeholder is resolved after
+/// the rest of the object is linked. See `link_self_canonical_bytecode_hash` for details.
+///
+/// The returned report reconciles the placeholders of the object before linking with the
+/// supplied symbols. See `reconcile_link` for details.
+///
 pub fn link(
     bytecode_buffer: inkwell::memory_buffer::MemoryBuffer,
     linker_symbols: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
@@ -76,20 +97,160 @@ pub fn link(
 ) -> anyhow::Result<(
     inkwell::memory_buffer::MemoryBuffer,
     era_compiler_common::ObjectFormat,
+    LinkReport,
 )> {
+    let link_report = reconcile_link(&bytecode_buffer, linker_symbols, factory_dependencies);
     if !bytecode_buffer.is_elf_eravm() {
-        return Ok((bytecode_buffer, era_compiler_common::ObjectFormat::Raw));
+        return Ok((
+            bytecode_buffer,
+            era_compiler_common::ObjectFormat::Raw,
+            link_report,
+        ));
+    }
+
+    if get_undefined_references(&bytecode_buffer)
+        .factory_dependencies
+        .contains(SELF_CANONICAL_BYTECODE_HASH_SYMBOL)
+        && !factory_dependencies.contains_key(SELF_CANONICAL_BYTECODE_HASH_SYMBOL)
+    {
+        let (bytecode_buffer, object_format) = link_self_canonical_bytecode_hash(
+            &bytecode_buffer,
+            linker_symbols,
+            factory_dependencies,
+        )?;
+        return Ok((bytecode_buffer, object_format, link_report));
     }
 
     let bytecode_buffer_linked = bytecode_buffer
         .link_module_eravm(linker_symbols, factory_dependencies)
-        .map_err(|error| anyhow::anyhow!("bytecode linking: {error}"))?;
+        .map_err(|error| Error::Linker(format!("bytecode linking: {error}")))?;
     let object_format = if bytecode_buffer_linked.is_elf_eravm() {
         era_compiler_common::ObjectFormat::ELF
     } else {
         era_compiler_common::ObjectFormat::Raw
     };
-    Ok((bytecode_buffer_linked, object_format))
+    Ok((bytecode_buffer_linked, object_format, link_report))
+}
+
+///
+/// Links `bytecode_buffer` which references its own canonical bytecode hash.
+///
+/// A bytecode cannot contain its own hash, so the canonical hash is the hash of the canonical
+/// bytecode, where the placeholders are zeroed. Since the canonical bytecode does not depend on
+/// the placeholders, the resolution reaches the fixed point in one step: the object is linked
+/// with the zero placeholder, hashed, and linked again with the hash.
+///
+/// The canonical hash is not the hash of the deployed bytecode, so it does not match the code
+/// hash of the contract, e.g. `extcodehash(address())`, and cannot be used as a factory
+/// dependency to deploy the contract itself.
+///
+/// If the object has any other references left, it is returned as is, as the canonical hash
+/// cannot be computed until they are resolved.
+///
+fn link_self_canonical_bytecode_hash(
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    linker_symbols: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
+    factory_dependencies: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_FIELD]>,
+) -> anyhow::Result<(
+    inkwell::memory_buffer::MemoryBuffer,
+    era_compiler_common::ObjectFormat,
+)> {
+    let mut factory_dependencies = factory_dependencies.to_owned();
+    factory_dependencies.insert(
+        SELF_CANONICAL_BYTECODE_HASH_SYMBOL.to_owned(),
+        [0; era_compiler_common::BYTE_LENGTH_FIELD],
+    );
+    let canonical_buffer = bytecode_buffer
+        .link_module_eravm(linker_symbols, &factory_dependencies)
+        .map_err(|error| Error::Linker(format!("canonical bytecode linking: {error}")))?;
+    if canonical_buffer.is_elf_eravm() {
+        factory_dependencies.remove(SELF_CANONICAL_BYTECODE_HASH_SYMBOL);
+        let bytecode_buffer_linked = bytecode_buffer
+            .link_module_eravm(linker_symbols, &factory_dependencies)
+            .map_err(|error| Error::Linker(format!("bytecode linking: {error}")))?;
+        return Ok((
+            bytecode_buffer_linked,
+            era_compiler_common::ObjectFormat::ELF,
+        ));
+    }
+
+    let self_canonical_bytecode_hash = hash(&canonical_buffer)?;
+    factory_dependencies.insert(
+        SELF_CANONICAL_BYTECODE_HASH_SYMBOL.to_owned(),
+        self_canonical_bytecode_hash,
+    );
+    let bytecode_buffer_linked = bytecode_buffer
+        .link_module_eravm(linker_symbols, &factory_dependencies)
+        .map_err(|error| Error::Linker(format!("bytecode linking: {error}")))?;
+    if bytecode_buffer_linked.get_size() != canonical_buffer.get_size() {
+        return Err(Error::Linker(
+            "self canonical bytecode hash resolution has changed the bytecode size".to_owned(),
+        )
+        .into());
+    }
+    Ok((
+        bytecode_buffer_linked,
+        era_compiler_common::ObjectFormat::Raw,
+    ))
+}
+
+///
+/// Links `bytecode_buffer` with the symbols known so far, returning the remaining references.
+///
+/// The returned object can be fed into this function again with the symbols discovered later,
+/// until there are no undefined references left and the object becomes raw bytecode.
+///
+/// The link report of this step is returned as well. See `reconcile_link` for details.
+///
+pub fn link_incremental(
+    bytecode_buffer: inkwell::memory_buffer::MemoryBuffer,
+    linker_symbols: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
+    factory_dependencies: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_FIELD]>,
+) -> anyhow::Result<(
+    inkwell::memory_buffer::MemoryBuffer,
+    UndefinedReferences,
+    LinkReport,
+)> {
+    let (bytecode_buffer, _object_format, link_report) =
+        link(bytecode_buffer, linker_symbols, factory_dependencies)?;
+    let undefined_references = get_undefined_references(&bytecode_buffer);
+    Ok((bytecode_buffer, undefined_references, link_report))
+}
+
+///
+/// Returns the linker symbols and factory dependencies `bytecode_buffer` still references.
+///
+/// Raw bytecode has no references left, so the result is empty for it.
+///
+pub fn get_undefined_references(
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+) -> UndefinedReferences {
+    if !bytecode_buffer.is_elf_eravm() {
+        return UndefinedReferences::default();
+    }
+
+    UndefinedReferences::new(
+        bytecode_buffer.get_undefined_references_eravm(),
+        bytecode_buffer.get_undefined_factory_dependencies_eravm(),
+    )
+}
+
+///
+/// Reconciles the placeholders of `bytecode_buffer` with `linker_symbols` and `factory_dependencies`.
+///
+/// Linked objects have no placeholders left, so only the unreferenced symbols are reported for them.
+///
+pub fn reconcile_link(
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+    linker_symbols: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS]>,
+    factory_dependencies: &BTreeMap<String, [u8; era_compiler_common::BYTE_LENGTH_FIELD]>,
+) -> LinkReport {
+    let undefined_references = get_undefined_references(bytecode_buffer);
+    LinkReport::new(
+        undefined_references.all().as_slice(),
+        linker_symbols,
+        factory_dependencies,
+    )
 }
 
 ///
@@ -127,13 +288,10 @@ pub fn hash(
 ///
 pub fn build(
     bytecode_buffer: inkwell::memory_buffer::MemoryBuffer,
-    metadata_hash: Option<era_compiler_common::Hash>,
+    metadata: &MetadataBuilder,
     assembly_text: Option<String>,
 ) -> anyhow::Result<Build> {
-    let metadata_hash = metadata_hash.as_ref().map(|hash| match hash {
-        era_compiler_common::Hash::Keccak256 { bytes, .. } => bytes.to_vec(),
-        hash @ era_compiler_common::Hash::Ipfs { .. } => hash.as_cbor_bytes(),
-    });
+    let metadata_hash = metadata.build();
     let bytecode_buffer_with_metadata = match metadata_hash {
         Some(ref metadata) => bytecode_buffer
             .append_metadata_eravm(metadata.as_slice())
@@ -146,6 +304,24 @@ pub fn build(
     Ok(build)
 }
 
+///
+/// Builds multiple contracts in parallel.
+///
+/// Each call of `build` runs on a worker thread and must create its own LLVM context.
+///
+pub fn build_many<I, F>(
+    inputs: BTreeMap<String, I>,
+    optimizer_settings: &OptimizerSettings,
+    threads: Option<usize>,
+    build: F,
+) -> BTreeMap<String, anyhow::Result<Build>>
+where
+    I: Send,
+    F: Fn(&str, I, &OptimizerSettings) -> anyhow::Result<Build> + Sync,
+{
+    crate::parallel::build_many(inputs, optimizer_settings, threads, build)
+}
+
 ///
 /// Implemented by items which are translated into LLVM IR.
 ///
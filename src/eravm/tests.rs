@@ -0,0 +1,186 @@
+//!
+//! The EraVM tests.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::context::IContext;
+use crate::eravm::context::build::Build;
+use crate::eravm::context::emit_request::EmitRequest;
+use crate::eravm::link_report::LinkReport;
+use crate::error::Error;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::profiler::Profiler;
+use crate::target_machine::TargetMachine;
+
+fn bytecode_buffer() -> inkwell::memory_buffer::MemoryBuffer {
+    inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+        &[0u8; era_compiler_common::BYTE_LENGTH_FIELD],
+        "bytecode_buffer",
+    )
+}
+
+#[test]
+pub fn check_link_report_success() {
+    let (_bytecode_buffer, object_format, link_report) =
+        crate::eravm::link(bytecode_buffer(), &BTreeMap::new(), &BTreeMap::new())
+            .expect("Failed to link");
+    assert!(matches!(
+        object_format,
+        era_compiler_common::ObjectFormat::Raw
+    ));
+    assert!(link_report.is_exact());
+    assert_eq!(link_report, LinkReport::default());
+
+    let (_bytecode_buffer, undefined_references, link_report) =
+        crate::eravm::link_incremental(bytecode_buffer(), &BTreeMap::new(), &BTreeMap::new())
+            .expect("Failed to link");
+    assert!(undefined_references.all().is_empty());
+    assert!(link_report.is_exact());
+}
+
+#[test]
+pub fn check_link_report_mismatch() {
+    let linker_symbols = BTreeMap::from([(
+        "library.sol:Library".to_owned(),
+        [0u8; era_compiler_common::BYTE_LENGTH_ETH_ADDRESS],
+    )]);
+    let factory_dependencies = BTreeMap::from([(
+        "factory.sol:Factory".to_owned(),
+        [0u8; era_compiler_common::BYTE_LENGTH_FIELD],
+    )]);
+
+    let (_bytecode_buffer, _object_format, link_report) =
+        crate::eravm::link(bytecode_buffer(), &linker_symbols, &factory_dependencies)
+            .expect("Failed to link");
+    assert!(link_report.is_complete());
+    assert!(!link_report.is_exact());
+    assert_eq!(
+        link_report.unreferenced_linker_symbols,
+        linker_symbols.keys().cloned().collect()
+    );
+    assert_eq!(
+        link_report.unreferenced_factory_dependencies,
+        factory_dependencies.keys().cloned().collect()
+    );
+
+    let (_bytecode_buffer, _undefined_references, link_report) =
+        crate::eravm::link_incremental(bytecode_buffer(), &linker_symbols, &BTreeMap::new())
+            .expect("Failed to link");
+    assert_eq!(
+        link_report.unreferenced_linker_symbols,
+        linker_symbols.keys().cloned().collect()
+    );
+
+    let link_report = LinkReport::new(
+        &[
+            "library.sol:Library".to_owned(),
+            "other.sol:Other".to_owned(),
+            crate::eravm::r#const::SELF_CANONICAL_BYTECODE_HASH_SYMBOL.to_owned(),
+        ],
+        &linker_symbols,
+        &BTreeMap::new(),
+    );
+    assert!(!link_report.is_complete());
+    assert_eq!(
+        link_report.unresolved,
+        ["other.sol:Other".to_owned()].into_iter().collect()
+    );
+    let error = link_report
+        .check_complete()
+        .expect_err("Must be rejected")
+        .to_string();
+    assert!(error.contains("`other.sol:Other`"), "{error}");
+}
+
+#[test]
+pub fn check_build_many() {
+    let inputs: BTreeMap<String, usize> = (0..4)
+        .map(|index| (format!("contract_{index}.sol:Test"), index))
+        .collect();
+
+    let results = crate::eravm::build_many(
+        inputs,
+        &OptimizerSettings::cycles(),
+        Some(2),
+        |contract_path, input, optimizer_settings| {
+            let llvm = inkwell::context::Context::create();
+            let mut context =
+                crate::testing::create_eravm_context(&llvm, optimizer_settings.to_owned());
+            crate::testing::add_function(&mut context, contract_path, input, 0)?;
+            context.build_unreachable()?;
+            let function = context
+                .module()
+                .get_function(contract_path)
+                .ok_or_else(|| anyhow::anyhow!("Function `{contract_path}` is not declared"))?;
+            Ok(Build::new(vec![function.count_params() as u8], None, None))
+        },
+    );
+    assert_eq!(results.len(), 4);
+    for (index, result) in results.into_values().enumerate() {
+        assert_eq!(result.expect("Failed to build").bytecode, vec![index as u8]);
+    }
+}
+
+#[test]
+pub fn check_error_stages() {
+    let llvm = inkwell::context::Context::create();
+    let build = |optimizer_settings: OptimizerSettings, is_terminated: bool| {
+        let mut context = crate::testing::create_eravm_context(&llvm, optimizer_settings);
+        crate::testing::add_function(&mut context, "test", 0, 0).expect("Failed to add function");
+        if is_terminated {
+            context.build_unreachable().expect("Always valid");
+        }
+        context
+            .build(
+                "test.sol:Test",
+                &MetadataBuilder::none(),
+                EmitRequest::object(),
+                false,
+                &mut Profiler::new(),
+                None,
+            )
+            .expect_err("Must fail")
+    };
+
+    let error = build(OptimizerSettings::cycles(), false);
+    assert!(
+        matches!(error.downcast_ref::<Error>(), Some(Error::Verification(_))),
+        "{error}"
+    );
+
+    let error = build(
+        OptimizerSettings::cycles().with_custom_pass_pipeline("unknown-pass".to_owned()),
+        true,
+    );
+    assert!(
+        matches!(error.downcast_ref::<Error>(), Some(Error::Optimization(_))),
+        "{error}"
+    );
+
+    let mut optimizer_settings = OptimizerSettings::cycles();
+    optimizer_settings.set_eravm_bytecode_size_limit(Some(1));
+    let error = build(optimizer_settings, true);
+    assert!(
+        matches!(
+            error.downcast_ref::<Error>(),
+            Some(Error::BytecodeSizeExceeded { limit: 1, .. })
+        ),
+        "{error}"
+    );
+
+    crate::eravm::initialize_target();
+    let target_machine = TargetMachine::new(
+        era_compiler_common::Target::EraVM,
+        &OptimizerSettings::cycles(),
+        &[],
+    )
+    .expect("Failed to create target machine");
+    let error = crate::eravm::assemble(&target_machine, "test.sol:Test", "unknown_opcode", None)
+        .expect_err("Must fail");
+    assert!(
+        matches!(error.downcast_ref::<Error>(), Some(Error::Assembly(_))),
+        "{error}"
+    );
+}
@@ -0,0 +1,84 @@
+//!
+//! The LLVM context error.
+//!
+
+///
+/// The LLVM context error.
+///
+/// The public pipeline functions, e.g. `build`, `prepare`, `assemble`, and `link`, still return
+/// `anyhow::Result`, so the typed error is not visible in their signatures and must be obtained
+/// with `anyhow::Error::downcast_ref`. The downcast returns `None` for the errors that are not
+/// specific to a pipeline stage, e.g. the debug dump I/O errors.
+///
+/// The messages are the same as before the error was introduced.
+///
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The LLVM IR verification error.
+    #[error("{0}")]
+    Verification(String),
+    /// The LLVM IR optimization error.
+    #[error("{0}")]
+    Optimization(String),
+    /// The assembly or bytecode emitting error.
+    #[error("{0}")]
+    Emitting(String),
+    /// The assembling error.
+    #[error("{0}")]
+    Assembly(String),
+    /// The bytecode size limit overflow.
+    #[error("bytecode size exceeds the limit of {limit} instructions")]
+    BytecodeSizeExceeded {
+        /// The bytecode size in instructions.
+        found: usize,
+        /// The bytecode size limit in instructions.
+        limit: usize,
+    },
+    /// The linker error.
+    #[error("{0}")]
+    Linker(String),
+    /// The compilation has been cancelled.
+    #[error("compilation cancelled before {stage}")]
+    Cancelled {
+        /// The pipeline stage the compilation has been cancelled before.
+        stage: String,
+    },
+    /// The error of the fallback build optimized for size.
+    #[error("falling back to optimizing for size: {0}")]
+    SizeFallback(Box<Error>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    pub fn check_error_messages() {
+        let error: anyhow::Error = Error::SizeFallback(Box::new(Error::BytecodeSizeExceeded {
+            found: 70000,
+            limit: 65536,
+        }))
+        .into();
+        assert_eq!(
+            error.to_string(),
+            "falling back to optimizing for size: bytecode size exceeds the limit of 65536 instructions"
+        );
+        match error.downcast_ref::<Error>() {
+            Some(Error::SizeFallback(inner)) => assert!(matches!(
+                inner.as_ref(),
+                Error::BytecodeSizeExceeded {
+                    found: 70000,
+                    limit: 65536
+                }
+            )),
+            _ => panic!("Unexpected error: {error:?}"),
+        }
+
+        let error = anyhow::Error::from(Error::Verification("broken module".to_owned()))
+            .context("building `test.sol:Test`");
+        assert!(matches!(
+            error.downcast_ref::<Error>(),
+            Some(Error::Verification(message)) if message == "broken module"
+        ));
+    }
+}
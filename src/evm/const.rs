@@ -4,3 +4,12 @@
 
 /// The entry function name.
 pub const ENTRY_FUNCTION_NAME: &str = "__entry";
+
+/// The EIP-1167 minimal proxy runtime code before the target address.
+pub const MINIMAL_PROXY_PREFIX: [u8; 10] =
+    [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+
+/// The EIP-1167 minimal proxy runtime code after the target address.
+pub const MINIMAL_PROXY_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
@@ -29,6 +29,32 @@ impl IAddressSpace for AddressSpace {
     fn stack() -> Self {
         Self::Stack
     }
+
+    fn heap() -> Self {
+        Self::Heap
+    }
+
+    fn constants() -> Self {
+        // The constants are placed in the code, so they can be copied with `codecopy`.
+        Self::Code
+    }
+
+    fn is_writable(self) -> bool {
+        !matches!(self, Self::Calldata | Self::ReturnData | Self::Code)
+    }
+
+    fn is_cast_legal_to(self, target: Self) -> bool {
+        if self == target {
+            return true;
+        }
+        let is_memory = |address_space: Self| {
+            matches!(
+                address_space,
+                Self::Heap | Self::Calldata | Self::ReturnData | Self::Code
+            )
+        };
+        is_memory(self) && is_memory(target)
+    }
 }
 
 impl From<AddressSpace> for inkwell::AddressSpace {
@@ -2,6 +2,12 @@
 //! The LLVM module build.
 //!
 
+use std::collections::BTreeMap;
+
+use crate::artifact_bundle::ArtifactBundle;
+use crate::diagnostic::Diagnostic;
+use crate::size_fallback::SizeFallback;
+
 ///
 /// The LLVM module build.
 ///
@@ -11,6 +17,18 @@ pub struct Build {
     pub bytecode: Vec<u8>,
     /// The project metadata hash.
     pub metadata_hash: Option<Vec<u8>>,
+    /// The warnings produced by the cross-contract analyses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// The diagnostics collected during lowering.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    /// The size fallback attempts.
+    #[serde(default)]
+    pub size_fallback: SizeFallback,
+    /// The static gas estimates of the basic blocks, keyed by the code offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_estimates: Option<BTreeMap<usize, u64>>,
 }
 
 impl Build {
@@ -21,6 +39,38 @@ impl Build {
         Self {
             bytecode,
             metadata_hash,
+            warnings: vec![],
+            diagnostics: vec![],
+            size_fallback: SizeFallback::default(),
+            gas_estimates: None,
         }
     }
+
+    ///
+    /// Estimates the static gas costs of the bytecode basic blocks.
+    ///
+    /// Must be called after linking, since the unlinked object is not valid bytecode.
+    ///
+    pub fn estimate_gas(&mut self) {
+        self.gas_estimates = Some(crate::evm::gas_estimate::estimate(self.bytecode.as_slice()));
+    }
+
+    ///
+    /// Converts the build into a combined artifact bundle.
+    ///
+    pub fn into_bundle(
+        self,
+        contract_path: &str,
+        code_segment: era_compiler_common::CodeSegment,
+    ) -> ArtifactBundle {
+        let mut bundle = ArtifactBundle::new(
+            contract_path.to_owned(),
+            Some(code_segment),
+            self.bytecode.as_slice(),
+        );
+        bundle.metadata_hash = self
+            .metadata_hash
+            .map(|hash| ArtifactBundle::encode_hex(hash.as_slice()));
+        bundle
+    }
 }
@@ -313,37 +313,6 @@ impl<'ctx> IEVMLAFunction<'ctx> for Function<'ctx> {
         key: &BlockKey,
         stack_hash: &[u8; era_compiler_common::BYTE_LENGTH_FIELD],
     ) -> anyhow::Result<Block<'ctx>> {
-        let evmla_data = self.evmla();
-
-        if evmla_data
-            .blocks
-            .get(key)
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-            .len()
-            == 1
-        {
-            return evmla_data
-                .blocks
-                .get(key)
-                .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-                .first()
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key));
-        }
-
-        evmla_data
-            .blocks
-            .get(key)
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))?
-            .iter()
-            .find(|block| {
-                block
-                    .evm()
-                    .stack_hashes
-                    .iter()
-                    .any(|hash| hash == stack_hash)
-            })
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Undeclared function block {}", key))
+        self.evmla().find_block(key, stack_hash)
     }
 }
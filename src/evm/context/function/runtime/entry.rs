@@ -4,6 +4,7 @@
 
 use std::marker::PhantomData;
 
+use crate::context::selector_dispatch::SelectorDispatch;
 use crate::context::IContext;
 use crate::evm::context::Context;
 use crate::evm::Dependency;
@@ -22,6 +23,8 @@ where
 {
     /// The runtime code AST representation.
     inner: B,
+    /// The optional selector dispatch emitted before the runtime code.
+    selector_dispatch: Option<SelectorDispatch>,
     /// The `D` phantom data.
     _pd: PhantomData<D>,
 }
@@ -37,9 +40,20 @@ where
     pub fn new(inner: B) -> Self {
         Self {
             inner,
+            selector_dispatch: None,
             _pd: PhantomData,
         }
     }
+
+    ///
+    /// Sets the selector dispatch emitted before the runtime code.
+    ///
+    /// The runtime code is only executed for short calldata and unknown selectors.
+    ///
+    pub fn with_selector_dispatch(mut self, selector_dispatch: SelectorDispatch) -> Self {
+        self.selector_dispatch = Some(selector_dispatch);
+        self
+    }
 }
 
 impl<B, D> WriteLLVM<D> for Entry<B, D>
@@ -63,6 +77,25 @@ where
         context.set_current_function(crate::evm::r#const::ENTRY_FUNCTION_NAME)?;
 
         context.set_basic_block(context.current_function().borrow().entry_block());
+        if let Some(selector_dispatch) = self.selector_dispatch {
+            if context.code_segment() == Some(era_compiler_common::CodeSegment::Deploy) {
+                anyhow::bail!("Selector dispatch is only supported in the runtime code");
+            }
+
+            let fallback_block = context.append_basic_block("selector_dispatch_fallback_block");
+            let calldata_size = crate::evm::instructions::calldata::size(context)?.into_int_value();
+            let calldata_word =
+                crate::evm::instructions::calldata::load(context, context.field_const(0))?
+                    .into_int_value();
+            selector_dispatch.build(
+                context,
+                calldata_size,
+                calldata_word,
+                fallback_block,
+                context.current_function().borrow().return_block(),
+            )?;
+            context.set_basic_block(fallback_block);
+        }
         self.inner.into_llvm(context)?;
         match context
             .basic_block()
@@ -0,0 +1,122 @@
+//!
+//! The minimal deploy code function.
+//!
+
+use crate::context::IContext;
+use crate::evm::context::Context;
+use crate::evm::Dependency;
+use crate::evm::WriteLLVM;
+
+///
+/// The minimal deploy code function.
+///
+/// Is the entry function of a deploy code object which returns its runtime code subobject as is,
+/// without running a constructor. Unlike the other runtime functions, it is not wrapped by
+/// `Entry`, as it is the whole deploy code.
+///
+/// The metadata is appended to the linked bytecode by `evm::build`.
+///
+#[derive(Debug, Clone)]
+pub struct MinimalDeployCode {
+    /// The deploy code object name.
+    deploy_object_name: String,
+    /// The runtime code object name.
+    runtime_object_name: String,
+    /// Whether the constructor arguments are appended to the returned runtime code.
+    are_constructor_arguments_copied: bool,
+}
+
+impl MinimalDeployCode {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(deploy_object_name: String, runtime_object_name: String) -> Self {
+        Self {
+            deploy_object_name,
+            runtime_object_name,
+            are_constructor_arguments_copied: false,
+        }
+    }
+
+    ///
+    /// Appends the constructor arguments, which follow the deploy code, to the returned runtime
+    /// code, so the runtime code can read them with `codecopy`.
+    ///
+    pub fn with_constructor_arguments(mut self) -> Self {
+        self.are_constructor_arguments_copied = true;
+        self
+    }
+}
+
+impl<D> WriteLLVM<D> for MinimalDeployCode
+where
+    D: Dependency,
+{
+    fn declare(&mut self, context: &mut Context<D>) -> anyhow::Result<()> {
+        let function_type = context.function_type::<inkwell::types::BasicTypeEnum>(vec![], 0);
+        context.add_function(
+            crate::evm::r#const::ENTRY_FUNCTION_NAME,
+            function_type,
+            0,
+            Some(inkwell::module::Linkage::External),
+        )?;
+        Ok(())
+    }
+
+    fn into_llvm(self, context: &mut Context<D>) -> anyhow::Result<()> {
+        context.set_current_function(crate::evm::r#const::ENTRY_FUNCTION_NAME)?;
+        context.set_basic_block(context.current_function().borrow().entry_block());
+
+        let runtime_code_offset = crate::evm::instructions::code::data_offset(
+            context,
+            self.runtime_object_name.as_str(),
+        )?
+        .into_int_value();
+        let runtime_code_size =
+            crate::evm::instructions::code::data_size(context, self.runtime_object_name.as_str())?
+                .into_int_value();
+        crate::evm::instructions::code::copy(
+            context,
+            context.field_const(0),
+            runtime_code_offset,
+            runtime_code_size,
+        )?;
+
+        let return_data_length = if self.are_constructor_arguments_copied {
+            let deploy_code_size = crate::evm::instructions::code::data_size(
+                context,
+                self.deploy_object_name.as_str(),
+            )?
+            .into_int_value();
+            let code_size = crate::evm::instructions::code::size(context)?.into_int_value();
+            let constructor_arguments_size = context.builder().build_int_sub(
+                code_size,
+                deploy_code_size,
+                "constructor_arguments_size",
+            )?;
+            crate::evm::instructions::code::copy(
+                context,
+                runtime_code_size,
+                deploy_code_size,
+                constructor_arguments_size,
+            )?;
+            context.builder().build_int_add(
+                runtime_code_size,
+                constructor_arguments_size,
+                "return_data_length",
+            )?
+        } else {
+            runtime_code_size
+        };
+        crate::evm::instructions::r#return::r#return(
+            context,
+            context.field_const(0),
+            return_data_length,
+        )?;
+
+        context.set_basic_block(context.current_function().borrow().return_block());
+        context.build_return(None)?;
+
+        Ok(())
+    }
+}
@@ -3,3 +3,4 @@
 //!
 
 pub mod entry;
+pub mod minimal_deploy_code;
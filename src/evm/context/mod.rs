@@ -6,28 +6,43 @@ pub mod address_space;
 pub mod build;
 pub mod evmla_data;
 pub mod function;
+pub mod vyper_data;
+
+#[cfg(test)]
+mod tests;
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::rc::Rc;
 
 use inkwell::types::BasicType;
+use inkwell::values::BasicValue;
 
+use crate::cancellation_handle::CancellationHandle;
 use crate::context::attribute::Attribute;
+use crate::context::constant_pool::ConstantPool;
 use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::function::evmla_builder::EVMLAFunctionBuilder;
 use crate::context::function::r#return::Return as FunctionReturn;
+use crate::context::pointer::Pointer;
 use crate::context::r#loop::Loop;
 use crate::context::IContext;
 use crate::debug_config::DebugConfig;
 use crate::debug_info::DebugInfo;
 use crate::dependency::Dependency;
+use crate::diagnostic::suggestion;
+use crate::diagnostic::Diagnostic;
+use crate::error::Error;
 use crate::optimizer::Optimizer;
-use crate::target_machine::TargetMachine;
+use crate::target_machine::options::Options as TargetOptions;
+use crate::target_machine_pool::TargetMachinePool;
 
 use self::address_space::AddressSpace;
 use self::evmla_data::EVMLAData;
 use self::function::intrinsics::Intrinsics;
 use self::function::Function;
+use self::vyper_data::VyperData;
 
 ///
 /// The LLVM IR generator context.
@@ -49,6 +64,8 @@ where
     module: inkwell::module::Module<'ctx>,
     /// The extra LLVM options.
     llvm_options: Vec<String>,
+    /// The target CPU and features.
+    target_options: TargetOptions,
     /// The current contract code type, which can be deploy or runtime.
     code_segment: era_compiler_common::CodeSegment,
     /// The LLVM intrinsic functions, defined on the LLVM side.
@@ -68,9 +85,17 @@ where
     debug_info: DebugInfo<'ctx>,
     /// The debug configuration telling whether to dump the needed IRs.
     debug_config: Option<DebugConfig>,
+    /// The diagnostics collected during lowering.
+    diagnostics: Vec<Diagnostic>,
+    /// The module-level constant bytes pool.
+    constant_pool: ConstantPool<'ctx, AddressSpace>,
+    /// Whether the target supports the unwinding.
+    is_unwinding_supported: bool,
 
     /// The EVM legacy assembly data.
     evmla_data: Option<EVMLAData<'ctx>>,
+    /// The Vyper data.
+    vyper_data: Option<VyperData>,
 }
 
 impl<'ctx, D> Context<'ctx, D>
@@ -83,6 +108,9 @@ where
     /// The loop stack default capacity.
     const LOOP_STACK_INITIAL_CAPACITY: usize = 16;
 
+    /// The LLVM personality function name, used for exception handling.
+    pub const FUNCTION_PERSONALITY: &'static str = "__personality";
+
     ///
     /// Initializes a new LLVM context.
     ///
@@ -103,6 +131,7 @@ where
             llvm,
             builder,
             llvm_options,
+            target_options: TargetOptions::default(),
             optimizer,
             module,
             code_segment,
@@ -114,43 +143,98 @@ where
             dependency_manager,
             debug_info,
             debug_config,
+            diagnostics: vec![],
+            constant_pool: ConstantPool::default(),
+            is_unwinding_supported: false,
 
             evmla_data: None,
+            vyper_data: None,
         }
     }
 
+    ///
+    /// Initializes a new LLVM context from the module `bitcode`, e.g. written by `write_bitcode`.
+    ///
+    /// Returns an error if the module target triple or data layout does not match EVM.
+    ///
+    /// Only the module is restored, so the context is meant to be built rather than extended.
+    ///
+    pub fn from_bitcode(
+        llvm: &'ctx inkwell::context::Context,
+        bitcode: &inkwell::memory_buffer::MemoryBuffer,
+        llvm_options: Vec<String>,
+        code_segment: era_compiler_common::CodeSegment,
+        optimizer: Optimizer,
+        dependency_manager: Option<D>,
+        debug_config: Option<DebugConfig>,
+    ) -> anyhow::Result<Self> {
+        let module = inkwell::module::Module::parse_bitcode_from_buffer(bitcode, llvm)
+            .map_err(|error| anyhow::anyhow!("bitcode parsing: {error}"))?;
+        let target_machine = TargetMachinePool::global().get(
+            era_compiler_common::Target::EVM,
+            optimizer.settings(),
+            llvm_options.as_slice(),
+            &TargetOptions::default(),
+        )?;
+        target_machine.check_target_data(&module)?;
+        Ok(Self::new(
+            llvm,
+            module,
+            llvm_options,
+            code_segment,
+            optimizer,
+            dependency_manager,
+            debug_config,
+        ))
+    }
+
     ///
     /// Builds the LLVM IR module, returning the build artifacts.
     ///
+    /// If `cancellation_handle` is set, it is checked between the pipeline stages.
+    ///
     pub fn build(
         self,
         contract_path: &str,
+        cancellation_handle: Option<&CancellationHandle>,
     ) -> anyhow::Result<inkwell::memory_buffer::MemoryBuffer> {
-        let target_machine = TargetMachine::new(
+        let target_machine = TargetMachinePool::global().get(
             era_compiler_common::Target::EVM,
             self.optimizer.settings(),
             self.llvm_options.as_slice(),
+            &self.target_options,
         )?;
         target_machine.set_target_data(self.module());
 
-        if let Some(ref debug_config) = self.debug_config {
-            debug_config.dump_llvm_ir_unoptimized(
-                contract_path,
-                Some(self.code_segment),
-                self.module(),
-                false,
-            )?;
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("verification")?;
         }
-        self.verify().map_err(|error| {
-            anyhow::anyhow!(
-                "{} code unoptimized LLVM IR verification: {error}",
-                self.code_segment,
-            )
-        })?;
+        self.check(contract_path)?;
 
         self.optimizer
-            .run(&target_machine, self.module())
-            .map_err(|error| anyhow::anyhow!("{} code optimizing: {error}", self.code_segment))?;
+            .prune_unreachable(&target_machine, self.module())
+            .map_err(|error| {
+                Error::Optimization(format!(
+                    "{} code unreachable function pruning: {error}",
+                    self.code_segment
+                ))
+            })?;
+
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("optimization")?;
+        }
+        self.optimizer
+            .run(
+                &target_machine,
+                self.module(),
+                contract_path,
+                Some(self.code_segment),
+                None,
+                self.debug_config.as_ref(),
+            )
+            .map_err(|error| {
+                Error::Optimization(format!("{} code optimizing: {error}", self.code_segment))
+            })?;
         if let Some(ref debug_config) = self.debug_config {
             debug_config.dump_llvm_ir_optimized(
                 contract_path,
@@ -160,20 +244,56 @@ where
             )?;
         }
         self.verify().map_err(|error| {
-            anyhow::anyhow!(
+            Error::Verification(format!(
                 "{} code optimized LLVM IR verification: {error}",
                 self.code_segment,
-            )
+            ))
         })?;
 
+        if let Some(cancellation_handle) = cancellation_handle {
+            cancellation_handle.check("emitting")?;
+        }
         let buffer = target_machine
             .write_to_memory_buffer(self.module(), inkwell::targets::FileType::Object)
             .map_err(|error| {
-                anyhow::anyhow!("{} code assembly emitting: {error}", self.code_segment)
+                Error::Emitting(format!(
+                    "{} code assembly emitting: {error}",
+                    self.code_segment
+                ))
             })?;
         Ok(buffer)
     }
 
+    ///
+    /// Checks the unoptimized LLVM IR module without optimizing it and generating code.
+    ///
+    /// The module is dumped if the debug configuration is set. The verification errors are
+    /// returned as `Error::Verification`.
+    ///
+    pub fn check(&self, contract_path: &str) -> anyhow::Result<()> {
+        if let Some(ref debug_config) = self.debug_config {
+            debug_config.dump_llvm_ir_unoptimized(
+                contract_path,
+                Some(self.code_segment),
+                self.module(),
+                false,
+            )?;
+        }
+        self.check_terminators().map_err(|error| {
+            Error::Verification(format!(
+                "{} code unoptimized LLVM IR verification: {error}",
+                self.code_segment,
+            ))
+        })?;
+        self.verify().map_err(|error| {
+            Error::Verification(format!(
+                "{} code unoptimized LLVM IR verification: {error}",
+                self.code_segment,
+            ))
+        })?;
+        Ok(())
+    }
+
     ///
     /// Verifies the current LLVM IR module.
     ///
@@ -183,6 +303,150 @@ where
             .map_err(|error| anyhow::anyhow!(error.to_string()))
     }
 
+    ///
+    /// Sets the target CPU and features, e.g. to select an EVM hard fork.
+    ///
+    pub fn set_target_options(&mut self, target_options: TargetOptions) {
+        self.target_options = target_options;
+    }
+
+    ///
+    /// Builds a call with the call site `attributes`.
+    ///
+    fn build_call_site(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: &[inkwell::values::BasicMetadataValueEnum<'ctx>],
+        attributes: &[Attribute],
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        if attributes.contains(&Attribute::AlwaysInline)
+            && attributes.contains(&Attribute::NoInline)
+        {
+            anyhow::bail!("Call site `{name}` cannot be both always and never inlined");
+        }
+
+        let call_site_value = self.builder.build_indirect_call(
+            function.r#type,
+            function.value.as_global_value().as_pointer_value(),
+            arguments,
+            name,
+        )?;
+        for attribute in attributes.iter() {
+            call_site_value.add_attribute(
+                inkwell::attributes::AttributeLoc::Function,
+                self.llvm.create_enum_attribute(*attribute as u32, 0),
+            );
+        }
+        self.modify_call_site_value(arguments, call_site_value, function);
+        Ok(call_site_value.try_as_basic_value().left())
+    }
+
+    ///
+    /// Links the internal function definitions of `source`, e.g. the runtime code module, into
+    /// the current module.
+    ///
+    /// Allows translating the functions shared by the deploy and runtime code only once, which
+    /// also keeps their bodies identical in both code segments. Only the functions with the
+    /// internal or private linkage are linked, so the ones unused by the current module are
+    /// removed by the unreachable function pruning during the build. The other definitions of
+    /// `source`, including the entry function, are dropped, and must not be called by the
+    /// linked functions.
+    ///
+    /// The linked functions are not registered in the context, as their return values have
+    /// already been lowered. Returns their declarations to be called with `build_call`, or an
+    /// error if a function is defined in both modules.
+    ///
+    pub fn link_shared_functions(
+        &mut self,
+        source: &inkwell::module::Module<'ctx>,
+    ) -> anyhow::Result<Vec<FunctionDeclaration<'ctx>>> {
+        let shared = source.clone();
+
+        let mut names = Vec::new();
+        for function in shared.get_functions().collect::<Vec<_>>() {
+            if function.count_basic_blocks() == 0 {
+                continue;
+            }
+            let name = function.get_name().to_string_lossy().to_string();
+            if !matches!(
+                function.get_linkage(),
+                inkwell::module::Linkage::Internal | inkwell::module::Linkage::Private
+            ) {
+                if function.get_first_use().is_some() {
+                    anyhow::bail!(
+                        "Shared functions must not call the non-internal function `{name}`"
+                    );
+                }
+                unsafe { function.delete() };
+                continue;
+            }
+            if self
+                .module
+                .get_function(name.as_str())
+                .is_some_and(|function| function.count_basic_blocks() != 0)
+            {
+                anyhow::bail!("Function `{name}` is defined in both modules");
+            }
+            names.push(name);
+        }
+
+        self.module
+            .link_in_module(shared)
+            .map_err(|error| anyhow::anyhow!("Shared functions linking error: {error}"))?;
+
+        names
+            .into_iter()
+            .map(|name| {
+                let value = self
+                    .module
+                    .get_function(name.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Shared function `{name}` is not linked"))?;
+                Ok(FunctionDeclaration::new(value.get_type(), value))
+            })
+            .collect()
+    }
+
+    ///
+    /// Sets whether the target supports the unwinding.
+    ///
+    /// If it does, `build_invoke` emits invokes whose cleanup landing pads resume the unwinding in
+    /// the caller, and the personality function is declared at the first invoke. Otherwise, it
+    /// falls back to plain calls.
+    ///
+    /// Returns an error if any function has already been declared, so all the calls of a module
+    /// are lowered in the same way.
+    ///
+    pub fn set_unwinding_supported(&mut self, is_supported: bool) -> anyhow::Result<()> {
+        if !self.functions.is_empty() {
+            anyhow::bail!("The unwinding support must be set before any function is declared");
+        }
+        self.is_unwinding_supported = is_supported;
+        Ok(())
+    }
+
+    ///
+    /// Whether the target supports the unwinding.
+    ///
+    pub fn is_unwinding_supported(&self) -> bool {
+        self.is_unwinding_supported
+    }
+
+    ///
+    /// Returns the personality function, declaring it if it is not declared yet.
+    ///
+    fn personality(&self) -> FunctionDeclaration<'ctx> {
+        let r#type = self.llvm.i32_type().fn_type(&[], false);
+        let value = self
+            .module
+            .get_function(Self::FUNCTION_PERSONALITY)
+            .unwrap_or_else(|| {
+                self.module
+                    .add_function(Self::FUNCTION_PERSONALITY, r#type, None)
+            });
+        FunctionDeclaration::new(r#type, value)
+    }
+
     ///
     /// Returns the LLVM intrinsics collection reference.
     ///
@@ -190,6 +454,19 @@ where
         &self.intrinsics
     }
 
+    ///
+    /// Returns the EVM legacy assembly block builder of the current function.
+    ///
+    /// The built data must be set with `set_evmla_data` of the current function.
+    ///
+    pub fn evmla_function_builder(&self, stack_size: usize) -> EVMLAFunctionBuilder<'ctx> {
+        EVMLAFunctionBuilder::new(
+            self.llvm,
+            self.current_function().borrow().declaration().value,
+            stack_size,
+        )
+    }
+
     ///
     /// Gets a full contract_path from the dependency manager.
     ///
@@ -203,6 +480,16 @@ where
             })
     }
 
+    ///
+    /// Gets the full contract paths of `identifiers` from the dependency manager at once.
+    ///
+    pub fn resolve_paths(&self, identifiers: &[&str]) -> anyhow::Result<BTreeMap<String, String>> {
+        self.dependency_manager
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The dependency manager is unset"))
+            .and_then(|manager| manager.resolve_paths(identifiers))
+    }
+
     ///
     /// Returns a Yul function type with the specified arguments and number of return values.
     ///
@@ -355,7 +642,7 @@ where
 
     type EVMLAData = EVMLAData<'ctx>;
 
-    type VyperData = ();
+    type VyperData = VyperData;
 
     fn llvm(&self) -> &'ctx inkwell::context::Context {
         self.llvm
@@ -373,10 +660,31 @@ where
         &self.debug_info
     }
 
+    fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics.as_slice()
+    }
+
+    fn push_diagnostic(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn optimizer(&self) -> &Optimizer {
+        &self.optimizer
+    }
+
     fn debug_config(&self) -> Option<&DebugConfig> {
         self.debug_config.as_ref()
     }
 
+    fn intern_constant_bytes_in(
+        &mut self,
+        bytes: &[u8],
+        address_space: Self::AddressSpace,
+    ) -> Pointer<'ctx, Self::AddressSpace> {
+        self.constant_pool
+            .intern(self.llvm, &self.module, bytes, address_space)
+    }
+
     fn set_code_segment(&mut self, code_segment: era_compiler_common::CodeSegment) {
         self.code_segment = code_segment;
     }
@@ -476,9 +784,10 @@ where
 
     fn set_current_function(&mut self, name: &str) -> anyhow::Result<()> {
         let function = self.functions.get(name).cloned().ok_or_else(|| {
-            anyhow::anyhow!("Failed to activate an undeclared function `{}`", name)
+            suggestion::undeclared("function", name, self.functions.keys().map(String::as_str))
         })?;
         self.current_function = Some(function);
+        self.builder.unset_current_debug_location();
         Ok(())
     }
 
@@ -502,14 +811,22 @@ where
         arguments: &[inkwell::values::BasicMetadataValueEnum<'ctx>],
         name: &str,
     ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
-        let call_site_value = self.builder.build_indirect_call(
-            function.r#type,
-            function.value.as_global_value().as_pointer_value(),
-            arguments,
-            name,
-        )?;
-        self.modify_call_site_value(arguments, call_site_value, function);
-        Ok(call_site_value.try_as_basic_value().left())
+        self.build_call_site(function, arguments, &[], name)
+    }
+
+    fn build_call_with_attributes(
+        &self,
+        function: FunctionDeclaration<'ctx>,
+        arguments: &[inkwell::values::BasicValueEnum<'ctx>],
+        attributes: &[Attribute],
+        name: &str,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
+        let arguments: Vec<inkwell::values::BasicMetadataValueEnum> = arguments
+            .iter()
+            .copied()
+            .map(inkwell::values::BasicMetadataValueEnum::from)
+            .collect();
+        self.build_call_site(function, arguments.as_slice(), attributes, name)
     }
 
     fn build_invoke(
@@ -518,7 +835,93 @@ where
         arguments: &[inkwell::values::BasicValueEnum<'ctx>],
         name: &str,
     ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
-        Self::build_call(self, function, arguments, name)
+        if !self.is_unwinding_supported {
+            return Self::build_call(self, function, arguments, name);
+        }
+        let personality = self.personality();
+        self.current_function()
+            .borrow()
+            .declaration()
+            .value
+            .set_personality_function(personality.value);
+
+        let return_pointer = if let Some(r#type) = function.r#type.get_return_type() {
+            let pointer = self.build_alloca(r#type, "invoke_return_pointer")?;
+            self.build_store(pointer, r#type.const_zero())?;
+            Some(pointer)
+        } else {
+            None
+        };
+
+        let success_block = self.append_basic_block("invoke_success_block");
+        let catch_block = self.append_basic_block("invoke_catch_block");
+        let current_block = self.basic_block();
+
+        self.set_basic_block(catch_block);
+        let landing_pad_type = self.structure_type(&[
+            self.ptr_type(AddressSpace::Stack.into())
+                .as_basic_type_enum(),
+            self.integer_type(era_compiler_common::BIT_LENGTH_X32)
+                .as_basic_type_enum(),
+        ]);
+        let landing_pad = self.builder.build_landing_pad(
+            landing_pad_type,
+            personality.value,
+            &[],
+            true,
+            "invoke_catch_landing",
+        )?;
+        self.builder.build_resume(landing_pad)?;
+
+        self.set_basic_block(current_block);
+        let call_site_value = self.builder.build_indirect_invoke(
+            function.r#type,
+            function.value.as_global_value().as_pointer_value(),
+            arguments,
+            success_block,
+            catch_block,
+            name,
+        )?;
+        self.modify_call_site_value(
+            arguments
+                .iter()
+                .copied()
+                .map(inkwell::values::BasicMetadataValueEnum::from)
+                .collect::<Vec<_>>()
+                .as_slice(),
+            call_site_value,
+            function,
+        );
+
+        self.set_basic_block(success_block);
+        if let (Some(return_pointer), Some(return_value)) =
+            (return_pointer, call_site_value.try_as_basic_value().left())
+        {
+            self.build_store(return_pointer, return_value)?;
+        }
+        match return_pointer {
+            Some(pointer) => self.build_load(pointer, "invoke_result").map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn build_empty_revert(&self) -> anyhow::Result<()> {
+        let offset_pointer = Pointer::new_with_offset(
+            self,
+            AddressSpace::Heap,
+            self.byte_type(),
+            self.field_const(0),
+            "empty_revert_offset_pointer",
+        )?;
+        self.build_call(
+            self.intrinsics().revert,
+            &[
+                offset_pointer.as_basic_value_enum(),
+                self.field_const(0).as_basic_value_enum(),
+            ],
+            "empty_revert",
+        )?;
+        self.build_unreachable()
     }
 
     fn set_solidity_data(&mut self, _data: Self::SolidityData) {
@@ -557,15 +960,15 @@ where
         self.evmla_data.as_mut()
     }
 
-    fn set_vyper_data(&mut self, _data: Self::VyperData) {
-        panic!("Unused with the EVM target");
+    fn set_vyper_data(&mut self, data: Self::VyperData) {
+        self.vyper_data = Some(data);
     }
 
     fn vyper(&self) -> Option<&Self::VyperData> {
-        panic!("Unused with the EVM target");
+        self.vyper_data.as_ref()
     }
 
     fn vyper_mut(&mut self) -> Option<&mut Self::VyperData> {
-        panic!("Unused with the EVM target");
+        self.vyper_data.as_mut()
     }
 }
@@ -0,0 +1,525 @@
+//!
+//! The LLVM IR generator context tests.
+//!
+
+use std::sync::Arc;
+
+use inkwell::values::BasicValue;
+
+use crate::cancellation_handle::CancellationHandle;
+use crate::context::attribute::Attribute;
+use crate::context::function::declaration::Declaration as FunctionDeclaration;
+use crate::context::pointer::Pointer;
+use crate::context::IContext;
+use crate::dependency::DummyDependency;
+use crate::error::Error;
+use crate::evm::context::address_space::AddressSpace;
+use crate::evm::context::function::runtime::minimal_deploy_code::MinimalDeployCode;
+use crate::evm::context::vyper_data::VyperData;
+use crate::evm::context::Context;
+use crate::evm::WriteLLVM;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::optimizer::stage::Stage;
+use crate::optimizer::Optimizer;
+use crate::testing::file_check::FileCheck;
+
+#[test]
+pub fn check_evm_vyper_data() {
+    crate::evm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let module = llvm.create_module("test");
+    let mut context = Context::<DummyDependency>::new(
+        &llvm,
+        module,
+        vec![],
+        era_compiler_common::CodeSegment::Runtime,
+        Optimizer::new(OptimizerSettings::none()),
+        None,
+        None,
+    );
+    assert!(context.vyper().is_none());
+    context.set_vyper_data(VyperData::new(64, false));
+    context
+        .vyper_mut()
+        .expect("Always exists")
+        .set_is_minimal_proxy_used();
+    let vyper = context.vyper().expect("Always exists");
+    assert_eq!(vyper.immutables_size(), 64);
+    assert!(vyper.is_minimal_proxy_used());
+
+    let code = VyperData::minimal_proxy_code(&[0x11; 20]);
+    assert_eq!(code.len(), 45);
+    assert_eq!(&code[10..30], [0x11; 20].as_slice());
+}
+
+#[test]
+pub fn check_evm_minimal_deploy_code() {
+    crate::evm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let module = llvm.create_module("Test");
+    let mut context = Context::<DummyDependency>::new(
+        &llvm,
+        module,
+        vec![],
+        era_compiler_common::CodeSegment::Deploy,
+        Optimizer::new(OptimizerSettings::none()),
+        None,
+        None,
+    );
+
+    let mut deploy_code = MinimalDeployCode::new("Test".to_owned(), "Test_deployed".to_owned())
+        .with_constructor_arguments();
+    WriteLLVM::declare(&mut deploy_code, &mut context).expect("Always valid");
+    WriteLLVM::into_llvm(deploy_code, &mut context).expect("Always valid");
+    context.verify().expect("Always valid");
+}
+
+#[test]
+pub fn check_verbatim() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function = crate::testing::add_function(&mut context, "evm_verbatim", 1, 1)
+        .expect("Failed to add function");
+    let outputs = crate::evm::instructions::verbatim::verbatim(
+        &mut context,
+        &[0x60, 0x01],
+        vec![function
+            .get_nth_param(0)
+            .expect("Always exists")
+            .into_int_value()],
+        1,
+        None,
+    )
+    .expect("Failed to translate");
+    assert_eq!(outputs.len(), 1);
+    let file_check = FileCheck::new()
+        .check(r#"call i256 asm sideeffect ".byte 0x60, 0x01", "=r,r"(i256 %0)"#)
+        .expect("Always valid");
+    crate::testing::check_function(&context, "evm_verbatim", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_allocate_heap() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function = crate::testing::add_function(&mut context, "allocate", 1, 1)
+        .expect("Failed to add function");
+    let offset = context
+        .allocate_heap(
+            function
+                .get_nth_param(0)
+                .expect("Always exists")
+                .into_int_value(),
+        )
+        .expect("Failed to allocate");
+    context
+        .build_return(Some(&offset))
+        .expect("Failed to build return");
+
+    let file_check = FileCheck::new()
+        .check("%heap_allocation_offset = load i256")
+        .and_then(|file_check| {
+            file_check.check("@llvm.uadd.with.overflow.i256(i256 %heap_allocation_offset, i256 %0)")
+        })
+        .and_then(|file_check| {
+            file_check
+                .check("icmp ugt i256 %heap_allocation_offset_new_value, 18446744073709551615")
+        })
+        .and_then(|file_check| file_check.check("heap_allocation_revert_block:"))
+        .and_then(|file_check| file_check.check("unreachable"))
+        .and_then(|file_check| file_check.check("heap_allocation_join_block:"))
+        .and_then(|file_check| {
+            file_check.check_next("store i256 %heap_allocation_offset_new_value")
+        })
+        .and_then(|file_check| file_check.check_next("ret i256 %heap_allocation_offset"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "allocate", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_constant_pool() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let pointer = context.intern_constant_bytes(&[0xde, 0xad]);
+    assert_eq!(pointer.address_space, AddressSpace::Code);
+    let file_check = FileCheck::new()
+        .check(
+            r#"@constant_bytes_0 = private unnamed_addr addrspace(4) constant [2 x i8] c"\DE\AD""#,
+        )
+        .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_emit_event() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_function(&mut context, "evm_emit_event", 0, 0)
+        .expect("Failed to add function");
+    let data_pointer = Pointer::new_with_offset(
+        &context,
+        AddressSpace::Calldata,
+        context.byte_type(),
+        context.field_const(4),
+        "event_data_pointer",
+    )
+    .expect("Always valid");
+    crate::evm::instructions::event::emit_event(
+        &mut context,
+        vec![context.field_const(1), context.field_const(2)],
+        data_pointer,
+        context.field_const(64),
+    )
+    .expect("Failed to emit");
+    let file_check = FileCheck::new()
+        .check("heap_allocation_join_block")
+        .and_then(|file_check| file_check.check("call void @llvm.memcpy.p1.p2.i256"))
+        .and_then(|file_check| file_check.check("call void @llvm.evm.log2"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "evm_emit_event", &file_check).expect("Must match");
+    let error = crate::evm::instructions::event::emit_event(
+        &mut context,
+        vec![context.field_const(0); crate::r#const::EVENT_TOPICS_LIMIT + 1],
+        data_pointer,
+        context.field_const(64),
+    )
+    .expect_err("Must be rejected");
+    assert!(error.to_string().contains("found 5"), "{error}");
+}
+
+#[test]
+pub fn check_evm_invoke_unwinding() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    assert!(!context.is_unwinding_supported());
+    crate::testing::add_function(&mut context, "callee", 1, 1).expect("Failed to add function");
+    crate::testing::add_function(&mut context, "caller", 0, 0).expect("Failed to add function");
+    let error = context
+        .set_unwinding_supported(true)
+        .expect_err("Must be rejected");
+    assert!(
+        error
+            .to_string()
+            .contains("before any function is declared"),
+        "{error}"
+    );
+    let callee = context
+        .get_function("callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+    context
+        .build_invoke(
+            callee,
+            &[context.field_const(1).as_basic_value_enum()],
+            "call",
+        )
+        .expect("Failed to build call");
+    assert!(context
+        .module()
+        .get_function(Context::<DummyDependency>::FUNCTION_PERSONALITY)
+        .is_none());
+    let file_check = FileCheck::new()
+        .check_not("personality")
+        .and_then(|file_check| file_check.check("call i256 @callee(i256 1)"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "caller", &file_check).expect("Must match");
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    context
+        .set_unwinding_supported(true)
+        .expect("Failed to enable the unwinding");
+    assert!(context.is_unwinding_supported());
+    assert!(context
+        .module()
+        .get_function(Context::<DummyDependency>::FUNCTION_PERSONALITY)
+        .is_none());
+    crate::testing::add_function(&mut context, "callee", 1, 1).expect("Failed to add function");
+    crate::testing::add_function(&mut context, "caller", 0, 0).expect("Failed to add function");
+    let callee = context
+        .get_function("callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+    context
+        .build_invoke(
+            callee,
+            &[context.field_const(2).as_basic_value_enum()],
+            "invoke",
+        )
+        .expect("Failed to build invoke");
+
+    let file_check = FileCheck::new()
+        .check("personality ptr @__personality")
+        .expect("Always valid")
+        .check("invoke i256 @callee(i256 2)")
+        .expect("Always valid")
+        .check("landingpad")
+        .expect("Always valid")
+        .check_next("cleanup")
+        .expect("Always valid")
+        .check_next("resume")
+        .expect("Always valid");
+    crate::testing::check_function(&context, "caller", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_evm_shared_functions() {
+    let llvm = inkwell::context::Context::create();
+    let mut runtime = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_function(&mut runtime, "shared", 1, 1)
+        .expect("Failed to add function")
+        .set_linkage(inkwell::module::Linkage::Private);
+    crate::testing::add_function(&mut runtime, "external", 0, 0).expect("Failed to add function");
+    crate::testing::add_function(&mut runtime, crate::evm_const::ENTRY_FUNCTION_NAME, 0, 0)
+        .expect("Failed to add function");
+
+    let mut deploy = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Deploy,
+        OptimizerSettings::none(),
+    );
+    let declarations = deploy
+        .link_shared_functions(runtime.module())
+        .expect("Failed to link shared functions");
+    assert_eq!(declarations.len(), 1);
+    let shared = declarations[0];
+    assert_eq!(shared.value.get_name().to_str(), Ok("shared"));
+    assert!(deploy.get_function("shared").is_none());
+    for name in ["external", crate::evm_const::ENTRY_FUNCTION_NAME] {
+        assert!(deploy.module().get_function(name).is_none());
+    }
+
+    crate::testing::add_function(&mut deploy, "deploy", 0, 0).expect("Failed to add function");
+    deploy
+        .build_call(
+            shared,
+            &[deploy.field_const(42).as_basic_value_enum()],
+            "shared_call",
+        )
+        .expect("Failed to build call");
+
+    let error = deploy
+        .link_shared_functions(runtime.module())
+        .expect_err("Must be rejected");
+    assert!(
+        error.to_string().contains("defined in both modules"),
+        "{error}"
+    );
+
+    let file_check = FileCheck::new()
+        .check("define private i256 @shared(i256")
+        .expect("Always valid")
+        .check("define void @deploy()")
+        .expect("Always valid")
+        .check("call i256 @shared(i256 42)")
+        .expect("Always valid");
+    crate::testing::check_module(&deploy, &file_check).expect("Must match");
+
+    let external = runtime
+        .module()
+        .get_function("external")
+        .expect("Always exists");
+    runtime
+        .set_current_function("shared")
+        .expect("Always exists");
+    runtime.set_basic_block(
+        runtime
+            .module()
+            .get_function("shared")
+            .expect("Always exists")
+            .get_first_basic_block()
+            .expect("Always exists"),
+    );
+    runtime
+        .build_call(
+            FunctionDeclaration::new(external.get_type(), external),
+            &[],
+            "external_call",
+        )
+        .expect("Failed to build call");
+    let mut deploy = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Deploy,
+        OptimizerSettings::none(),
+    );
+    let error = deploy
+        .link_shared_functions(runtime.module())
+        .expect_err("Must be rejected");
+    assert!(
+        error
+            .to_string()
+            .contains("must not call the non-internal function `external`"),
+        "{error}"
+    );
+}
+
+#[test]
+pub fn check_call_site_attributes() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_function(&mut context, "callee", 1, 1).expect("Failed to add function");
+    crate::testing::add_function(&mut context, "caller", 0, 0).expect("Failed to add function");
+    let callee = context
+        .get_function("callee")
+        .expect("Always exists")
+        .borrow()
+        .declaration();
+
+    let error = context
+        .build_call_with_attributes(
+            callee,
+            &[context.field_const(0).as_basic_value_enum()],
+            &[Attribute::AlwaysInline, Attribute::NoInline],
+            "conflicting",
+        )
+        .expect_err("Must be rejected");
+    assert!(
+        error.to_string().contains("both always and never inlined"),
+        "{error}"
+    );
+
+    context
+        .build_call_with_attributes(
+            callee,
+            &[context.field_const(1).as_basic_value_enum()],
+            &[Attribute::AlwaysInline],
+            "inlined",
+        )
+        .expect("Failed to build call");
+    context
+        .build_call_with_attributes(
+            callee,
+            &[context.field_const(2).as_basic_value_enum()],
+            &[Attribute::Cold, Attribute::NoInline],
+            "outlined",
+        )
+        .expect("Failed to build call");
+
+    let file_check = FileCheck::new()
+        .check("call i256 @callee(i256 1) #")
+        .expect("Always valid")
+        .check("call i256 @callee(i256 2) #")
+        .expect("Always valid")
+        .check("attributes #{{.*}} = { alwaysinline }")
+        .expect("Always valid")
+        .check("attributes #{{.*}} = { cold noinline }")
+        .expect("Always valid");
+    crate::testing::check_module(&context, &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_evm_build_cancellation() {
+    crate::evm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let create_context = |optimizer: Optimizer| {
+        let mut context = Context::<DummyDependency>::new(
+            &llvm,
+            llvm.create_module("Test"),
+            vec![],
+            era_compiler_common::CodeSegment::Deploy,
+            optimizer,
+            None,
+            None,
+        );
+        let mut deploy_code = MinimalDeployCode::new("Test".to_owned(), "Test_deployed".to_owned());
+        WriteLLVM::declare(&mut deploy_code, &mut context).expect("Always valid");
+        WriteLLVM::into_llvm(deploy_code, &mut context).expect("Always valid");
+        context
+    };
+    let is_cancelled_before = |error: &anyhow::Error, expected: &str| {
+        matches!(
+            error.downcast_ref::<Error>(),
+            Some(Error::Cancelled { stage }) if stage == expected
+        )
+    };
+
+    let cancellation_handle = CancellationHandle::new();
+    cancellation_handle.cancel();
+    let error = create_context(Optimizer::new(OptimizerSettings::cycles()))
+        .build("Test", Some(&cancellation_handle))
+        .expect_err("Must be cancelled");
+    assert!(is_cancelled_before(&error, "verification"), "{error}");
+
+    let cancellation_handle = CancellationHandle::new();
+    let mut optimizer = Optimizer::new(OptimizerSettings::cycles());
+    let callback_handle = cancellation_handle.clone();
+    optimizer.register_callback(
+        "cancel",
+        Stage::PostOptimization,
+        Arc::new(move |_module| {
+            callback_handle.cancel();
+            Ok(())
+        }),
+    );
+    let error = create_context(optimizer)
+        .build("Test", Some(&cancellation_handle))
+        .expect_err("Must be cancelled");
+    assert!(is_cancelled_before(&error, "emitting"), "{error}");
+}
+
+#[test]
+pub fn check_evm_check_only() {
+    crate::evm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let mut context = Context::<DummyDependency>::new(
+        &llvm,
+        llvm.create_module("Test"),
+        vec![],
+        era_compiler_common::CodeSegment::Deploy,
+        Optimizer::new(OptimizerSettings::cycles()),
+        None,
+        None,
+    );
+    let mut deploy_code = MinimalDeployCode::new("Test".to_owned(), "Test_deployed".to_owned());
+    WriteLLVM::declare(&mut deploy_code, &mut context).expect("Always valid");
+    WriteLLVM::into_llvm(deploy_code, &mut context).expect("Always valid");
+    context.check("Test").expect("Must be valid");
+
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    let error = context
+        .check("Test")
+        .expect_err("The unterminated function must be rejected");
+    match error.downcast_ref::<Error>() {
+        Some(Error::Verification(message)) => assert!(
+            message.contains("code unoptimized LLVM IR verification: "),
+            "{message}"
+        ),
+        _ => panic!("Unexpected error: {error}"),
+    }
+}
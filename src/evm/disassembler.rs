@@ -0,0 +1,200 @@
+//!
+//! The EVM bytecode disassembler.
+//!
+
+use std::fmt::Write;
+
+///
+/// Returns the annotated disassembly of `bytecode`.
+///
+/// Each instruction is printed on its own line with its offset and immediate. The basic blocks
+/// are split the same way as in `gas_estimate::estimate`, and each block is preceded by a label
+/// with its offset. The jump destinations are marked with a comment.
+///
+/// The data appended to the code, such as the metadata, is decoded as instructions as well.
+///
+pub fn disassemble(bytecode: &[u8]) -> String {
+    let mut text = String::with_capacity(bytecode.len() * 16);
+    let mut is_block_started = false;
+
+    let mut offset = 0;
+    while offset < bytecode.len() {
+        let opcode = bytecode[offset];
+        if !is_block_started || opcode == 0x5b {
+            if is_block_started {
+                text.push('\n');
+            }
+            writeln!(text, "block_{offset:04x}:").expect("Always valid");
+        }
+        is_block_started = true;
+
+        write!(text, "    {offset:04x}: ").expect("Always valid");
+        match mnemonic(opcode) {
+            Some(mnemonic) => text.push_str(mnemonic),
+            None => write!(text, "INVALID(0x{opcode:02x})").expect("Always valid"),
+        }
+
+        offset += 1;
+        if (0x60..=0x7f).contains(&opcode) {
+            let size = (opcode - 0x5f) as usize;
+            let immediate = &bytecode[offset..bytecode.len().min(offset + size)];
+            text.push_str(" 0x");
+            for byte in immediate.iter() {
+                write!(text, "{byte:02x}").expect("Always valid");
+            }
+            if immediate.len() < size {
+                text.push_str(" ; truncated");
+            }
+            offset += size;
+        }
+        if opcode == 0x5b {
+            text.push_str(" ; jumpdest");
+        }
+        text.push('\n');
+
+        if matches!(opcode, 0x00 | 0x56 | 0x57 | 0xf3 | 0xfd | 0xfe | 0xff) {
+            is_block_started = false;
+            if offset < bytecode.len() {
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+///
+/// Returns the mnemonic of `opcode`, or `None` if the opcode is not defined.
+///
+pub fn mnemonic(opcode: u8) -> Option<&'static str> {
+    const PUSH: [&str; 32] = [
+        "PUSH1", "PUSH2", "PUSH3", "PUSH4", "PUSH5", "PUSH6", "PUSH7", "PUSH8", "PUSH9", "PUSH10",
+        "PUSH11", "PUSH12", "PUSH13", "PUSH14", "PUSH15", "PUSH16", "PUSH17", "PUSH18", "PUSH19",
+        "PUSH20", "PUSH21", "PUSH22", "PUSH23", "PUSH24", "PUSH25", "PUSH26", "PUSH27", "PUSH28",
+        "PUSH29", "PUSH30", "PUSH31", "PUSH32",
+    ];
+    const DUP: [&str; 16] = [
+        "DUP1", "DUP2", "DUP3", "DUP4", "DUP5", "DUP6", "DUP7", "DUP8", "DUP9", "DUP10", "DUP11",
+        "DUP12", "DUP13", "DUP14", "DUP15", "DUP16",
+    ];
+    const SWAP: [&str; 16] = [
+        "SWAP1", "SWAP2", "SWAP3", "SWAP4", "SWAP5", "SWAP6", "SWAP7", "SWAP8", "SWAP9", "SWAP10",
+        "SWAP11", "SWAP12", "SWAP13", "SWAP14", "SWAP15", "SWAP16",
+    ];
+    const LOG: [&str; 5] = ["LOG0", "LOG1", "LOG2", "LOG3", "LOG4"];
+
+    Some(match opcode {
+        0x00 => "STOP",
+        0x01 => "ADD",
+        0x02 => "MUL",
+        0x03 => "SUB",
+        0x04 => "DIV",
+        0x05 => "SDIV",
+        0x06 => "MOD",
+        0x07 => "SMOD",
+        0x08 => "ADDMOD",
+        0x09 => "MULMOD",
+        0x0a => "EXP",
+        0x0b => "SIGNEXTEND",
+        0x10 => "LT",
+        0x11 => "GT",
+        0x12 => "SLT",
+        0x13 => "SGT",
+        0x14 => "EQ",
+        0x15 => "ISZERO",
+        0x16 => "AND",
+        0x17 => "OR",
+        0x18 => "XOR",
+        0x19 => "NOT",
+        0x1a => "BYTE",
+        0x1b => "SHL",
+        0x1c => "SHR",
+        0x1d => "SAR",
+        0x20 => "KECCAK256",
+        0x30 => "ADDRESS",
+        0x31 => "BALANCE",
+        0x32 => "ORIGIN",
+        0x33 => "CALLER",
+        0x34 => "CALLVALUE",
+        0x35 => "CALLDATALOAD",
+        0x36 => "CALLDATASIZE",
+        0x37 => "CALLDATACOPY",
+        0x38 => "CODESIZE",
+        0x39 => "CODECOPY",
+        0x3a => "GASPRICE",
+        0x3b => "EXTCODESIZE",
+        0x3c => "EXTCODECOPY",
+        0x3d => "RETURNDATASIZE",
+        0x3e => "RETURNDATACOPY",
+        0x3f => "EXTCODEHASH",
+        0x40 => "BLOCKHASH",
+        0x41 => "COINBASE",
+        0x42 => "TIMESTAMP",
+        0x43 => "NUMBER",
+        0x44 => "PREVRANDAO",
+        0x45 => "GASLIMIT",
+        0x46 => "CHAINID",
+        0x47 => "SELFBALANCE",
+        0x48 => "BASEFEE",
+        0x49 => "BLOBHASH",
+        0x4a => "BLOBBASEFEE",
+        0x50 => "POP",
+        0x51 => "MLOAD",
+        0x52 => "MSTORE",
+        0x53 => "MSTORE8",
+        0x54 => "SLOAD",
+        0x55 => "SSTORE",
+        0x56 => "JUMP",
+        0x57 => "JUMPI",
+        0x58 => "PC",
+        0x59 => "MSIZE",
+        0x5a => "GAS",
+        0x5b => "JUMPDEST",
+        0x5c => "TLOAD",
+        0x5d => "TSTORE",
+        0x5e => "MCOPY",
+        0x5f => "PUSH0",
+        0x60..=0x7f => PUSH[(opcode - 0x60) as usize],
+        0x80..=0x8f => DUP[(opcode - 0x80) as usize],
+        0x90..=0x9f => SWAP[(opcode - 0x90) as usize],
+        0xa0..=0xa4 => LOG[(opcode - 0xa0) as usize],
+        0xf0 => "CREATE",
+        0xf1 => "CALL",
+        0xf2 => "CALLCODE",
+        0xf3 => "RETURN",
+        0xf4 => "DELEGATECALL",
+        0xf5 => "CREATE2",
+        0xfa => "STATICCALL",
+        0xfd => "REVERT",
+        0xfe => "INVALID",
+        0xff => "SELFDESTRUCT",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    pub fn check_evm_disassemble() {
+        // PUSH1 0x04 JUMP JUMPDEST PUSH0 STOP PUSH2 0xab(truncated)
+        let bytecode = [0x60, 0x04, 0x56, 0x5b, 0x5f, 0x00, 0x61, 0xab];
+        let text = super::disassemble(bytecode.as_slice());
+        assert_eq!(
+            text,
+            [
+                "block_0000:",
+                "    0000: PUSH1 0x04",
+                "    0002: JUMP",
+                "",
+                "block_0003:",
+                "    0003: JUMPDEST ; jumpdest",
+                "    0004: PUSH0",
+                "    0005: STOP",
+                "",
+                "block_0006:",
+                "    0006: PUSH2 0xab ; truncated",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
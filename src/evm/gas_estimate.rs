@@ -0,0 +1,138 @@
+//!
+//! The EVM static gas estimation.
+//!
+
+use std::collections::BTreeMap;
+
+///
+/// Returns the static gas estimates of the basic blocks of `bytecode`, keyed by the block offset.
+///
+/// The blocks start at the beginning of the code, at each `JUMPDEST`, and after each jump and
+/// terminating instruction. The estimates only include the static costs with the warm storage
+/// and account access, so the memory expansion, copying, and cold access costs are not counted.
+///
+/// The data appended to the code, such as the metadata, is decoded as instructions as well.
+///
+pub fn estimate(bytecode: &[u8]) -> BTreeMap<usize, u64> {
+    let mut estimates = BTreeMap::new();
+    let mut block_offset = 0;
+    let mut block_gas = 0;
+    let mut is_block_started = false;
+
+    let mut offset = 0;
+    while offset < bytecode.len() {
+        let opcode = bytecode[offset];
+        if opcode == 0x5b && is_block_started {
+            estimates.insert(block_offset, block_gas);
+            block_offset = offset;
+            block_gas = 0;
+        }
+        is_block_started = true;
+        block_gas += static_gas(opcode);
+
+        offset += 1;
+        if (0x60..=0x7f).contains(&opcode) {
+            offset += (opcode - 0x5f) as usize;
+        }
+
+        if matches!(opcode, 0x00 | 0x56 | 0x57 | 0xf3 | 0xfd | 0xfe | 0xff) {
+            estimates.insert(block_offset, block_gas);
+            block_offset = offset;
+            block_gas = 0;
+            is_block_started = false;
+        }
+    }
+    if is_block_started {
+        estimates.insert(block_offset, block_gas);
+    }
+    estimates
+}
+
+///
+/// Returns the static gas cost of `opcode`, assuming the warm storage and account access.
+///
+/// The unknown opcodes are free, as they abort the execution.
+///
+pub fn static_gas(opcode: u8) -> u64 {
+    match opcode {
+        0x00 | 0xf3 | 0xfd | 0xfe => 0,
+        0x5b => 1,
+        0x30
+        | 0x32..=0x34
+        | 0x36
+        | 0x38
+        | 0x3a
+        | 0x3d
+        | 0x41..=0x46
+        | 0x48
+        | 0x4a
+        | 0x50
+        | 0x58..=0x5a
+        | 0x5f => 2,
+        0x01
+        | 0x03
+        | 0x10..=0x1d
+        | 0x35
+        | 0x37
+        | 0x39
+        | 0x3e
+        | 0x49
+        | 0x51..=0x53
+        | 0x5e
+        | 0x60..=0x9f => 3,
+        0x02 | 0x04..=0x07 | 0x0b | 0x47 => 5,
+        0x08 | 0x09 | 0x56 => 8,
+        0x0a | 0x57 => 10,
+        0x40 => 20,
+        0x20 => 30,
+        0x31 | 0x3b | 0x3c | 0x3f | 0x54 | 0x55 | 0x5c | 0x5d | 0xf1 | 0xf2 | 0xf4 | 0xfa => 100,
+        0xa0..=0xa4 => 375 * (1 + (opcode - 0xa0) as u64),
+        0xff => 5000,
+        0xf0 | 0xf5 => 32000,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::evm::context::build::Build;
+
+    #[test]
+    pub fn check_evm_gas_estimate() {
+        // PUSH1 0x04 JUMP JUMPDEST(unreachable) JUMPDEST PUSH0 PUSH0 RETURN
+        let bytecode = [0x60, 0x04, 0x56, 0x5b, 0x5b, 0x5f, 0x5f, 0xf3];
+        let estimates = super::estimate(bytecode.as_slice());
+        assert_eq!(
+            estimates.into_iter().collect::<Vec<(usize, u64)>>(),
+            vec![(0, 11), (3, 1), (4, 5)]
+        );
+    }
+
+    #[test]
+    pub fn check_evm_gas_estimate_push_data() {
+        // PUSH2 0x5b5b JUMPI SLOAD STOP
+        let bytecode = [0x61, 0x5b, 0x5b, 0x57, 0x54, 0x00];
+        let estimates = super::estimate(bytecode.as_slice());
+        assert_eq!(
+            estimates.into_iter().collect::<Vec<(usize, u64)>>(),
+            vec![(0, 13), (4, 100)]
+        );
+
+        // PUSH32 truncated by the end of the code
+        let estimates = super::estimate(&[0x7f, 0x01]);
+        assert_eq!(
+            estimates.into_iter().collect::<Vec<(usize, u64)>>(),
+            vec![(0, 3)]
+        );
+    }
+
+    #[test]
+    pub fn check_evm_build_gas_estimates() {
+        let mut build = Build::new(vec![0x5f, 0x5f, 0xf3], None);
+        assert!(build.gas_estimates.is_none());
+        build.estimate_gas();
+        assert_eq!(build.gas_estimates, Some(BTreeMap::from([(0, 4)])));
+    }
+}
@@ -153,6 +153,39 @@ where
         .expect("Always exists"))
 }
 
+///
+/// Translates the `blobhash` instruction.
+///
+pub fn blob_hash<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    index: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    Ok(context
+        .build_call(
+            context.intrinsics().blobhash,
+            &[index.as_basic_value_enum()],
+            "blobhash",
+        )?
+        .expect("Always exists"))
+}
+
+///
+/// Translates the `blobbasefee` instruction.
+///
+pub fn blob_base_fee<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+) -> anyhow::Result<inkwell::values::BasicValueEnum<'ctx>>
+where
+    D: Dependency,
+{
+    Ok(context
+        .build_call(context.intrinsics().blobbasefee, &[], "blobbasefee")?
+        .expect("Always exists"))
+}
+
 ///
 /// Translates the `msize` instruction.
 ///
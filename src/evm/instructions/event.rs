@@ -85,3 +85,60 @@ where
     }?;
     Ok(())
 }
+
+///
+/// Emits an event with `topics` and the ABI-encoded data at `data_pointer`.
+///
+/// The data outside of the heap is staged into a newly allocated heap range first, since the
+/// `log` instructions only accept heap ranges.
+///
+/// Returns an error if there are more than `EVENT_TOPICS_LIMIT` topics or the data cannot be
+/// copied to the heap from its address space.
+///
+pub fn emit_event<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    topics: Vec<inkwell::values::IntValue<'ctx>>,
+    data_pointer: Pointer<'ctx, AddressSpace>,
+    data_length: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    if topics.len() > crate::r#const::EVENT_TOPICS_LIMIT {
+        anyhow::bail!(
+            "The number of event topics must be from 0 to {}, found {}",
+            crate::r#const::EVENT_TOPICS_LIMIT,
+            topics.len(),
+        );
+    }
+
+    let copy_function = match data_pointer.address_space {
+        AddressSpace::Heap => {
+            let data_offset = data_pointer.to_int(context, "event_data_offset")?;
+            return log(context, data_offset, data_length, topics);
+        }
+        AddressSpace::Calldata => context.intrinsics().memory_copy_from_calldata,
+        AddressSpace::ReturnData => context.intrinsics().memory_copy_from_return_data,
+        AddressSpace::Code => context.intrinsics().memory_copy_from_code,
+        address_space => anyhow::bail!(
+            "Event data in the address space {address_space:?} cannot be staged to the heap"
+        ),
+    };
+
+    let data_offset = context.allocate_heap(data_length)?;
+    let destination = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        data_offset,
+        "event_data_staging_pointer",
+    )?;
+    context.build_memcpy(
+        copy_function,
+        destination,
+        data_pointer,
+        data_length,
+        "event_data_staging_memcpy",
+    )?;
+    log(context, data_offset, data_length, topics)
+}
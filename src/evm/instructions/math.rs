@@ -103,6 +103,10 @@ where
 ///
 /// Translates the `keccak256` instruction.
 ///
+/// If `input_length` is a compile-time zero, the result is folded to the hash of the empty input.
+/// Other constant inputs are folded only if the hash constant folding is enabled, and the memory
+/// range has been written with constants in the current basic block.
+///
 pub fn keccak256<'ctx, D>(
     context: &mut Context<'ctx, D>,
     input_offset: inkwell::values::IntValue<'ctx>,
@@ -111,6 +115,16 @@ pub fn keccak256<'ctx, D>(
 where
     D: Dependency,
 {
+    if input_length.get_zero_extended_constant() == Some(0) {
+        let hash = crate::const_hash::keccak256(&[]);
+        return Ok(context
+            .field_const_big(&num::BigUint::from_bytes_be(hash.as_slice()))?
+            .as_basic_value_enum());
+    }
+    if let Some(hash) = context.fold_constant_hash(AddressSpace::Heap, input_offset, input_length) {
+        return Ok(hash.as_basic_value_enum());
+    }
+
     let input_offset_pointer = Pointer::new_with_offset(
         context,
         AddressSpace::Heap,
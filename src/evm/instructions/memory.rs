@@ -85,3 +85,43 @@ where
     )?;
     Ok(())
 }
+
+///
+/// Translates the `mcopy` instruction.
+///
+/// Uses the main heap. The source and destination ranges may overlap.
+///
+pub fn copy<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    destination_offset: inkwell::values::IntValue<'ctx>,
+    source_offset: inkwell::values::IntValue<'ctx>,
+    size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let destination = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        destination_offset,
+        "mcopy_destination_pointer",
+    )?;
+
+    let source = Pointer::new_with_offset(
+        context,
+        AddressSpace::Heap,
+        context.byte_type(),
+        source_offset,
+        "mcopy_source_pointer",
+    )?;
+
+    context.build_memcpy(
+        context.intrinsics().memory_move_heap,
+        destination,
+        source,
+        size,
+        "mcopy_memmove",
+    )?;
+    Ok(())
+}
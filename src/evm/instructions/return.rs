@@ -72,6 +72,26 @@ where
     Ok(())
 }
 
+///
+/// Reverts with the return data of the last call, bubbling up its error.
+///
+/// The return data is copied to the heap starting from zero, which is safe to overwrite since
+/// the execution ends here.
+///
+pub fn revert_forward_return_data<D>(context: &mut Context<D>) -> anyhow::Result<()>
+where
+    D: Dependency,
+{
+    let size = crate::evm::instructions::return_data::size(context)?.into_int_value();
+    crate::evm::instructions::return_data::copy(
+        context,
+        context.field_const(0),
+        context.field_const(0),
+        size,
+    )?;
+    revert(context, context.field_const(0), size)
+}
+
 ///
 /// Translates the `stop` instruction.
 ///
@@ -0,0 +1,124 @@
+//!
+//! The EVM instructions tests.
+//!
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::testing::file_check::FileCheck;
+
+#[test]
+pub fn check_transient_storage() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function = crate::testing::add_function(&mut context, "transient", 2, 0)
+        .expect("Failed to add function");
+    let position = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    let value = function
+        .get_nth_param(1)
+        .expect("Always exists")
+        .into_int_value();
+    super::storage::transient_load(&mut context, position).expect("Failed to translate");
+    super::storage::transient_store(&mut context, position, value).expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %transient_storage_load_position_pointer = inttoptr i256 %0 to ptr addrspace(6)
+; CHECK-NEXT: %transient_storage_load_value = load i256, ptr addrspace(6) %transient_storage_load_position_pointer
+; CHECK-NEXT: %transient_storage_store_position_pointer = inttoptr i256 %0 to ptr addrspace(6)
+; CHECK-NEXT: store i256 %1, ptr addrspace(6) %transient_storage_store_position_pointer
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "transient", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_memory_copy() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function =
+        crate::testing::add_function(&mut context, "mcopy", 3, 0).expect("Failed to add function");
+    let operand = |index| {
+        function
+            .get_nth_param(index)
+            .expect("Always exists")
+            .into_int_value()
+    };
+    super::memory::copy(&mut context, operand(0), operand(1), operand(2))
+        .expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %mcopy_destination_pointer = inttoptr i256 %0 to ptr addrspace(1)
+; CHECK-NEXT: %mcopy_source_pointer = inttoptr i256 %1 to ptr addrspace(1)
+; CHECK-NEXT: call void @llvm.memmove.p1.p1.i256(ptr addrspace(1) {{.*}}%mcopy_destination_pointer, ptr addrspace(1) {{.*}}%mcopy_source_pointer, i256 %2, i1 false)
+; CHECK-NOT: @llvm.memcpy
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "mcopy", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_blob_context() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function =
+        crate::testing::add_function(&mut context, "blob", 1, 0).expect("Failed to add function");
+    let index = function
+        .get_nth_param(0)
+        .expect("Always exists")
+        .into_int_value();
+    super::context::blob_hash(&mut context, index).expect("Failed to translate");
+    super::context::blob_base_fee(&mut context).expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %blobhash = call i256 @llvm.evm.blobhash(i256 %0)
+; CHECK-NEXT: %blobbasefee = call i256 @llvm.evm.blobbasefee()
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "blob", &file_check).expect("Must match");
+}
+
+#[test]
+pub fn check_revert_forward_return_data() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_function(&mut context, "bubble", 0, 0).expect("Failed to add function");
+    super::r#return::revert_forward_return_data(&mut context).expect("Failed to translate");
+
+    let file_check = FileCheck::parse(
+        r#"
+; CHECK: %returndatasize = call i256 @llvm.evm.returndatasize()
+; CHECK-NEXT: call void @llvm.memcpy.p1.p3.i256(ptr addrspace(1) {{.*}}, ptr addrspace(3) {{.*}}, i256 %returndatasize, i1 false)
+; CHECK-NEXT: call void @llvm.evm.revert(ptr addrspace(1) {{.*}}, i256 %returndatasize)
+; CHECK-NEXT: unreachable
+"#,
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid");
+    crate::testing::check_function(&context, "bubble", &file_check).expect("Must match");
+}
@@ -0,0 +1,94 @@
+//!
+//! Translates the Yul `verbatim` instructions.
+//!
+
+use crate::context::IContext;
+use crate::diagnostic::span::Span;
+use crate::evm::context::Context;
+use crate::evm::Dependency;
+
+///
+/// Translates the `verbatim_<N>i_<M>o` instruction.
+///
+/// The raw `data` bytes are emitted in place as an inline assembly `.byte` directive.
+/// The `input_values` are passed as the inline assembly operands in order, and the `output_size`
+/// values it produces are returned in order.
+///
+/// The `span` is only used by the EraVM counterpart, where the instruction is unsupported.
+///
+pub fn verbatim<'ctx, D>(
+    context: &mut Context<'ctx, D>,
+    data: &[u8],
+    input_values: Vec<inkwell::values::IntValue<'ctx>>,
+    output_size: usize,
+    _span: Option<Span>,
+) -> anyhow::Result<Vec<inkwell::values::IntValue<'ctx>>>
+where
+    D: Dependency,
+{
+    if data.is_empty() {
+        anyhow::bail!("verbatim data must not be empty");
+    }
+
+    let argument_types = vec![context.field_type().into(); input_values.len()];
+    let function_type = match output_size {
+        0 => context
+            .void_type()
+            .fn_type(argument_types.as_slice(), false),
+        1 => context
+            .field_type()
+            .fn_type(argument_types.as_slice(), false),
+        size => context
+            .structure_type(vec![context.field_type(); size].as_slice())
+            .fn_type(argument_types.as_slice(), false),
+    };
+    let assembly = format!(
+        ".byte {}",
+        data.iter()
+            .map(|byte| format!("0x{byte:02x}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let constraints = std::iter::repeat("=r")
+        .take(output_size)
+        .chain(std::iter::repeat("r").take(input_values.len()))
+        .collect::<Vec<&str>>()
+        .join(",");
+    let inline_assembly = context.llvm().create_inline_asm(
+        function_type,
+        assembly,
+        constraints,
+        true,
+        false,
+        None,
+        false,
+    );
+
+    let arguments: Vec<inkwell::values::BasicMetadataValueEnum<'ctx>> =
+        input_values.into_iter().map(Into::into).collect();
+    let call_site_value = context.builder().build_indirect_call(
+        function_type,
+        inline_assembly,
+        arguments.as_slice(),
+        "verbatim",
+    )?;
+
+    let result = call_site_value.try_as_basic_value().left();
+    match (output_size, result) {
+        (0, _) => Ok(vec![]),
+        (1, Some(value)) => Ok(vec![value.into_int_value()]),
+        (size, Some(value)) => (0..size)
+            .map(|index| {
+                Ok(context
+                    .builder()
+                    .build_extract_value(
+                        value.into_struct_value(),
+                        index as u32,
+                        format!("verbatim_output_{index}").as_str(),
+                    )?
+                    .into_int_value())
+            })
+            .collect(),
+        (_, None) => anyhow::bail!("verbatim inline assembly returned no value"),
+    }
+}
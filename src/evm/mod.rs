@@ -4,9 +4,17 @@
 
 pub mod r#const;
 pub mod context;
+pub mod disassembler;
+pub mod gas_estimate;
 pub mod instructions;
 
+use std::collections::BTreeMap;
+
 use crate::dependency::Dependency;
+use crate::evm::context::build::Build;
+use crate::metadata::MetadataBuilder;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::undefined_references::UndefinedReferences;
 
 use self::context::Context;
 
@@ -17,6 +25,63 @@ pub fn initialize_target() {
     inkwell::targets::Target::initialize_evm(&inkwell::targets::InitializationConfig::default());
 }
 
+///
+/// Disassembles `bytecode` into the annotated text.
+///
+/// See `disassembler::disassemble` for the output format.
+///
+pub fn disassemble(bytecode: &[u8]) -> String {
+    self::disassembler::disassemble(bytecode)
+}
+
+///
+/// Returns the linker symbols `bytecode_buffer` still references.
+///
+/// EVM objects do not reference factory dependencies by placeholders, so only linker symbols
+/// are reported.
+///
+pub fn get_undefined_references(
+    bytecode_buffer: &inkwell::memory_buffer::MemoryBuffer,
+) -> UndefinedReferences {
+    UndefinedReferences::new(bytecode_buffer.get_undefined_references_evm(), vec![])
+}
+
+///
+/// Converts the linked `bytecode_buffer` into a build, appending the metadata.
+///
+/// The metadata must only be appended to the linked bytecode, as the unlinked object is not
+/// valid bytecode.
+///
+pub fn build(
+    bytecode_buffer: inkwell::memory_buffer::MemoryBuffer,
+    metadata: &MetadataBuilder,
+) -> Build {
+    let metadata_hash = metadata.build();
+    let mut bytecode = bytecode_buffer.as_slice().to_vec();
+    if let Some(ref metadata) = metadata_hash {
+        bytecode.extend_from_slice(metadata.as_slice());
+    }
+    Build::new(bytecode, metadata_hash)
+}
+
+///
+/// Builds multiple contracts in parallel.
+///
+/// Each call of `build` runs on a worker thread and must create its own LLVM context.
+///
+pub fn build_many<I, F>(
+    inputs: BTreeMap<String, I>,
+    optimizer_settings: &OptimizerSettings,
+    threads: Option<usize>,
+    build: F,
+) -> BTreeMap<String, anyhow::Result<Build>>
+where
+    I: Send,
+    F: Fn(&str, I, &OptimizerSettings) -> anyhow::Result<Build> + Sync,
+{
+    crate::parallel::build_many(inputs, optimizer_settings, threads, build)
+}
+
 ///
 /// Implemented by items which are translated into LLVM IR.
 ///
@@ -5,26 +5,50 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::upper_case_acronyms)]
 
+pub(crate) mod artifact_bundle;
+pub(crate) mod cancellation_handle;
 pub(crate) mod r#const;
+pub(crate) mod const_hash;
 pub(crate) mod context;
 pub(crate) mod debug_config;
 pub(crate) mod debug_info;
 pub(crate) mod dependency;
+pub(crate) mod diagnostic;
 pub(crate) mod eravm;
+pub(crate) mod error;
 pub(crate) mod evm;
+pub(crate) mod llvm_options;
+pub(crate) mod metadata;
 pub(crate) mod optimizer;
+pub(crate) mod parallel;
+pub(crate) mod profiler;
+pub(crate) mod selector_check;
+pub(crate) mod size_fallback;
+pub(crate) mod source_map;
 pub(crate) mod target_machine;
+pub(crate) mod target_machine_pool;
+pub mod testing;
+pub(crate) mod undefined_references;
 
+pub use self::artifact_bundle::ArtifactBundle;
+pub use self::cancellation_handle::CancellationHandle;
+pub use self::const_hash::keccak256 as const_keccak256;
+pub use self::const_hash::sha256 as const_sha256;
 pub use self::context::attribute::memory::Memory as MemoryAttribute;
 pub use self::context::attribute::Attribute;
+pub use self::context::const_operand::ConstOperand;
+pub use self::context::constant_pool::ConstantPool;
 pub use self::context::function::block::evmla_data::EVMLAData as FunctionBlockEVMLAData;
 pub use self::context::function::block::key::Key as BlockKey;
 pub use self::context::function::block::Block as FunctionBlock;
 pub use self::context::function::declaration::Declaration as FunctionDeclaration;
+pub use self::context::function::evmla_builder::EVMLAFunctionBuilder;
 pub use self::context::function::evmla_data::EVMLAData as FunctionEVMLAData;
 pub use self::context::function::r#return::Return as FunctionReturn;
 pub use self::context::pointer::Pointer;
 pub use self::context::r#loop::Loop;
+pub use self::context::return_data_copy_policy::ReturnDataCopyPolicy;
+pub use self::context::selector_dispatch::SelectorDispatch;
 pub use self::context::traits::address_space::IAddressSpace;
 pub use self::context::traits::evmla_data::IEVMLAData;
 pub use self::context::traits::evmla_function::IEVMLAFunction;
@@ -35,10 +59,23 @@ pub use self::debug_config::DebugConfig;
 pub use self::debug_info::DebugInfo;
 pub use self::dependency::Dependency;
 pub use self::dependency::DummyDependency;
+pub use self::diagnostic::severity::Severity as DiagnosticSeverity;
+pub use self::diagnostic::span::Span as DiagnosticSpan;
+pub use self::diagnostic::Diagnostic;
 pub use self::eravm::assemble as eravm_assemble;
+pub use self::eravm::assembly::instruction::Instruction as EraVMAssemblyInstruction;
+pub use self::eravm::assembly::line::Line as EraVMAssemblyLine;
+pub use self::eravm::assembly::section::Section as EraVMAssemblySection;
+pub use self::eravm::assembly::Assembly as EraVMAssembly;
 pub use self::eravm::build as eravm_build;
+pub use self::eravm::build_many as eravm_build_many;
+pub use self::eravm::context::active_pointer::ActivePointer as EraVMActivePointer;
 pub use self::eravm::context::address_space::AddressSpace as EraVMAddressSpace;
+pub use self::eravm::context::assembly_hook::AssemblyHook as EraVMAssemblyHook;
 pub use self::eravm::context::build::Build as EraVMBuild;
+pub use self::eravm::context::build_cache::BuildCache as EraVMBuildCache;
+pub use self::eravm::context::emit_request::EmitRequest as EraVMEmitRequest;
+pub use self::eravm::context::entry_abi::EntryAbi as EraVMEntryAbi;
 pub use self::eravm::context::evmla_data::EVMLAData as EraVMContextEVMLAData;
 pub use self::eravm::context::function::intrinsics::Intrinsics as EraVMIntrinsicFunction;
 pub use self::eravm::context::function::llvm_runtime::LLVMRuntime as EraVMLLVMRuntime;
@@ -50,11 +87,25 @@ pub use self::eravm::context::function::vyper_data::VyperData as EraVMFunctionVy
 pub use self::eravm::context::function::yul_data::YulData as EraVMFunctionYulData;
 pub use self::eravm::context::function::Function as EraVMFunction;
 pub use self::eravm::context::global::Global as EraVMGlobal;
+pub use self::eravm::context::immutables_layout::ImmutablesLayout as EraVMImmutablesLayout;
+pub use self::eravm::context::landing_pad::LandingPad as EraVMLandingPad;
+pub use self::eravm::context::metrics::Metrics as EraVMBuildMetrics;
+pub use self::eravm::context::optimized_module::OptimizedModule as EraVMOptimizedModule;
+pub use self::eravm::context::selfdestruct_policy::SelfdestructPolicy as EraVMSelfdestructPolicy;
+pub use self::eravm::context::simulation_registry::arguments::Arguments as EraVMSimulationArguments;
+pub use self::eravm::context::simulation_registry::Callback as EraVMSimulationCallback;
+pub use self::eravm::context::simulation_registry::SimulationRegistry as EraVMSimulationRegistry;
 pub use self::eravm::context::solidity_data::SolidityData as EraVMContextSolidityData;
 pub use self::eravm::context::vyper_data::VyperData as EraVMContextVyperData;
 pub use self::eravm::context::yul_data::YulData as EraVMContextYulData;
 pub use self::eravm::context::Context as EraVMContext;
+pub use self::eravm::dependency_graph::DependencyGraph as EraVMDependencyGraph;
 pub use self::eravm::disassemble as eravm_disassemble;
+pub use self::eravm::disassembler as eravm_disassembler;
+pub use self::eravm::disassembler::immediate_format::ImmediateFormat as EraVMDisassemblerImmediateFormat;
+pub use self::eravm::disassembler::instruction::Instruction as EraVMDisassembledInstruction;
+pub use self::eravm::disassembler::options::Options as EraVMDisassemblerOptions;
+pub use self::eravm::ergs_estimate as eravm_ergs_estimate;
 pub use self::eravm::evm::arithmetic as eravm_evm_arithmetic;
 pub use self::eravm::evm::bitwise as eravm_evm_bitwise;
 pub use self::eravm::evm::call as eravm_evm_call;
@@ -72,24 +123,41 @@ pub use self::eravm::evm::memory as eravm_evm_memory;
 pub use self::eravm::evm::r#return as eravm_evm_return;
 pub use self::eravm::evm::return_data as eravm_evm_return_data;
 pub use self::eravm::evm::storage as eravm_evm_storage;
+pub use self::eravm::extensions::aa as eravm_aa;
 pub use self::eravm::extensions::abi as eravm_abi;
 pub use self::eravm::extensions::call as eravm_call;
+pub use self::eravm::extensions::code as eravm_code;
 pub use self::eravm::extensions::general as eravm_general;
 pub use self::eravm::extensions::math as eravm_math;
+pub use self::eravm::extensions::simulation::call_type::CallType as EraVMSimulationCallType;
+pub use self::eravm::extensions::simulation::Simulation as EraVMSimulation;
+pub use self::eravm::get_undefined_references as eravm_get_undefined_references;
 pub use self::eravm::hash as eravm_hash;
 pub use self::eravm::link as eravm_link;
+pub use self::eravm::link_incremental as eravm_link_incremental;
+pub use self::eravm::link_report::LinkReport as EraVMLinkReport;
 pub use self::eravm::r#const as eravm_const;
+pub use self::eravm::reconcile_link as eravm_reconcile_link;
 pub use self::eravm::utils as eravm_utils;
 pub use self::eravm::DummyLLVMWritable as EraVMDummyLLVMWritable;
 pub use self::eravm::WriteLLVM as EraVMWriteLLVM;
+pub use self::error::Error;
+pub use self::evm::build as evm_build;
+pub use self::evm::build_many as evm_build_many;
 pub use self::evm::context::address_space::AddressSpace as EVMAddressSpace;
 pub use self::evm::context::build::Build as EVMBuild;
 pub use self::evm::context::evmla_data::EVMLAData as EVMContextEVMLAData;
 pub use self::evm::context::function::intrinsics::Intrinsics as EVMIntrinsicFunction;
 pub use self::evm::context::function::runtime::entry::Entry as EVMEntryFunction;
+pub use self::evm::context::function::runtime::minimal_deploy_code::MinimalDeployCode as EVMMinimalDeployCode;
 pub use self::evm::context::function::vyper_data::VyperData as EVMFunctionVyperData;
 pub use self::evm::context::function::Function as EVMFunction;
+pub use self::evm::context::vyper_data::VyperData as EVMContextVyperData;
 pub use self::evm::context::Context as EVMContext;
+pub use self::evm::disassemble as evm_disassemble;
+pub use self::evm::disassembler as evm_disassembler;
+pub use self::evm::gas_estimate as evm_gas_estimate;
+pub use self::evm::get_undefined_references as evm_get_undefined_references;
 pub use self::evm::instructions::arithmetic as evm_arithmetic;
 pub use self::evm::instructions::bitwise as evm_bitwise;
 pub use self::evm::instructions::call as evm_call;
@@ -109,11 +177,34 @@ pub use self::evm::instructions::storage as evm_storage;
 pub use self::evm::r#const as evm_const;
 pub use self::evm::DummyLLVMWritable as EVMDummyLLVMWritable;
 pub use self::evm::WriteLLVM as EVMWriteLLVM;
+pub use self::llvm_options::supported_option::SupportedOption as LLVMSupportedOption;
+pub use self::llvm_options::LLVMOptions;
+pub use self::metadata::cbor_value::CborValue as MetadataCborValue;
+pub use self::metadata::MetadataBuilder;
+pub use self::optimizer::callback::Callback as OptimizerCallback;
+pub use self::optimizer::callback::CallbackFunction as OptimizerCallbackFunction;
+pub use self::optimizer::fallback_policy::FallbackFunction as OptimizerFallbackFunction;
+pub use self::optimizer::fallback_policy::FallbackPolicy as OptimizerFallbackPolicy;
 pub use self::optimizer::settings::size_level::SizeLevel as OptimizerSettingsSizeLevel;
 pub use self::optimizer::settings::Settings as OptimizerSettings;
+pub use self::optimizer::stage::Stage as OptimizerStage;
 pub use self::optimizer::Optimizer;
+pub use self::profiler::run::Run as ProfilerRun;
+pub use self::profiler::Profiler;
 pub use self::r#const::*;
+pub use self::selector_check::SelectorCheck;
+pub use self::size_fallback::attempt::Attempt as SizeFallbackAttempt;
+pub use self::size_fallback::SizeFallback;
+pub use self::source_map::entry::Entry as SourceMapEntry;
+pub use self::source_map::SourceMap;
+pub use self::target_machine::builder::Builder as TargetMachineBuilder;
+pub use self::target_machine::code_model::CodeModel as TargetCodeModel;
+pub use self::target_machine::options::Options as TargetOptions;
+pub use self::target_machine::reloc_mode::RelocMode as TargetRelocMode;
 pub use self::target_machine::TargetMachine;
+pub use self::target_machine_pool::pooled::Pooled as PooledTargetMachine;
+pub use self::target_machine_pool::TargetMachinePool;
+pub use self::undefined_references::UndefinedReferences;
 
 ///
 /// Initializes the target machine.
@@ -0,0 +1,141 @@
+//!
+//! The LLVM options.
+//!
+
+pub mod supported_option;
+
+#[cfg(test)]
+mod tests;
+
+use self::supported_option::SupportedOption;
+
+///
+/// The LLVM options.
+///
+/// The target-specific options, prefixed with `-eravm-` or `-evm-`, are validated, whereas the
+/// generic LLVM options are passed through as is.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LLVMOptions {
+    /// Whether the CSE of the EraVM `sha3` system requests is disabled.
+    pub is_sha3_sreq_cse_disabled: bool,
+    /// The EraVM jump table density threshold.
+    pub jump_table_density_threshold: Option<u32>,
+    /// The generic LLVM options.
+    pub other: Vec<String>,
+}
+
+impl LLVMOptions {
+    /// The EraVM `sha3` system request CSE disabling option.
+    pub const ERAVM_DISABLE_SHA3_SREQ_CSE: &'static str = "-eravm-disable-sha3-sreq-cse";
+
+    /// The EraVM jump table density threshold option.
+    pub const ERAVM_JUMP_TABLE_DENSITY_THRESHOLD: &'static str =
+        "-eravm-jump-table-density-threshold";
+
+    /// The EraVM target-specific option prefix.
+    const ERAVM_PREFIX: &'static str = "-eravm-";
+
+    /// The EVM target-specific option prefix.
+    const EVM_PREFIX: &'static str = "-evm-";
+
+    ///
+    /// Parses and validates the LLVM options for `target`.
+    ///
+    /// The option values can be passed either as `-option=value` or as the next argument.
+    ///
+    pub fn try_from_arguments(
+        target: era_compiler_common::Target,
+        arguments: &[String],
+    ) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+
+        let mut arguments = arguments.iter();
+        while let Some(argument) = arguments.next() {
+            let (name, value) = match argument.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (argument.as_str(), None),
+            };
+            if !name.starts_with(Self::ERAVM_PREFIX) && !name.starts_with(Self::EVM_PREFIX) {
+                options.other.push(argument.to_owned());
+                continue;
+            }
+
+            let option = Self::supported_options(target)
+                .iter()
+                .find(|option| option.name == name)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "LLVM option `{name}` is not supported by target `{target}`. Supported options:\n{}",
+                        Self::supported_options(target)
+                            .iter()
+                            .map(|option| option.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                    )
+                })?;
+            let value = match (option.value, value) {
+                (Some(_), Some(value)) => Some(value),
+                (Some(placeholder), None) => Some(arguments.next().cloned().ok_or_else(|| {
+                    anyhow::anyhow!("LLVM option `{name}` requires a value <{placeholder}>")
+                })?),
+                (None, Some(_)) => anyhow::bail!("LLVM option `{name}` does not take a value"),
+                (None, None) => None,
+            };
+
+            match (name, value) {
+                (Self::ERAVM_DISABLE_SHA3_SREQ_CSE, _) => options.is_sha3_sreq_cse_disabled = true,
+                (Self::ERAVM_JUMP_TABLE_DENSITY_THRESHOLD, Some(value)) => {
+                    options.jump_table_density_threshold =
+                        Some(value.parse::<u32>().map_err(|error| {
+                            anyhow::anyhow!(
+                                "LLVM option `{name}` value `{value}` is invalid: {error}"
+                            )
+                        })?);
+                }
+                _ => unreachable!("All supported options are handled above"),
+            }
+        }
+
+        Ok(options)
+    }
+
+    ///
+    /// Returns the target-specific options supported for `target`.
+    ///
+    pub fn supported_options(target: era_compiler_common::Target) -> &'static [SupportedOption] {
+        match target {
+            era_compiler_common::Target::EraVM => &[
+                SupportedOption::new(
+                    Self::ERAVM_DISABLE_SHA3_SREQ_CSE,
+                    None,
+                    "Disables the CSE of the `sha3` system requests.",
+                ),
+                SupportedOption::new(
+                    Self::ERAVM_JUMP_TABLE_DENSITY_THRESHOLD,
+                    Some("value"),
+                    "Sets the minimal density of the switch cases to lower it to a jump table.",
+                ),
+            ],
+            era_compiler_common::Target::EVM => &[],
+        }
+    }
+
+    ///
+    /// Returns the command line arguments to pass to LLVM.
+    ///
+    pub fn to_arguments(&self) -> Vec<String> {
+        let mut arguments = Vec::with_capacity(2 + self.other.len());
+        if self.is_sha3_sreq_cse_disabled {
+            arguments.push(Self::ERAVM_DISABLE_SHA3_SREQ_CSE.to_owned());
+        }
+        if let Some(threshold) = self.jump_table_density_threshold {
+            arguments.push(format!(
+                "{}={threshold}",
+                Self::ERAVM_JUMP_TABLE_DENSITY_THRESHOLD
+            ));
+        }
+        arguments.extend(self.other.iter().cloned());
+        arguments
+    }
+}
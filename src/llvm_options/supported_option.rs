@@ -0,0 +1,43 @@
+//!
+//! The supported LLVM option.
+//!
+
+///
+/// The supported LLVM option.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedOption {
+    /// The option name, including the leading dash.
+    pub name: &'static str,
+    /// The value placeholder, if the option takes a value.
+    pub value: Option<&'static str>,
+    /// The option description.
+    pub description: &'static str,
+}
+
+impl SupportedOption {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub const fn new(
+        name: &'static str,
+        value: Option<&'static str>,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            value,
+            description,
+        }
+    }
+}
+
+impl std::fmt::Display for SupportedOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{} <{value}>", self.name)?,
+            None => write!(f, "{}", self.name)?,
+        }
+        write!(f, "    {}", self.description)
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! The LLVM options tests.
+//!
+
+use crate::llvm_options::LLVMOptions;
+
+#[test]
+pub fn check_llvm_options() {
+    let arguments = vec![
+        "-eravm-jump-table-density-threshold".to_owned(),
+        "20".to_owned(),
+        "-eravm-disable-sha3-sreq-cse".to_owned(),
+        "-debug-only=isel".to_owned(),
+    ];
+    let options =
+        LLVMOptions::try_from_arguments(era_compiler_common::Target::EraVM, arguments.as_slice())
+            .expect("Failed to parse the options");
+    assert!(options.is_sha3_sreq_cse_disabled);
+    assert_eq!(options.jump_table_density_threshold, Some(20));
+    assert_eq!(
+        options.to_arguments(),
+        vec![
+            "-eravm-disable-sha3-sreq-cse".to_owned(),
+            "-eravm-jump-table-density-threshold=20".to_owned(),
+            "-debug-only=isel".to_owned(),
+        ]
+    );
+
+    assert!(LLVMOptions::try_from_arguments(
+        era_compiler_common::Target::EraVM,
+        &["-eravm-unknown".to_owned()],
+    )
+    .is_err());
+    assert!(LLVMOptions::try_from_arguments(
+        era_compiler_common::Target::EraVM,
+        &["-eravm-jump-table-density-threshold".to_owned()],
+    )
+    .is_err());
+}
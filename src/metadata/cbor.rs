@@ -0,0 +1,66 @@
+//!
+//! The minimal CBOR encoder of the contract metadata.
+//!
+
+/// The CBOR unsigned integer major type.
+pub const MAJOR_TYPE_UNSIGNED: u8 = 0;
+
+/// The CBOR byte string major type.
+pub const MAJOR_TYPE_BYTES: u8 = 2;
+
+/// The CBOR text string major type.
+pub const MAJOR_TYPE_TEXT: u8 = 3;
+
+/// The CBOR array major type.
+pub const MAJOR_TYPE_ARRAY: u8 = 4;
+
+/// The CBOR map major type.
+pub const MAJOR_TYPE_MAP: u8 = 5;
+
+/// The CBOR `false` simple value.
+pub const SIMPLE_FALSE: u8 = 0xf4;
+
+/// The CBOR `true` simple value.
+pub const SIMPLE_TRUE: u8 = 0xf5;
+
+///
+/// Writes the header of a CBOR item of `major_type` with `length` or value to `buffer`.
+///
+pub fn write_header(buffer: &mut Vec<u8>, major_type: u8, length: u64) {
+    let major_type = major_type << 5;
+    match length {
+        length @ 0..=23 => buffer.push(major_type | length as u8),
+        length @ 24..=0xff => {
+            buffer.push(major_type | 24);
+            buffer.push(length as u8);
+        }
+        length @ 0x100..=0xffff => {
+            buffer.push(major_type | 25);
+            buffer.extend_from_slice((length as u16).to_be_bytes().as_slice());
+        }
+        length @ 0x10000..=0xffffffff => {
+            buffer.push(major_type | 26);
+            buffer.extend_from_slice((length as u32).to_be_bytes().as_slice());
+        }
+        length => {
+            buffer.push(major_type | 27);
+            buffer.extend_from_slice(length.to_be_bytes().as_slice());
+        }
+    }
+}
+
+///
+/// Writes a CBOR byte string to `buffer`.
+///
+pub fn write_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    write_header(buffer, MAJOR_TYPE_BYTES, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+///
+/// Writes a CBOR text string to `buffer`.
+///
+pub fn write_text(buffer: &mut Vec<u8>, text: &str) {
+    write_header(buffer, MAJOR_TYPE_TEXT, text.len() as u64);
+    buffer.extend_from_slice(text.as_bytes());
+}
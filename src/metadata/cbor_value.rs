@@ -0,0 +1,55 @@
+//!
+//! The contract metadata CBOR value.
+//!
+
+use super::cbor;
+
+///
+/// The contract metadata CBOR value.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum CborValue {
+    /// The unsigned integer.
+    Unsigned(u64),
+    /// The byte string.
+    Bytes(Vec<u8>),
+    /// The text string.
+    Text(String),
+    /// The array.
+    Array(Vec<CborValue>),
+    /// The map with text keys.
+    Map(Vec<(String, CborValue)>),
+    /// The boolean.
+    Bool(bool),
+}
+
+impl CborValue {
+    ///
+    /// Writes the CBOR encoding of the value to `buffer`.
+    ///
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Unsigned(value) => cbor::write_header(buffer, cbor::MAJOR_TYPE_UNSIGNED, *value),
+            Self::Bytes(bytes) => cbor::write_bytes(buffer, bytes.as_slice()),
+            Self::Text(text) => cbor::write_text(buffer, text.as_str()),
+            Self::Array(values) => {
+                cbor::write_header(buffer, cbor::MAJOR_TYPE_ARRAY, values.len() as u64);
+                for value in values.iter() {
+                    value.write(buffer);
+                }
+            }
+            Self::Map(entries) => {
+                cbor::write_header(buffer, cbor::MAJOR_TYPE_MAP, entries.len() as u64);
+                for (key, value) in entries.iter() {
+                    cbor::write_text(buffer, key.as_str());
+                    value.write(buffer);
+                }
+            }
+            Self::Bool(value) => buffer.push(if *value {
+                cbor::SIMPLE_TRUE
+            } else {
+                cbor::SIMPLE_FALSE
+            }),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//!
+//! The contract metadata builder.
+//!
+
+pub mod cbor;
+pub mod cbor_value;
+
+#[cfg(test)]
+mod tests;
+
+use self::cbor_value::CborValue;
+
+///
+/// The contract metadata builder.
+///
+/// Packs the metadata appended to the bytecode of both EraVM and EVM contracts:
+/// - without a hash and compiler versions, no metadata is appended
+/// - with a keccak256 hash only, the raw hash is appended
+/// - with an IPFS hash only, the CBOR-encoded hash is appended
+/// - with additional entries, a CBOR map of the hash and entries is appended, followed by its
+///   length as a big-endian 2-byte integer, like in `solc`
+///
+#[derive(Debug, Default, Clone)]
+pub struct MetadataBuilder {
+    /// The metadata hash, whose variant defines the hash type.
+    hash: Option<era_compiler_common::Hash>,
+    /// The additional CBOR entries in the order of insertion, e.g. the compiler versions.
+    entries: Vec<(String, CborValue)>,
+}
+
+impl MetadataBuilder {
+    /// The CBOR key of the keccak256 hash.
+    pub const KEY_KECCAK256: &'static str = "keccak256";
+
+    /// The CBOR key of the IPFS hash.
+    pub const KEY_IPFS: &'static str = "ipfs";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(hash: Option<era_compiler_common::Hash>) -> Self {
+        Self {
+            hash,
+            entries: vec![],
+        }
+    }
+
+    ///
+    /// A shortcut constructor for the mode where no metadata is appended.
+    ///
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Adds a compiler version to the CBOR metadata.
+    ///
+    pub fn with_version(self, name: &str, version: semver::Version) -> Self {
+        self.with_entry(name, CborValue::Text(version.to_string()))
+    }
+
+    ///
+    /// Adds an arbitrary entry to the CBOR metadata, e.g. an experimental flag or a source hash.
+    ///
+    /// The entries are encoded after the hash in the order of insertion. The keys are not
+    /// deduplicated, so a key must not be equal to another one or the hash key.
+    ///
+    pub fn with_entry(mut self, key: &str, value: CborValue) -> Self {
+        self.entries.push((key.to_owned(), value));
+        self
+    }
+
+    ///
+    /// Returns the metadata hash.
+    ///
+    pub fn hash(&self) -> Option<&era_compiler_common::Hash> {
+        self.hash.as_ref()
+    }
+
+    ///
+    /// Packs the metadata, or returns `None` if no metadata is appended.
+    ///
+    pub fn build(&self) -> Option<Vec<u8>> {
+        if self.entries.is_empty() {
+            return self.hash.as_ref().map(|hash| match hash {
+                era_compiler_common::Hash::Keccak256 { bytes, .. } => bytes.to_vec(),
+                hash @ era_compiler_common::Hash::Ipfs { .. } => hash.as_cbor_bytes(),
+            });
+        }
+
+        let hash_entry = self.hash.as_ref().map(|hash| match hash {
+            era_compiler_common::Hash::Keccak256 { bytes, .. } => {
+                (Self::KEY_KECCAK256, bytes.as_slice())
+            }
+            era_compiler_common::Hash::Ipfs { bytes, .. } => (Self::KEY_IPFS, bytes.as_slice()),
+        });
+
+        let mut metadata = Vec::with_capacity(era_compiler_common::BYTE_LENGTH_FIELD * 4);
+        cbor::write_header(
+            &mut metadata,
+            cbor::MAJOR_TYPE_MAP,
+            (self.entries.len() + hash_entry.iter().count()) as u64,
+        );
+        if let Some((key, bytes)) = hash_entry {
+            cbor::write_text(&mut metadata, key);
+            cbor::write_bytes(&mut metadata, bytes);
+        }
+        for (key, value) in self.entries.iter() {
+            cbor::write_text(&mut metadata, key.as_str());
+            value.write(&mut metadata);
+        }
+        let length = metadata.len() as u16;
+        metadata.extend_from_slice(length.to_be_bytes().as_slice());
+        Some(metadata)
+    }
+
+    ///
+    /// Returns the size of the packed metadata in bytes.
+    ///
+    pub fn size(&self) -> usize {
+        self.build()
+            .map(|metadata| metadata.len())
+            .unwrap_or_default()
+    }
+}
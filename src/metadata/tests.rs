@@ -0,0 +1,42 @@
+//!
+//! The metadata tests.
+//!
+
+use crate::metadata::cbor_value::CborValue;
+use crate::metadata::MetadataBuilder;
+
+#[test]
+pub fn check_metadata_builder() {
+    assert_eq!(MetadataBuilder::none().build(), None);
+    assert_eq!(MetadataBuilder::none().size(), 0);
+
+    let metadata = MetadataBuilder::none()
+        .with_version("zksolc", semver::Version::new(1, 5, 0))
+        .build()
+        .expect("Always exists");
+    let mut expected = vec![0xa1, 0x66];
+    expected.extend_from_slice(b"zksolc");
+    expected.push(0x65);
+    expected.extend_from_slice(b"1.5.0");
+    expected.extend_from_slice(14u16.to_be_bytes().as_slice());
+    assert_eq!(metadata, expected);
+}
+
+#[test]
+pub fn check_metadata_builder_custom_entries() {
+    let metadata = MetadataBuilder::none()
+        .with_entry(
+            "flags",
+            CborValue::Array(vec![CborValue::Bool(true), CborValue::Unsigned(500)]),
+        )
+        .with_entry("src", CborValue::Bytes(vec![0xab]))
+        .build()
+        .expect("Always exists");
+    let mut expected = vec![0xa2, 0x65];
+    expected.extend_from_slice(b"flags");
+    expected.extend_from_slice(&[0x82, 0xf5, 0x19, 0x01, 0xf4, 0x63]);
+    expected.extend_from_slice(b"src");
+    expected.extend_from_slice(&[0x41, 0xab]);
+    expected.extend_from_slice(18u16.to_be_bytes().as_slice());
+    assert_eq!(metadata, expected);
+}
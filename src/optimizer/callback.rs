@@ -0,0 +1,58 @@
+//!
+//! The LLVM optimizer embedder callback.
+//!
+
+use std::sync::Arc;
+
+use super::stage::Stage;
+
+///
+/// The LLVM optimizer embedder callback function.
+///
+pub type CallbackFunction =
+    dyn Fn(&inkwell::module::Module) -> anyhow::Result<()> + Send + Sync + 'static;
+
+///
+/// The LLVM optimizer embedder callback.
+///
+/// Callbacks of the same stage are run in the order of registration.
+///
+#[derive(Clone)]
+pub struct Callback {
+    /// The callback name, used in profiling and error messages.
+    pub name: String,
+    /// The pipeline stage to run the callback at.
+    pub stage: Stage,
+    /// The callback function.
+    function: Arc<CallbackFunction>,
+}
+
+impl Callback {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, stage: Stage, function: Arc<CallbackFunction>) -> Self {
+        Self {
+            name,
+            stage,
+            function,
+        }
+    }
+
+    ///
+    /// Runs the callback on `module`.
+    ///
+    pub fn run(&self, module: &inkwell::module::Module) -> anyhow::Result<()> {
+        (self.function)(module)
+            .map_err(|error| anyhow::anyhow!("{} callback `{}`: {error}", self.stage, self.name))
+    }
+}
+
+impl std::fmt::Debug for Callback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callback")
+            .field("name", &self.name)
+            .field("stage", &self.stage)
+            .finish_non_exhaustive()
+    }
+}
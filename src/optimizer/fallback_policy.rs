@@ -0,0 +1,149 @@
+//!
+//! The bytecode size overflow fallback policy.
+//!
+
+use std::sync::Arc;
+
+use super::settings::size_level::SizeLevel;
+use super::settings::Settings;
+
+///
+/// The custom fallback policy function.
+///
+/// Receives the optimized module whose bytecode has exceeded the size limit and the settings it
+/// has been built with, and returns the settings of the next attempt, or `None` to give up.
+///
+pub type FallbackFunction =
+    dyn Fn(&inkwell::module::Module, &Settings) -> Option<Settings> + Send + Sync + 'static;
+
+///
+/// The bytecode size overflow fallback policy.
+///
+#[derive(Clone)]
+pub enum FallbackPolicy {
+    /// The size overflow is reported as an error.
+    None,
+    /// The build is retried once, optimizing for size.
+    SizeOnly,
+    /// The build is retried with `-Os`, and then with `-Oz`.
+    Staged,
+    /// The next attempt settings are chosen by the embedder.
+    Custom(Arc<FallbackFunction>),
+}
+
+impl FallbackPolicy {
+    ///
+    /// Returns the policy implied by the fallback flag of `settings`.
+    ///
+    pub fn from_settings(settings: &Settings) -> Self {
+        if settings.is_fallback_to_size_enabled() {
+            Self::SizeOnly
+        } else {
+            Self::None
+        }
+    }
+
+    ///
+    /// Returns the settings of the next attempt after the build with `settings` has exceeded the
+    /// size limit, or `None` if there are no attempts left.
+    ///
+    /// Attempts with the same settings as the previous one are never made.
+    ///
+    pub fn next_settings(
+        &self,
+        module: &inkwell::module::Module,
+        settings: &Settings,
+    ) -> Option<Settings> {
+        let next_settings = match self {
+            Self::None => None,
+            Self::SizeOnly => Some(Settings::size()),
+            Self::Staged => match settings.level_middle_end_size {
+                SizeLevel::Zero => Some(Settings::new(
+                    inkwell::OptimizationLevel::Default,
+                    SizeLevel::S,
+                    inkwell::OptimizationLevel::Aggressive,
+                )),
+                SizeLevel::S => Some(Settings::size()),
+                SizeLevel::Z => None,
+            },
+            Self::Custom(function) => function(module, settings),
+        }?;
+        if &next_settings == settings {
+            return None;
+        }
+        Some(next_settings)
+    }
+}
+
+impl std::fmt::Debug for FallbackPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::SizeOnly => write!(f, "SizeOnly"),
+            Self::Staged => write!(f, "Staged"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::optimizer::settings::size_level::SizeLevel;
+    use crate::optimizer::settings::Settings;
+
+    use super::FallbackPolicy;
+
+    #[test]
+    pub fn check_next_settings() {
+        let llvm = inkwell::context::Context::create();
+        let module = llvm.create_module("test");
+        let size_s = Settings::new(
+            inkwell::OptimizationLevel::Default,
+            SizeLevel::S,
+            inkwell::OptimizationLevel::Aggressive,
+        );
+
+        assert!(FallbackPolicy::None
+            .next_settings(&module, &Settings::cycles())
+            .is_none());
+
+        assert_eq!(
+            FallbackPolicy::SizeOnly.next_settings(&module, &Settings::cycles()),
+            Some(Settings::size())
+        );
+        assert!(FallbackPolicy::SizeOnly
+            .next_settings(&module, &Settings::size())
+            .is_none());
+
+        assert_eq!(
+            FallbackPolicy::Staged.next_settings(&module, &Settings::cycles()),
+            Some(size_s.clone())
+        );
+        assert_eq!(
+            FallbackPolicy::Staged.next_settings(&module, &size_s),
+            Some(Settings::size())
+        );
+        assert!(FallbackPolicy::Staged
+            .next_settings(&module, &Settings::size())
+            .is_none());
+
+        let repeating =
+            FallbackPolicy::Custom(Arc::new(|_module, settings| Some(settings.clone())));
+        assert!(repeating
+            .next_settings(&module, &Settings::cycles())
+            .is_none());
+
+        let mut settings = Settings::cycles();
+        settings.enable_fallback_to_size();
+        assert!(matches!(
+            FallbackPolicy::from_settings(&settings),
+            FallbackPolicy::SizeOnly
+        ));
+        assert!(matches!(
+            FallbackPolicy::from_settings(&Settings::cycles()),
+            FallbackPolicy::None
+        ));
+    }
+}
@@ -2,11 +2,26 @@
 //! The LLVM optimizing tools.
 //!
 
+pub mod callback;
+pub mod fallback_policy;
 pub mod settings;
+pub mod stage;
 
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::debug_config::DebugConfig;
+use crate::profiler::Profiler;
 use crate::target_machine::TargetMachine;
 
+use self::callback::Callback;
+use self::callback::CallbackFunction;
+use self::fallback_policy::FallbackPolicy;
 use self::settings::Settings;
+use self::stage::Stage;
 
 ///
 /// The LLVM optimizing tools.
@@ -15,34 +30,242 @@ use self::settings::Settings;
 pub struct Optimizer {
     /// The optimizer settings.
     settings: Settings,
+    /// The embedder callbacks in the order of registration.
+    #[serde(skip)]
+    callbacks: Vec<Callback>,
+    /// The size overflow fallback policy. Implied by the settings if unset.
+    #[serde(skip)]
+    fallback_policy: Option<FallbackPolicy>,
 }
 
 impl Optimizer {
+    /// The pipeline that deletes the unreachable internal functions.
+    pub const PRUNING_PIPELINE: &'static str = "globaldce";
+
+    /// The staged pipeline, which approximates the default one for diagnostic purposes.
+    /// Its output is not guaranteed to match the default pipeline output, so it is only dumped
+    /// and never passed to the code generator.
+    pub const STAGED_PIPELINE: [(&'static str, &'static str); 4] = [
+        (
+            "module_simplification",
+            "ipsccp,globalopt,function(sroa,early-cse,simplifycfg,instcombine)",
+        ),
+        (
+            "inliner",
+            "cgscc(inline,function-attrs),function(sroa,early-cse,instcombine,simplifycfg)",
+        ),
+        (
+            "loop_optimization",
+            "function(loop-mssa(licm),loop(loop-rotate,indvars,loop-deletion),simplifycfg)",
+        ),
+        (
+            "cleanup",
+            "function(gvn,sccp,dce,instcombine,simplifycfg),globaldce",
+        ),
+    ];
+
     ///
     /// A shortcut constructor.
     ///
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            callbacks: vec![],
+            fallback_policy: None,
+        }
+    }
+
+    ///
+    /// Registers an embedder callback to run at `stage`.
+    ///
+    pub fn register_callback(&mut self, name: &str, stage: Stage, function: Arc<CallbackFunction>) {
+        self.callbacks
+            .push(Callback::new(name.to_owned(), stage, function));
+    }
+
+    ///
+    /// Whether any embedder callbacks are registered.
+    ///
+    pub fn has_callbacks(&self) -> bool {
+        !self.callbacks.is_empty()
     }
 
     ///
     /// Runs the new pass manager.
     ///
+    /// The custom pass pipeline from the settings is used instead of the default one if set.
+    ///
+    /// The embedder callbacks are run before and after the pass pipeline according to their
+    /// stages. If `profiler` is set, each callback run is saved to it.
+    ///
+    /// If the staged pipeline is enabled and `debug_config` is set, the staged pipeline is also run
+    /// on a copy of the module, with the IR and timings dumped after each stage. The module itself
+    /// is always optimized with the default pipeline, so the emitted code does not depend on it.
+    ///
     pub fn run(
         &self,
         target_machine: &TargetMachine,
         module: &inkwell::module::Module,
-    ) -> Result<(), inkwell::support::LLVMString> {
-        target_machine.run_optimization_passes(
+        contract_path: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        mut profiler: Option<&mut Profiler>,
+        debug_config: Option<&DebugConfig>,
+    ) -> anyhow::Result<()> {
+        self.run_callbacks(
+            Stage::PreOptimization,
+            module,
+            contract_path,
+            code_segment,
+            profiler.as_deref_mut(),
+        )?;
+        if let Some(debug_config) = debug_config {
+            if self.settings.is_staged_pipeline_enabled
+                && self.settings.custom_pass_pipeline.is_none()
+            {
+                self.run_staged(
+                    target_machine,
+                    &module.clone(),
+                    contract_path,
+                    code_segment,
+                    debug_config,
+                )?;
+            }
+        }
+        target_machine
+            .run_optimization_passes(module, self.settings.pass_pipeline().as_str())
+            .map_err(|error| anyhow::anyhow!(error.to_string()))?;
+        self.run_callbacks(
+            Stage::PostOptimization,
             module,
-            format!("default<O{}>", self.settings.middle_end_as_string()).as_str(),
+            contract_path,
+            code_segment,
+            profiler,
         )
     }
 
+    ///
+    /// Deletes the internal functions unreachable from the externally visible ones.
+    ///
+    /// Run before the optimization pipeline to save the optimizer time on the code the front-end
+    /// has lowered conservatively. Functions referenced in any way, e.g. as near call exception
+    /// handlers, are considered reachable.
+    ///
+    pub fn prune_unreachable(
+        &self,
+        target_machine: &TargetMachine,
+        module: &inkwell::module::Module,
+    ) -> anyhow::Result<()> {
+        target_machine
+            .run_optimization_passes(module, Self::PRUNING_PIPELINE)
+            .map_err(|error| anyhow::anyhow!(error.to_string()))
+    }
+
     ///
     /// Returns the optimizer settings reference.
     ///
     pub fn settings(&self) -> &Settings {
         &self.settings
     }
+
+    ///
+    /// Replaces the optimizer settings, keeping the registered callbacks.
+    ///
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.settings = settings;
+    }
+
+    ///
+    /// Sets the size overflow fallback policy, which overrides the settings fallback flag.
+    ///
+    pub fn set_fallback_policy(&mut self, fallback_policy: FallbackPolicy) {
+        self.fallback_policy = Some(fallback_policy);
+    }
+
+    ///
+    /// Returns the size overflow fallback policy, if it has been set explicitly.
+    ///
+    pub fn fallback_policy(&self) -> Option<&FallbackPolicy> {
+        self.fallback_policy.as_ref()
+    }
+
+    ///
+    /// Returns the settings of the next attempt after the bytecode of `module` has exceeded the
+    /// size limit, or `None` if the overflow must be reported as an error.
+    ///
+    /// The bytecode size limit is carried over to the next attempt settings.
+    ///
+    pub fn fallback_settings(&self, module: &inkwell::module::Module) -> Option<Settings> {
+        let mut settings = match self.fallback_policy {
+            Some(ref fallback_policy) => fallback_policy.next_settings(module, &self.settings),
+            None => {
+                FallbackPolicy::from_settings(&self.settings).next_settings(module, &self.settings)
+            }
+        }?;
+        settings.set_eravm_bytecode_size_limit(self.settings.eravm_bytecode_size_limit);
+        Some(settings)
+    }
+
+    ///
+    /// Runs the staged pipeline, dumping the IR and timings after each stage.
+    ///
+    /// Must be given a copy of the module, since the pipeline output is only for diagnostics.
+    ///
+    fn run_staged(
+        &self,
+        target_machine: &TargetMachine,
+        module: &inkwell::module::Module,
+        contract_path: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        debug_config: &DebugConfig,
+    ) -> anyhow::Result<()> {
+        let mut timings = Vec::with_capacity(Self::STAGED_PIPELINE.len());
+        for (index, (stage_name, passes)) in Self::STAGED_PIPELINE.into_iter().enumerate() {
+            let start = Instant::now();
+            target_machine
+                .run_optimization_passes(module, passes)
+                .map_err(|error| anyhow::anyhow!("stage `{stage_name}`: {error}"))?;
+            timings.push((stage_name.to_owned(), start.elapsed()));
+
+            debug_config.dump_llvm_ir_stage(
+                contract_path,
+                code_segment,
+                module,
+                index,
+                stage_name,
+            )?;
+        }
+        debug_config.dump_stage_timings(contract_path, code_segment, timings.as_slice())
+    }
+
+    ///
+    /// Runs the callbacks registered for `stage`.
+    ///
+    fn run_callbacks(
+        &self,
+        stage: Stage,
+        module: &inkwell::module::Module,
+        contract_path: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        mut profiler: Option<&mut Profiler>,
+    ) -> anyhow::Result<()> {
+        for callback in self
+            .callbacks
+            .iter()
+            .filter(|callback| callback.stage == stage)
+        {
+            let run = profiler.as_ref().map(|profiler| {
+                profiler.start_pipeline_element(
+                    contract_path,
+                    code_segment,
+                    format!("{stage} callback `{}`", callback.name).as_str(),
+                    &self.settings,
+                )
+            });
+            callback.run(module)?;
+            if let (Some(profiler), Some(run)) = (profiler.as_deref_mut(), run) {
+                profiler.finish_pipeline_element(run);
+            }
+        }
+        Ok(())
+    }
 }
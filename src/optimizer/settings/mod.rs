@@ -21,6 +21,19 @@ pub struct Settings {
     pub level_back_end: inkwell::OptimizationLevel,
     /// Fallback to optimizing for size if the bytecode is too large.
     pub is_fallback_to_size_enabled: bool,
+    /// The custom middle-end pass pipeline, which replaces the default one if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_pass_pipeline: Option<String>,
+    /// Whether the staged pipeline is run on a copy of the module, dumping the IR and timings
+    /// after each stage. Does not affect the optimized module.
+    #[serde(default)]
+    pub is_staged_pipeline_enabled: bool,
+    /// The EraVM bytecode size limit in instructions, which is the VM default if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eravm_bytecode_size_limit: Option<usize>,
+    /// Whether hashes of memory written with constants in the same basic block are folded.
+    #[serde(default)]
+    pub is_hash_constant_folding_enabled: bool,
 
     /// Whether the LLVM `verify each` option is enabled.
     pub is_verify_each_enabled: bool,
@@ -45,6 +58,10 @@ impl Settings {
             level_middle_end_size,
             level_back_end,
             is_fallback_to_size_enabled: false,
+            custom_pass_pipeline: None,
+            is_staged_pipeline_enabled: false,
+            eravm_bytecode_size_limit: None,
+            is_hash_constant_folding_enabled: false,
 
             is_verify_each_enabled: false,
             is_debug_logging_enabled: false,
@@ -67,6 +84,10 @@ impl Settings {
             level_middle_end_size,
             level_back_end,
             is_fallback_to_size_enabled: false,
+            custom_pass_pipeline: None,
+            is_staged_pipeline_enabled: false,
+            eravm_bytecode_size_limit: None,
+            is_hash_constant_folding_enabled: false,
 
             is_verify_each_enabled,
             is_debug_logging_enabled,
@@ -230,6 +251,70 @@ impl Settings {
     pub fn is_fallback_to_size_enabled(&self) -> bool {
         self.is_fallback_to_size_enabled
     }
+
+    ///
+    /// Enables running the staged pipeline with the IR and timings dumped after each stage.
+    ///
+    /// Only has effect if the debug configuration is set. The staged pipeline is run on a copy of
+    /// the module, so the emitted code is the same as without it.
+    ///
+    pub fn enable_staged_pipeline(&mut self) {
+        self.is_staged_pipeline_enabled = true;
+    }
+
+    ///
+    /// Sets the custom middle-end pass pipeline, e.g. `function(instcombine,simplifycfg)`.
+    ///
+    pub fn with_custom_pass_pipeline(mut self, pipeline: String) -> Self {
+        self.custom_pass_pipeline = Some(pipeline);
+        self
+    }
+
+    ///
+    /// Sets or resets the custom middle-end pass pipeline.
+    ///
+    pub fn set_custom_pass_pipeline(&mut self, pipeline: Option<String>) {
+        self.custom_pass_pipeline = pipeline;
+    }
+
+    ///
+    /// Sets the EraVM bytecode size limit in instructions, e.g. for chains with modified VM limits.
+    ///
+    pub fn set_eravm_bytecode_size_limit(&mut self, limit: Option<usize>) {
+        self.eravm_bytecode_size_limit = limit;
+    }
+
+    ///
+    /// Returns the EraVM bytecode size limit in instructions.
+    ///
+    pub fn eravm_bytecode_size_limit(&self) -> usize {
+        self.eravm_bytecode_size_limit
+            .unwrap_or(crate::eravm::r#const::BYTECODE_SIZE_LIMIT_DEFAULT)
+    }
+
+    ///
+    /// Enables folding the hashes of memory written with constants in the same basic block.
+    ///
+    pub fn enable_hash_constant_folding(&mut self) {
+        self.is_hash_constant_folding_enabled = true;
+    }
+
+    ///
+    /// Whether the hash constant folding is enabled.
+    ///
+    pub fn is_hash_constant_folding_enabled(&self) -> bool {
+        self.is_hash_constant_folding_enabled
+    }
+
+    ///
+    /// Returns the middle-end pass pipeline, which is the custom one if set.
+    ///
+    pub fn pass_pipeline(&self) -> String {
+        match self.custom_pass_pipeline {
+            Some(ref pipeline) => pipeline.to_owned(),
+            None => format!("default<O{}>", self.middle_end_as_string()),
+        }
+    }
 }
 
 impl PartialEq for Settings {
@@ -237,6 +322,7 @@ impl PartialEq for Settings {
         self.level_middle_end == other.level_middle_end
             && self.level_middle_end_size == other.level_middle_end_size
             && self.level_back_end == other.level_back_end
+            && self.custom_pass_pipeline == other.custom_pass_pipeline
     }
 }
 
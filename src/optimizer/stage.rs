@@ -0,0 +1,23 @@
+//!
+//! The LLVM optimizer pipeline stage.
+//!
+
+///
+/// The LLVM optimizer pipeline stage, where the embedder callbacks are run.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Stage {
+    /// Before the middle-end pass pipeline.
+    PreOptimization,
+    /// After the middle-end pass pipeline.
+    PostOptimization,
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::PreOptimization => write!(f, "pre-optimization"),
+            Stage::PostOptimization => write!(f, "post-optimization"),
+        }
+    }
+}
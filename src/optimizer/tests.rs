@@ -0,0 +1,242 @@
+//!
+//! The LLVM optimizing tools tests.
+//!
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::debug_config::DebugConfig;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::optimizer::stage::Stage;
+use crate::optimizer::Optimizer;
+use crate::profiler::Profiler;
+use crate::target_machine::TargetMachine;
+
+///
+/// Creates a module with an external function and an unreferenced private one.
+///
+fn create_module(llvm: &inkwell::context::Context) -> inkwell::module::Module<'_> {
+    let module = llvm.create_module("test");
+    let builder = llvm.create_builder();
+    let r#type = llvm.void_type().fn_type(&[], false);
+    for (name, linkage) in [
+        ("external", inkwell::module::Linkage::External),
+        ("unreferenced", inkwell::module::Linkage::Private),
+    ] {
+        let function = module.add_function(name, r#type, Some(linkage));
+        builder.position_at_end(llvm.append_basic_block(function, "entry"));
+        builder.build_return(None).expect("Failed to build return");
+    }
+    module
+}
+
+///
+/// Runs `optimizer` on a new module, returning the module or the error.
+///
+fn run<'ctx>(
+    llvm: &'ctx inkwell::context::Context,
+    optimizer: &Optimizer,
+) -> anyhow::Result<inkwell::module::Module<'ctx>> {
+    crate::eravm::initialize_target();
+    let target_machine = TargetMachine::new(
+        era_compiler_common::Target::EraVM,
+        optimizer.settings(),
+        &[],
+    )?;
+    let module = create_module(llvm);
+    optimizer.run(&target_machine, &module, "test.sol:Test", None, None, None)?;
+    Ok(module)
+}
+
+#[test]
+pub fn check_custom_pass_pipeline() {
+    assert_eq!(OptimizerSettings::cycles().pass_pipeline(), "default<O3>");
+    assert_eq!(OptimizerSettings::size().pass_pipeline(), "default<Oz>");
+    let settings = OptimizerSettings::cycles().with_custom_pass_pipeline("verify".to_owned());
+    assert_eq!(settings.pass_pipeline(), "verify");
+    assert_ne!(settings, OptimizerSettings::cycles());
+
+    let llvm = inkwell::context::Context::create();
+    let module = run(&llvm, &Optimizer::new(OptimizerSettings::cycles())).expect("Failed to run");
+    assert!(module.get_function("unreferenced").is_none());
+
+    let module = run(&llvm, &Optimizer::new(settings)).expect("Failed to run");
+    assert!(module.get_function("external").is_some());
+    assert!(module.get_function("unreferenced").is_some());
+
+    let mut settings = OptimizerSettings::cycles();
+    settings.set_custom_pass_pipeline(Some("globaldce".to_owned()));
+    let module = run(&llvm, &Optimizer::new(settings)).expect("Failed to run");
+    assert!(module.get_function("external").is_some());
+    assert!(module.get_function("unreferenced").is_none());
+
+    let settings = OptimizerSettings::cycles().with_custom_pass_pipeline("no-such-pass".to_owned());
+    assert!(run(&llvm, &Optimizer::new(settings)).is_err());
+}
+
+#[test]
+pub fn check_callbacks() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let record = |name: &'static str| {
+        let log = log.clone();
+        Arc::new(
+            move |module: &inkwell::module::Module| -> anyhow::Result<()> {
+                log.lock().expect("Sync").push(format!(
+                    "{name}:{}",
+                    module.get_function("unreferenced").is_some()
+                ));
+                Ok(())
+            },
+        )
+    };
+
+    let mut optimizer = Optimizer::new(OptimizerSettings::cycles());
+    optimizer.register_callback("post", Stage::PostOptimization, record("post"));
+    optimizer.register_callback("first", Stage::PreOptimization, record("first"));
+    optimizer.register_callback("second", Stage::PreOptimization, record("second"));
+    optimizer.set_settings(OptimizerSettings::size());
+
+    crate::eravm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let target_machine = TargetMachine::new(
+        era_compiler_common::Target::EraVM,
+        optimizer.settings(),
+        &[],
+    )
+    .expect("Failed to create target machine");
+    let module = create_module(&llvm);
+    let mut profiler = Profiler::new();
+    optimizer
+        .run(
+            &target_machine,
+            &module,
+            "test.sol:Test",
+            None,
+            Some(&mut profiler),
+            None,
+        )
+        .expect("Failed to run");
+    assert_eq!(
+        log.lock().expect("Sync").as_slice(),
+        ["first:true", "second:true", "post:false"]
+    );
+    assert_eq!(
+        profiler
+            .timings()
+            .iter()
+            .map(|(run, _duration)| run.element.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "pre-optimization callback `first`",
+            "pre-optimization callback `second`",
+            "post-optimization callback `post`",
+        ]
+    );
+
+    log.lock().expect("Sync").clear();
+    let mut optimizer = Optimizer::new(OptimizerSettings::cycles());
+    optimizer.register_callback(
+        "failing",
+        Stage::PreOptimization,
+        Arc::new(|_module| anyhow::bail!("invalid module")),
+    );
+    optimizer.register_callback("post", Stage::PostOptimization, record("post"));
+    let error = run(&llvm, &optimizer).expect_err("Must fail");
+    assert_eq!(
+        error.to_string(),
+        "pre-optimization callback `failing`: invalid module"
+    );
+    assert!(log.lock().expect("Sync").is_empty());
+}
+
+#[test]
+pub fn check_staged_pipeline() {
+    let output_directory = std::env::temp_dir().join(format!(
+        "era_compiler_staged_pipeline_{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(output_directory.as_path()).expect("Failed to create directory");
+    let debug_config = DebugConfig::new(output_directory.clone());
+
+    crate::eravm::initialize_target();
+    let llvm = inkwell::context::Context::create();
+    let mut settings = OptimizerSettings::cycles();
+    settings.enable_staged_pipeline();
+    let target_machine = TargetMachine::new(era_compiler_common::Target::EraVM, &settings, &[])
+        .expect("Failed to create target machine");
+    let file_names = || {
+        std::fs::read_dir(output_directory.as_path())
+            .expect("Failed to read directory")
+            .map(|entry| {
+                entry
+                    .expect("Failed to read entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+    };
+
+    let module = create_module(&llvm);
+    Optimizer::new(settings.clone())
+        .run(
+            &target_machine,
+            &module,
+            "test.sol:Test",
+            None,
+            None,
+            Some(&debug_config),
+        )
+        .expect("Failed to run");
+    assert!(module.get_function("unreferenced").is_none());
+    let dumped = file_names();
+    for (index, (stage_name, _passes)) in Optimizer::STAGED_PIPELINE.into_iter().enumerate() {
+        let prefix = format!("test.sol.Test.stage{index}_{stage_name}.");
+        assert!(
+            dumped.iter().any(|name| name.starts_with(prefix.as_str())),
+            "{prefix}: {dumped:?}"
+        );
+    }
+    let timings = std::fs::read_to_string(output_directory.join("test.sol.Test.stage_timings.txt"))
+        .expect("Failed to read timings");
+    assert_eq!(timings.lines().count(), Optimizer::STAGED_PIPELINE.len());
+    assert!(
+        timings.starts_with("stage0_module_simplification: "),
+        "{timings}"
+    );
+
+    let default_module = create_module(&llvm);
+    Optimizer::new(OptimizerSettings::cycles())
+        .run(
+            &target_machine,
+            &default_module,
+            "default.sol:Test",
+            None,
+            None,
+            None,
+        )
+        .expect("Failed to run");
+    assert_eq!(
+        module.print_to_string().to_string(),
+        default_module.print_to_string().to_string()
+    );
+
+    let module = create_module(&llvm);
+    settings.set_custom_pass_pipeline(Some("verify".to_owned()));
+    Optimizer::new(settings)
+        .run(
+            &target_machine,
+            &module,
+            "custom.sol:Test",
+            None,
+            None,
+            Some(&debug_config),
+        )
+        .expect("Failed to run");
+    assert!(module.get_function("unreferenced").is_some());
+    assert!(!file_names()
+        .iter()
+        .any(|name| name.starts_with("custom.sol")));
+
+    std::fs::remove_dir_all(output_directory).expect("Failed to remove directory");
+}
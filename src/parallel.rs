@@ -0,0 +1,104 @@
+//!
+//! The parallel build driver.
+//!
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+
+///
+/// Builds `inputs` on a pool of `threads` workers.
+///
+/// LLVM contexts cannot be shared between threads, so `build` is expected to create its own
+/// LLVM context, module, and target machine for each input. The optimizer settings are shared
+/// by all workers to keep the target machine configuration identical.
+///
+/// If `threads` is not set, the available parallelism is used.
+///
+pub fn build_many<I, B, F>(
+    inputs: BTreeMap<String, I>,
+    optimizer_settings: &OptimizerSettings,
+    threads: Option<usize>,
+    build: F,
+) -> BTreeMap<String, anyhow::Result<B>>
+where
+    I: Send,
+    B: Send,
+    F: Fn(&str, I, &OptimizerSettings) -> anyhow::Result<B> + Sync,
+{
+    let threads = threads
+        .or_else(|| {
+            std::thread::available_parallelism()
+                .ok()
+                .map(|threads| threads.get())
+        })
+        .unwrap_or(1)
+        .clamp(1, inputs.len().max(1));
+
+    let queue = Mutex::new(inputs.into_iter());
+    let results = Mutex::new(BTreeMap::new());
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let next = queue.lock().expect("Sync").next();
+                let Some((contract_path, input)) = next else {
+                    break;
+                };
+                let result = build(contract_path.as_str(), input, optimizer_settings);
+                results.lock().expect("Sync").insert(contract_path, result);
+            });
+        }
+    });
+    results.into_inner().expect("Sync")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::optimizer::settings::Settings as OptimizerSettings;
+
+    #[test]
+    pub fn check_build_many() {
+        let inputs: BTreeMap<String, u64> = (0..8)
+            .map(|index| (format!("contract_{index}.sol:Test"), index))
+            .collect();
+
+        for threads in [None, Some(1), Some(3), Some(64)] {
+            let results = super::build_many(
+                inputs.clone(),
+                &OptimizerSettings::cycles(),
+                threads,
+                |contract_path, input, optimizer_settings| {
+                    assert_eq!(optimizer_settings, &OptimizerSettings::cycles());
+                    if input % 4 == 3 {
+                        anyhow::bail!("{contract_path} failed");
+                    }
+                    Ok(input * 2)
+                },
+            );
+            assert_eq!(
+                results.keys().collect::<Vec<&String>>(),
+                inputs.keys().collect::<Vec<&String>>()
+            );
+            for (contract_path, input) in inputs.iter() {
+                match results[contract_path] {
+                    Ok(output) => assert_eq!(output, input * 2),
+                    Err(ref error) => {
+                        assert_eq!(input % 4, 3);
+                        assert_eq!(error.to_string(), format!("{contract_path} failed"));
+                    }
+                }
+            }
+        }
+
+        assert!(super::build_many(
+            BTreeMap::<String, u64>::new(),
+            &OptimizerSettings::none(),
+            None,
+            |_contract_path, input, _optimizer_settings| Ok(input),
+        )
+        .is_empty());
+    }
+}
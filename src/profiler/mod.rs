@@ -0,0 +1,78 @@
+//!
+//! The LLVM pipeline profiler.
+//!
+
+pub mod run;
+
+#[cfg(test)]
+mod tests;
+
+use std::time::Duration;
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+
+use self::run::Run;
+
+///
+/// The LLVM pipeline profiler.
+///
+/// Collects the durations of the pipeline elements in the order they are finished.
+///
+#[derive(Debug, Default, Clone)]
+pub struct Profiler {
+    /// The finished pipeline element runs with their durations.
+    timings: Vec<(Run, Duration)>,
+}
+
+impl Profiler {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Starts a pipeline element run.
+    ///
+    pub fn start_pipeline_element(
+        &self,
+        translation_unit: &str,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        element: &str,
+        settings: &OptimizerSettings,
+    ) -> Run {
+        Run::new(
+            translation_unit.to_owned(),
+            code_segment,
+            element.to_owned(),
+            settings.to_string(),
+        )
+    }
+
+    ///
+    /// Finishes a pipeline element run, saving and returning its duration.
+    ///
+    pub fn finish_pipeline_element(&mut self, run: Run) -> Duration {
+        let duration = run.elapsed();
+        self.timings.push((run, duration));
+        duration
+    }
+
+    ///
+    /// Returns the finished runs with their durations.
+    ///
+    pub fn timings(&self) -> &[(Run, Duration)] {
+        self.timings.as_slice()
+    }
+
+    ///
+    /// Returns the run names with their durations in milliseconds.
+    ///
+    pub fn to_vec(&self) -> Vec<(String, u64)> {
+        self.timings
+            .iter()
+            .map(|(run, duration)| (run.to_string(), duration.as_millis() as u64))
+            .collect()
+    }
+}
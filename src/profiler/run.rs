@@ -0,0 +1,64 @@
+//!
+//! The profiler pipeline element run.
+//!
+
+use std::time::Duration;
+use std::time::Instant;
+
+///
+/// The profiler pipeline element run.
+///
+/// Is created when a pipeline element starts and is passed back to the profiler when it ends.
+///
+#[derive(Debug, Clone)]
+pub struct Run {
+    /// The translation unit, usually the contract path.
+    pub translation_unit: String,
+    /// The code segment, if applicable.
+    pub code_segment: Option<era_compiler_common::CodeSegment>,
+    /// The pipeline element name.
+    pub element: String,
+    /// The optimizer settings the element is run with.
+    pub settings: String,
+    /// The start time.
+    start: Instant,
+}
+
+impl Run {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// Starts the timer.
+    ///
+    pub fn new(
+        translation_unit: String,
+        code_segment: Option<era_compiler_common::CodeSegment>,
+        element: String,
+        settings: String,
+    ) -> Self {
+        Self {
+            translation_unit,
+            code_segment,
+            element,
+            settings,
+            start: Instant::now(),
+        }
+    }
+
+    ///
+    /// Returns the time elapsed since the run has started.
+    ///
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl std::fmt::Display for Run {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.translation_unit)?;
+        if let Some(code_segment) = self.code_segment {
+            write!(f, ":{code_segment}")?;
+        }
+        write!(f, " {} ({})", self.element, self.settings)
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! The LLVM pipeline profiler tests.
+//!
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::profiler::Profiler;
+
+#[test]
+pub fn check_profiler() {
+    let settings = OptimizerSettings::none();
+    let mut profiler = Profiler::new();
+
+    let runtime = profiler.start_pipeline_element(
+        "test.sol:Test",
+        Some(era_compiler_common::CodeSegment::Runtime),
+        "first",
+        &settings,
+    );
+    let unsegmented = profiler.start_pipeline_element("test.sol:Test", None, "second", &settings);
+    profiler.finish_pipeline_element(unsegmented);
+    profiler.finish_pipeline_element(runtime);
+
+    let names: Vec<String> = profiler
+        .to_vec()
+        .into_iter()
+        .map(|(name, _duration)| name)
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            format!("test.sol:Test second ({settings})"),
+            format!(
+                "test.sol:Test:{} first ({settings})",
+                era_compiler_common::CodeSegment::Runtime
+            ),
+        ]
+    );
+    assert_eq!(profiler.timings()[1].0.element, "first");
+}
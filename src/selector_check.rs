@@ -0,0 +1,159 @@
+//!
+//! The cross-contract selector consistency check.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+///
+/// The cross-contract selector consistency check.
+///
+/// The dispatcher selectors are declared by the front-end for each contract, and compared with
+/// the expected interfaces and with the other facets of the same diamond.
+///
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SelectorCheck {
+    /// The dispatcher selectors declared by each contract.
+    pub dispatchers: BTreeMap<String, BTreeSet<u32>>,
+    /// The selectors each contract is expected to dispatch.
+    pub interfaces: BTreeMap<String, BTreeSet<u32>>,
+    /// The groups of contracts intended to be facets of a single diamond.
+    pub diamonds: Vec<BTreeSet<String>>,
+}
+
+impl SelectorCheck {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Declares the dispatcher selectors of `contract_path`.
+    ///
+    pub fn declare_dispatcher(&mut self, contract_path: &str, selectors: BTreeSet<u32>) {
+        self.dispatchers.insert(contract_path.to_owned(), selectors);
+    }
+
+    ///
+    /// Declares the selectors `contract_path` is expected to dispatch.
+    ///
+    pub fn expect_interface(&mut self, contract_path: &str, selectors: BTreeSet<u32>) {
+        self.interfaces.insert(contract_path.to_owned(), selectors);
+    }
+
+    ///
+    /// Declares a group of contracts intended to be facets of a single diamond.
+    ///
+    pub fn declare_diamond(&mut self, facets: BTreeSet<String>) {
+        self.diamonds.push(facets);
+    }
+
+    ///
+    /// Runs the check, returning the warnings for each affected contract.
+    ///
+    pub fn check(&self) -> BTreeMap<String, Vec<String>> {
+        let mut warnings: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for (contract_path, expected) in self.interfaces.iter() {
+            let declared = match self.dispatchers.get(contract_path) {
+                Some(declared) => declared,
+                None => {
+                    warnings.entry(contract_path.to_owned()).or_default().push(
+                        "no dispatcher selectors declared for the expected interface".to_owned(),
+                    );
+                    continue;
+                }
+            };
+            for selector in expected.difference(declared) {
+                warnings
+                    .entry(contract_path.to_owned())
+                    .or_default()
+                    .push(format!(
+                        "missing selector `0x{selector:08x}` of the expected interface"
+                    ));
+            }
+        }
+
+        for facets in self.diamonds.iter() {
+            let mut owners: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+            for facet in facets.iter() {
+                for selector in self.dispatchers.get(facet).into_iter().flatten() {
+                    owners.entry(*selector).or_default().push(facet.as_str());
+                }
+            }
+            for (selector, owners) in owners.into_iter() {
+                if owners.len() < 2 {
+                    continue;
+                }
+                for owner in owners.iter() {
+                    let others = owners
+                        .iter()
+                        .filter(|other| *other != owner)
+                        .map(|other| format!("`{other}`"))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    warnings
+                        .entry((*owner).to_owned())
+                        .or_default()
+                        .push(format!(
+                        "selector `0x{selector:08x}` is also dispatched by diamond facets {others}"
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+
+    use super::SelectorCheck;
+
+    #[test]
+    pub fn check_selector_check() {
+        let mut check = SelectorCheck::new();
+        check.declare_dispatcher("a.sol:A", BTreeSet::from([0x01, 0x02]));
+        check.declare_dispatcher("b.sol:B", BTreeSet::from([0x02, 0x03]));
+        check.declare_dispatcher("c.sol:C", BTreeSet::from([0x04]));
+        check.expect_interface("a.sol:A", BTreeSet::from([0x01, 0x05]));
+        check.expect_interface("c.sol:C", BTreeSet::from([0x04]));
+        check.expect_interface("d.sol:D", BTreeSet::from([0x06]));
+        check.declare_diamond(BTreeSet::from([
+            "a.sol:A".to_owned(),
+            "b.sol:B".to_owned(),
+            "c.sol:C".to_owned(),
+        ]));
+
+        assert_eq!(
+            check.check(),
+            BTreeMap::from([
+                (
+                    "a.sol:A".to_owned(),
+                    vec![
+                        "missing selector `0x00000005` of the expected interface".to_owned(),
+                        "selector `0x00000002` is also dispatched by diamond facets `b.sol:B`"
+                            .to_owned(),
+                    ]
+                ),
+                (
+                    "b.sol:B".to_owned(),
+                    vec![
+                        "selector `0x00000002` is also dispatched by diamond facets `a.sol:A`"
+                            .to_owned()
+                    ]
+                ),
+                (
+                    "d.sol:D".to_owned(),
+                    vec!["no dispatcher selectors declared for the expected interface".to_owned()]
+                ),
+            ])
+        );
+        assert!(SelectorCheck::new().check().is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! The size fallback build attempt.
+//!
+
+use std::time::Duration;
+
+///
+/// The size fallback build attempt.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Attempt {
+    /// The optimizer settings of the attempt.
+    pub settings: String,
+    /// The bytecode size in bytes.
+    pub size: usize,
+    /// The attempt duration.
+    pub duration: Duration,
+}
+
+impl Attempt {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(settings: String, size: usize, duration: Duration) -> Self {
+        Self {
+            settings,
+            size,
+            duration,
+        }
+    }
+}
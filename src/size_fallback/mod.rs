@@ -0,0 +1,71 @@
+//!
+//! The size fallback report.
+//!
+
+pub mod attempt;
+
+#[cfg(test)]
+mod tests;
+
+use self::attempt::Attempt;
+
+///
+/// The size fallback report.
+///
+/// Contains the build attempts in the order they were made. The first one uses the original
+/// optimizer settings, and the next one, if any, is the fallback to optimizing for size.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SizeFallback {
+    /// The build attempts.
+    pub attempts: Vec<Attempt>,
+    /// The bytecode size limit in bytes, if the target has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl SizeFallback {
+    ///
+    /// Whether the fallback to optimizing for size was attempted.
+    ///
+    pub fn is_attempted(&self) -> bool {
+        self.attempts.len() > 1
+    }
+
+    ///
+    /// Returns the bytecode size before the fallback.
+    ///
+    pub fn size_before(&self) -> Option<usize> {
+        self.attempts.first().map(|attempt| attempt.size)
+    }
+
+    ///
+    /// Returns the bytecode size after the fallback, if it was attempted.
+    ///
+    pub fn size_after(&self) -> Option<usize> {
+        if !self.is_attempted() {
+            return None;
+        }
+        self.attempts.last().map(|attempt| attempt.size)
+    }
+
+    ///
+    /// Returns the optimizer settings of the final attempt.
+    ///
+    pub fn settings_used(&self) -> Option<&str> {
+        self.attempts
+            .last()
+            .map(|attempt| attempt.settings.as_str())
+    }
+
+    ///
+    /// Returns the number of bytes left before the limit in the final attempt.
+    ///
+    /// Negative if the final attempt still exceeds the limit.
+    ///
+    pub fn headroom(&self) -> Option<isize> {
+        let limit = self.limit?;
+        let size = self.attempts.last()?.size;
+        Some(limit as isize - size as isize)
+    }
+}
@@ -0,0 +1,49 @@
+//!
+//! The size fallback report tests.
+//!
+
+use std::time::Duration;
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::size_fallback::attempt::Attempt;
+use crate::size_fallback::SizeFallback;
+
+#[test]
+pub fn check_size_fallback_report() {
+    let empty = SizeFallback::default();
+    assert!(!empty.is_attempted());
+    assert_eq!(empty.size_before(), None);
+    assert_eq!(empty.size_after(), None);
+    assert_eq!(empty.settings_used(), None);
+    assert_eq!(empty.headroom(), None);
+
+    let mut report = SizeFallback {
+        attempts: vec![Attempt::new(
+            OptimizerSettings::cycles().to_string(),
+            1024,
+            Duration::from_millis(10),
+        )],
+        limit: Some(768),
+    };
+    assert!(!report.is_attempted());
+    assert_eq!(report.size_before(), Some(1024));
+    assert_eq!(report.size_after(), None);
+    assert_eq!(report.headroom(), Some(-256));
+
+    report.attempts.push(Attempt::new(
+        OptimizerSettings::size().to_string(),
+        512,
+        Duration::from_millis(20),
+    ));
+    assert!(report.is_attempted());
+    assert_eq!(report.size_before(), Some(1024));
+    assert_eq!(report.size_after(), Some(512));
+    assert_eq!(
+        report.settings_used(),
+        Some(OptimizerSettings::size().to_string().as_str())
+    );
+    assert_eq!(report.headroom(), Some(256));
+
+    report.limit = None;
+    assert_eq!(report.headroom(), None);
+}
@@ -0,0 +1,59 @@
+//!
+//! The source map entry.
+//!
+
+///
+/// The source map entry.
+///
+/// Describes a single instruction of the bytecode.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Entry {
+    /// The instruction offset in the bytecode.
+    pub offset: usize,
+    /// The index of the function in the source map function list.
+    pub function: usize,
+    /// The index of the file in the source map file list, if the location is known.
+    pub file: Option<usize>,
+    /// The source line, starting from 1. Zero if unknown.
+    pub line: u32,
+    /// The source column, starting from 1. Zero if unknown.
+    pub column: u32,
+}
+
+impl Entry {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        offset: usize,
+        function: usize,
+        file: Option<usize>,
+        line: u32,
+        column: u32,
+    ) -> Self {
+        Self {
+            offset,
+            function,
+            file,
+            line,
+            column,
+        }
+    }
+
+    ///
+    /// Returns the solc-style fields of the entry: line, column, file, and function.
+    ///
+    /// The unknown file is encoded as `-1`, as solc does.
+    ///
+    pub fn fields(&self) -> [String; 4] {
+        [
+            self.line.to_string(),
+            self.column.to_string(),
+            self.file
+                .map(|file| file.to_string())
+                .unwrap_or_else(|| "-1".to_owned()),
+            self.function.to_string(),
+        ]
+    }
+}
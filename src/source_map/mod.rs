@@ -0,0 +1,169 @@
+//!
+//! The bytecode source map.
+//!
+
+pub mod entry;
+
+use std::collections::BTreeMap;
+
+use self::entry::Entry;
+
+///
+/// The bytecode source map.
+///
+/// Maps the bytecode instruction offsets to the functions and source locations, using the debug
+/// locations attached to the LLVM IR.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SourceMap {
+    /// The source files referenced by the entries.
+    pub files: Vec<String>,
+    /// The functions referenced by the entries.
+    pub functions: Vec<String>,
+    /// The entries, one per instruction, in the bytecode order.
+    pub entries: Vec<Entry>,
+}
+
+impl SourceMap {
+    ///
+    /// Builds the source map from the text assembly.
+    ///
+    /// Only the instructions of the code sections are mapped. `instruction_size` returns the
+    /// size in bytes of the instruction with the given mnemonic.
+    ///
+    /// Returns `None` if the assembly has no source locations, e.g. if the debug info is absent.
+    ///
+    pub fn from_assembly<F>(assembly: &str, instruction_size: F) -> Option<Self>
+    where
+        F: Fn(&str) -> usize,
+    {
+        let (source_map, is_location_found) = Self::parse_assembly(assembly, instruction_size);
+        if !is_location_found {
+            return None;
+        }
+        Some(source_map)
+    }
+
+    ///
+    /// Parses the text assembly into the source map, which has the unknown locations if the debug
+    /// info is absent.
+    ///
+    /// Returns the source map and whether any source location has been found.
+    ///
+    pub(crate) fn parse_assembly<F>(assembly: &str, instruction_size: F) -> (Self, bool)
+    where
+        F: Fn(&str) -> usize,
+    {
+        let mut source_map = Self::default();
+        let mut file_indexes = BTreeMap::new();
+
+        let mut is_code_section = true;
+        let mut offset = 0;
+        let mut location: Option<(usize, u32, u32)> = None;
+        let mut is_location_found = false;
+
+        for line in assembly.lines() {
+            let line = match line.split_once(';') {
+                Some((line, _comment)) => line,
+                None => line,
+            }
+            .trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line
+                .strip_prefix(".section")
+                .map(|section| section.trim())
+                .or_else(|| (line == ".text" || line == ".data").then_some(line))
+            {
+                is_code_section = section.starts_with(".text");
+                continue;
+            }
+            if let Some(directive) = line.strip_prefix(".file") {
+                let mut parts = directive.split_whitespace();
+                let index = parts.next().and_then(|index| index.parse::<usize>().ok());
+                let name = directive.rsplit('"').nth(1);
+                if let (Some(index), Some(name)) = (index, name) {
+                    file_indexes.insert(index, source_map.files.len());
+                    source_map.files.push(name.to_owned());
+                }
+                continue;
+            }
+            if let Some(directive) = line.strip_prefix(".loc") {
+                let mut parts = directive
+                    .split_whitespace()
+                    .map(|part| part.parse::<u32>().ok());
+                let file = parts.next().flatten();
+                let source_line = parts.next().flatten().unwrap_or_default();
+                let column = parts.next().flatten().unwrap_or_default();
+                location = file
+                    .and_then(|file| file_indexes.get(&(file as usize)).copied())
+                    .map(|file| (file, source_line, column));
+                is_location_found |= location.is_some();
+                continue;
+            }
+            if line.starts_with('.') {
+                continue;
+            }
+            if let Some(label) = line.strip_suffix(':') {
+                if is_code_section && !label.starts_with('.') {
+                    source_map.functions.push(label.to_owned());
+                    location = None;
+                }
+                continue;
+            }
+            if !is_code_section || source_map.functions.is_empty() {
+                continue;
+            }
+
+            let mnemonic = line.split_whitespace().next().unwrap_or_default();
+            let (file, source_line, column) = match location {
+                Some((file, source_line, column)) => (Some(file), source_line, column),
+                None => (None, 0, 0),
+            };
+            source_map.entries.push(Entry::new(
+                offset,
+                source_map.functions.len() - 1,
+                file,
+                source_line,
+                column,
+            ));
+            offset += instruction_size(mnemonic);
+        }
+
+        (source_map, is_location_found)
+    }
+
+    ///
+    /// Returns the source map in the solc compressed format.
+    ///
+    /// The entries are separated with `;`, and each entry consists of the line, column, file,
+    /// and function fields separated with `:`. As in solc, the fields equal to the ones of the
+    /// previous entry are omitted, along with the trailing separators.
+    ///
+    pub fn to_compressed(&self) -> String {
+        let mut previous: Option<[String; 4]> = None;
+        self.entries
+            .iter()
+            .map(|entry| {
+                let fields = entry.fields();
+                let mut compressed: Vec<&str> = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| match previous {
+                        Some(ref previous) if previous[index] == *field => "",
+                        _ => field.as_str(),
+                    })
+                    .collect();
+                while compressed.last() == Some(&"") {
+                    compressed.pop();
+                }
+                let compressed = compressed.join(":");
+                previous = Some(fields);
+                compressed
+            })
+            .collect::<Vec<String>>()
+            .join(";")
+    }
+}
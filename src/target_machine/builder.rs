@@ -0,0 +1,105 @@
+//!
+//! The LLVM target machine builder.
+//!
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+
+use super::code_model::CodeModel;
+use super::options::Options;
+use super::reloc_mode::RelocMode;
+use super::TargetMachine;
+
+///
+/// The LLVM target machine builder.
+///
+/// Allows configuring the target machine without recompiling the crate, e.g. to experiment
+/// with position-independent EraVM code or large-model EVM artifacts.
+///
+#[derive(Debug, Clone)]
+pub struct Builder {
+    /// The LLVM target.
+    target: era_compiler_common::Target,
+    /// The optimizer settings.
+    optimizer_settings: OptimizerSettings,
+    /// The target-specific LLVM options.
+    llvm_options: Vec<String>,
+    /// The target machine options.
+    options: Options,
+}
+
+impl Builder {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        target: era_compiler_common::Target,
+        optimizer_settings: &OptimizerSettings,
+    ) -> Self {
+        Self {
+            target,
+            optimizer_settings: optimizer_settings.to_owned(),
+            llvm_options: vec![],
+            options: Options::default(),
+        }
+    }
+
+    ///
+    /// Sets the target-specific LLVM options.
+    ///
+    pub fn with_llvm_options(mut self, llvm_options: &[String]) -> Self {
+        self.llvm_options = llvm_options.to_vec();
+        self
+    }
+
+    ///
+    /// Sets all target machine options at once.
+    ///
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    ///
+    /// Sets the target CPU name.
+    ///
+    pub fn with_cpu(mut self, cpu: &str) -> Self {
+        self.options = self.options.with_cpu(cpu);
+        self
+    }
+
+    ///
+    /// Enables or disables the target `feature`.
+    ///
+    pub fn with_feature(mut self, feature: &str, is_enabled: bool) -> Self {
+        self.options = self.options.with_feature(feature, is_enabled);
+        self
+    }
+
+    ///
+    /// Sets the relocation mode.
+    ///
+    pub fn with_reloc_mode(mut self, reloc_mode: RelocMode) -> Self {
+        self.options.reloc_mode = reloc_mode;
+        self
+    }
+
+    ///
+    /// Sets the code model.
+    ///
+    pub fn with_code_model(mut self, code_model: CodeModel) -> Self {
+        self.options.code_model = code_model;
+        self
+    }
+
+    ///
+    /// Creates the target machine.
+    ///
+    pub fn build(self) -> anyhow::Result<TargetMachine> {
+        TargetMachine::new_with_options(
+            self.target,
+            &self.optimizer_settings,
+            self.llvm_options.as_slice(),
+            &self.options,
+        )
+    }
+}
@@ -0,0 +1,51 @@
+//!
+//! The LLVM target machine code model.
+//!
+
+///
+/// The LLVM target machine code model.
+///
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodeModel {
+    /// The target default.
+    #[default]
+    Default,
+    /// The small code model.
+    Small,
+    /// The kernel code model.
+    Kernel,
+    /// The medium code model.
+    Medium,
+    /// The large code model.
+    Large,
+}
+
+impl From<CodeModel> for inkwell::targets::CodeModel {
+    fn from(value: CodeModel) -> Self {
+        match value {
+            CodeModel::Default => Self::Default,
+            CodeModel::Small => Self::Small,
+            CodeModel::Kernel => Self::Kernel,
+            CodeModel::Medium => Self::Medium,
+            CodeModel::Large => Self::Large,
+        }
+    }
+}
+
+impl std::str::FromStr for CodeModel {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "default" => Ok(Self::Default),
+            "small" => Ok(Self::Small),
+            "kernel" => Ok(Self::Kernel),
+            "medium" => Ok(Self::Medium),
+            "large" => Ok(Self::Large),
+            value => anyhow::bail!("Unknown code model `{value}`"),
+        }
+    }
+}
@@ -2,9 +2,22 @@
 //! The LLVM target machine.
 //!
 
+pub mod builder;
+pub mod code_model;
+pub mod options;
+pub mod reloc_mode;
+
+#[cfg(test)]
+mod tests;
+
+use crate::eravm::disassembler::options::Options as EraVMDisassemblerOptions;
+use crate::llvm_options::LLVMOptions;
 use crate::optimizer::settings::size_level::SizeLevel as OptimizerSettingsSizeLevel;
 use crate::optimizer::settings::Settings as OptimizerSettings;
 
+use self::builder::Builder;
+use self::options::Options;
+
 ///
 /// The LLVM target machine.
 ///
@@ -18,6 +31,10 @@ pub struct TargetMachine {
     optimizer_settings: OptimizerSettings,
 }
 
+// SAFETY: the LLVM target machine is not bound to the thread it was created on, and it is only
+// used by one build at a time, e.g. when handed out by the `TargetMachinePool`.
+unsafe impl Send for TargetMachine {}
+
 impl TargetMachine {
     /// The LLVM target name.
     pub const VM_TARGET_NAME: &'static str = "eravm";
@@ -28,32 +45,44 @@ impl TargetMachine {
     ///
     /// A shortcut constructor.
     ///
-    /// Supported LLVM options:
-    /// `-eravm-disable-sha3-sreq-cse`
-    /// `-eravm-jump-table-density-threshold <value>`
+    /// The target-specific LLVM options are validated with `LLVMOptions`, which also lists the
+    /// supported ones.
     ///
     pub fn new(
         target: era_compiler_common::Target,
         optimizer_settings: &OptimizerSettings,
         llvm_options: &[String],
     ) -> anyhow::Result<Self> {
-        let mut arguments = Vec::with_capacity(1 + llvm_options.len());
-        arguments.push(target.to_string());
-        arguments.extend_from_slice(llvm_options);
-        if arguments.len() > 1 {
-            let arguments: Vec<&str> = arguments.iter().map(|argument| argument.as_str()).collect();
-            inkwell::support::parse_command_line_options(arguments.as_slice(), "LLVM options");
-        }
+        Self::new_with_options(
+            target,
+            optimizer_settings,
+            llvm_options,
+            &Options::default(),
+        )
+    }
+
+    ///
+    /// A shortcut constructor with the target CPU, features, relocation mode, and code model.
+    ///
+    /// See `new` for the LLVM options validation.
+    ///
+    pub fn new_with_options(
+        target: era_compiler_common::Target,
+        optimizer_settings: &OptimizerSettings,
+        llvm_options: &[String],
+        options: &Options,
+    ) -> anyhow::Result<Self> {
+        Self::parse_llvm_options(target, llvm_options)?;
 
         let target_machine = inkwell::targets::Target::from_name(target.to_string().as_str())
             .ok_or_else(|| anyhow::anyhow!("LLVM target machine `{target}` not found"))?
             .create_target_machine(
                 &inkwell::targets::TargetTriple::create(target.triple()),
-                "",
-                "",
+                options.cpu.as_str(),
+                options.features_string().as_str(),
                 optimizer_settings.level_back_end,
-                inkwell::targets::RelocMode::Default,
-                inkwell::targets::CodeModel::Default,
+                options.reloc_mode.into(),
+                options.code_model.into(),
             )
             .ok_or_else(|| {
                 anyhow::anyhow!("LLVM target machine `{target}` initialization error")
@@ -66,6 +95,34 @@ impl TargetMachine {
         })
     }
 
+    ///
+    /// Returns the target machine builder.
+    ///
+    pub fn builder(
+        target: era_compiler_common::Target,
+        optimizer_settings: &OptimizerSettings,
+    ) -> Builder {
+        Builder::new(target, optimizer_settings)
+    }
+
+    ///
+    /// Validates and applies the process-wide LLVM options.
+    ///
+    pub fn parse_llvm_options(
+        target: era_compiler_common::Target,
+        llvm_options: &[String],
+    ) -> anyhow::Result<()> {
+        let llvm_options = LLVMOptions::try_from_arguments(target, llvm_options)?.to_arguments();
+        let mut arguments = Vec::with_capacity(1 + llvm_options.len());
+        arguments.push(target.to_string());
+        arguments.extend_from_slice(llvm_options.as_slice());
+        if arguments.len() > 1 {
+            let arguments: Vec<&str> = arguments.iter().map(|argument| argument.as_str()).collect();
+            inkwell::support::parse_command_line_options(arguments.as_slice(), "LLVM options");
+        }
+        Ok(())
+    }
+
     ///
     /// Sets the target-specific data in the module.
     ///
@@ -74,6 +131,37 @@ impl TargetMachine {
         module.set_data_layout(&self.target_machine.get_target_data().get_data_layout());
     }
 
+    ///
+    /// Checks whether the target-specific data in the module matches the target machine.
+    ///
+    /// The module with the target data not set yet is considered matching.
+    ///
+    pub fn check_target_data(&self, module: &inkwell::module::Module) -> anyhow::Result<()> {
+        let triple = module.get_triple();
+        let expected_triple = self.target_machine.get_triple();
+        if !triple.as_str().to_bytes().is_empty() && triple != expected_triple {
+            anyhow::bail!(
+                "target triple mismatch: expected `{}`, found `{}`",
+                expected_triple.as_str().to_string_lossy(),
+                triple.as_str().to_string_lossy(),
+            );
+        }
+
+        let data_layout = module.get_data_layout();
+        let expected_data_layout = self.target_machine.get_target_data().get_data_layout();
+        if !data_layout.as_str().to_bytes().is_empty()
+            && data_layout.as_str() != expected_data_layout.as_str()
+        {
+            anyhow::bail!(
+                "target data layout mismatch: expected `{}`, found `{}`",
+                expected_data_layout.as_str().to_string_lossy(),
+                data_layout.as_str().to_string_lossy(),
+            );
+        }
+
+        Ok(())
+    }
+
     ///
     /// Translates textual assembly to the object code.
     ///
@@ -90,10 +178,9 @@ impl TargetMachine {
     pub fn disassemble(
         &self,
         memory_buffer: &inkwell::memory_buffer::MemoryBuffer,
-        pc: u64,
-        options: u64,
+        options: &EraVMDisassemblerOptions,
     ) -> Result<inkwell::memory_buffer::MemoryBuffer, inkwell::support::LLVMString> {
-        memory_buffer.disassemble_eravm(&self.target_machine, pc, options)
+        memory_buffer.disassemble_eravm(&self.target_machine, options.pc, options.mode)
     }
 
     ///
@@ -0,0 +1,69 @@
+//!
+//! The LLVM target machine options.
+//!
+
+use super::code_model::CodeModel;
+use super::reloc_mode::RelocMode;
+
+///
+/// The LLVM target machine options.
+///
+/// Selects the target CPU, e.g. an EraVM protocol version or an EVM hard fork, and the
+/// target feature string, so one compiler binary can produce code for several of them.
+/// Also sets the relocation mode and code model.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Options {
+    /// The target CPU name. Empty means the target default.
+    pub cpu: String,
+    /// The enabled and disabled target features, e.g. `+feature` and `-feature`.
+    pub features: Vec<String>,
+    /// The relocation mode.
+    #[serde(default)]
+    pub reloc_mode: RelocMode,
+    /// The code model.
+    #[serde(default)]
+    pub code_model: CodeModel,
+}
+
+impl Options {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(cpu: String, features: Vec<String>) -> Self {
+        Self {
+            cpu,
+            features,
+            reloc_mode: RelocMode::default(),
+            code_model: CodeModel::default(),
+        }
+    }
+
+    ///
+    /// Sets the target CPU name.
+    ///
+    pub fn with_cpu(mut self, cpu: &str) -> Self {
+        self.cpu = cpu.to_owned();
+        self
+    }
+
+    ///
+    /// Enables or disables the target `feature`.
+    ///
+    /// The latest setting of a feature takes precedence.
+    ///
+    pub fn with_feature(mut self, feature: &str, is_enabled: bool) -> Self {
+        self.features
+            .retain(|existing| existing.trim_start_matches(['+', '-']) != feature);
+        self.features
+            .push(format!("{}{feature}", if is_enabled { '+' } else { '-' }));
+        self
+    }
+
+    ///
+    /// Returns the LLVM target feature string, e.g. `+a,-b`.
+    ///
+    pub fn features_string(&self) -> String {
+        self.features.join(",")
+    }
+}
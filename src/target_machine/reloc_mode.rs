@@ -0,0 +1,47 @@
+//!
+//! The LLVM target machine relocation mode.
+//!
+
+///
+/// The LLVM target machine relocation mode.
+///
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelocMode {
+    /// The target default.
+    #[default]
+    Default,
+    /// The non-relocatable code.
+    Static,
+    /// The position-independent code.
+    Pic,
+    /// The relocatable code with non-PIC external references.
+    DynamicNoPic,
+}
+
+impl From<RelocMode> for inkwell::targets::RelocMode {
+    fn from(value: RelocMode) -> Self {
+        match value {
+            RelocMode::Default => Self::Default,
+            RelocMode::Static => Self::Static,
+            RelocMode::Pic => Self::PIC,
+            RelocMode::DynamicNoPic => Self::DynamicNoPic,
+        }
+    }
+}
+
+impl std::str::FromStr for RelocMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "default" => Ok(Self::Default),
+            "static" => Ok(Self::Static),
+            "pic" => Ok(Self::Pic),
+            "dynamic-no-pic" => Ok(Self::DynamicNoPic),
+            value => anyhow::bail!("Unknown relocation mode `{value}`"),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//!
+//! The target machine tests.
+//!
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::target_machine::code_model::CodeModel as TargetCodeModel;
+use crate::target_machine::TargetMachine;
+
+#[test]
+pub fn check_target_machine_builder() {
+    crate::eravm::initialize_target();
+
+    let target_machine = TargetMachine::builder(
+        era_compiler_common::Target::EraVM,
+        &OptimizerSettings::cycles(),
+    )
+    .with_reloc_mode("static".parse().expect("Always valid"))
+    .with_code_model(TargetCodeModel::Small)
+    .build()
+    .expect("Failed to create target machine");
+    let default = TargetMachine::new(
+        era_compiler_common::Target::EraVM,
+        &OptimizerSettings::cycles(),
+        &[],
+    )
+    .expect("Failed to create target machine");
+    assert_eq!(target_machine.get_triple(), default.get_triple());
+
+    assert!("huge".parse::<TargetCodeModel>().is_err());
+}
@@ -0,0 +1,120 @@
+//!
+//! The thread-safe LLVM target machine pool.
+//!
+
+pub mod pooled;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::target_machine::options::Options as TargetOptions;
+use crate::target_machine::TargetMachine;
+
+use self::pooled::Pooled;
+
+///
+/// The target machine pool key: the target, optimizer settings, LLVM options, and target options.
+///
+pub type Key = (String, String, Vec<String>, TargetOptions);
+
+///
+/// The thread-safe LLVM target machine pool.
+///
+/// Hands out idle target machines created with the same target, optimizer settings,
+/// LLVM options, and target options, so compiling many small contracts does not create a machine per build.
+/// Each machine is used by one build at a time and is returned to the pool when dropped.
+///
+#[derive(Debug, Default)]
+pub struct TargetMachinePool {
+    /// The idle target machines.
+    idle: Mutex<HashMap<Key, Vec<TargetMachine>>>,
+}
+
+impl TargetMachinePool {
+    /// The maximum number of idle machines kept per key.
+    pub const IDLE_MACHINES_LIMIT: usize = 64;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Returns the process-wide pool used by the contexts.
+    ///
+    pub fn global() -> &'static Self {
+        static POOL: OnceLock<TargetMachinePool> = OnceLock::new();
+        POOL.get_or_init(Self::new)
+    }
+
+    ///
+    /// Returns an idle target machine or creates a new one.
+    ///
+    /// As the LLVM options are process-wide, they are re-applied when an idle machine is reused.
+    ///
+    pub fn get(
+        &self,
+        target: era_compiler_common::Target,
+        optimizer_settings: &OptimizerSettings,
+        llvm_options: &[String],
+        target_options: &TargetOptions,
+    ) -> anyhow::Result<Pooled<'_>> {
+        let key = (
+            target.to_string(),
+            format!("{optimizer_settings:?}"),
+            llvm_options.to_vec(),
+            target_options.to_owned(),
+        );
+        let idle = self
+            .idle
+            .lock()
+            .expect("Sync")
+            .get_mut(&key)
+            .and_then(Vec::pop);
+        let target_machine = match idle {
+            Some(target_machine) => {
+                TargetMachine::parse_llvm_options(target, llvm_options)?;
+                target_machine
+            }
+            None => TargetMachine::new_with_options(
+                target,
+                optimizer_settings,
+                llvm_options,
+                target_options,
+            )?,
+        };
+        Ok(Pooled::new(self, key, target_machine))
+    }
+
+    ///
+    /// Returns the number of idle machines in the pool.
+    ///
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().expect("Sync").values().map(Vec::len).sum()
+    }
+
+    ///
+    /// Drops all idle machines.
+    ///
+    pub fn clear(&self) {
+        self.idle.lock().expect("Sync").clear();
+    }
+
+    ///
+    /// Returns `target_machine` to the pool, unless there are enough idle machines with `key`.
+    ///
+    fn release(&self, key: Key, target_machine: TargetMachine) {
+        let mut idle = self.idle.lock().expect("Sync");
+        let machines = idle.entry(key).or_default();
+        if machines.len() < Self::IDLE_MACHINES_LIMIT {
+            machines.push(target_machine);
+        }
+    }
+}
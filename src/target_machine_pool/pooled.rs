@@ -0,0 +1,53 @@
+//!
+//! The target machine borrowed from the pool.
+//!
+
+use crate::target_machine::TargetMachine;
+
+use super::Key;
+use super::TargetMachinePool;
+
+///
+/// The target machine borrowed from the pool.
+///
+/// Dereferences to the target machine and returns it to the pool when dropped.
+///
+#[derive(Debug)]
+pub struct Pooled<'pool> {
+    /// The pool to return the machine to.
+    pool: &'pool TargetMachinePool,
+    /// The pool key.
+    key: Key,
+    /// The target machine. Only `None` while being returned to the pool.
+    target_machine: Option<TargetMachine>,
+}
+
+impl<'pool> Pooled<'pool> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(pool: &'pool TargetMachinePool, key: Key, target_machine: TargetMachine) -> Self {
+        Self {
+            pool,
+            key,
+            target_machine: Some(target_machine),
+        }
+    }
+}
+
+impl std::ops::Deref for Pooled<'_> {
+    type Target = TargetMachine;
+
+    fn deref(&self) -> &Self::Target {
+        self.target_machine.as_ref().expect("Always exists")
+    }
+}
+
+impl Drop for Pooled<'_> {
+    fn drop(&mut self) {
+        if let Some(target_machine) = self.target_machine.take() {
+            self.pool
+                .release(std::mem::take(&mut self.key), target_machine);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//!
+//! The target machine pool tests.
+//!
+
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::target_machine::options::Options as TargetOptions;
+use crate::target_machine_pool::TargetMachinePool;
+
+#[test]
+pub fn check_target_machine_pool() {
+    crate::eravm::initialize_target();
+
+    let pool = TargetMachinePool::new();
+    let settings = OptimizerSettings::cycles();
+    {
+        let first = pool
+            .get(
+                era_compiler_common::Target::EraVM,
+                &settings,
+                &[],
+                &TargetOptions::default(),
+            )
+            .expect("Failed to create target machine");
+        let second = pool
+            .get(
+                era_compiler_common::Target::EraVM,
+                &settings,
+                &[],
+                &TargetOptions::default(),
+            )
+            .expect("Failed to create target machine");
+        assert_eq!(first.get_triple(), second.get_triple());
+        assert_eq!(pool.idle_count(), 0);
+    }
+    assert_eq!(pool.idle_count(), 2);
+
+    let reused = pool
+        .get(
+            era_compiler_common::Target::EraVM,
+            &settings,
+            &[],
+            &TargetOptions::default(),
+        )
+        .expect("Failed to reuse target machine");
+    assert_eq!(pool.idle_count(), 1);
+    drop(reused);
+
+    let _other = pool
+        .get(
+            era_compiler_common::Target::EraVM,
+            &OptimizerSettings::size(),
+            &[],
+            &TargetOptions::default(),
+        )
+        .expect("Failed to create target machine");
+    assert_eq!(pool.idle_count(), 2);
+
+    pool.clear();
+    assert_eq!(pool.idle_count(), 0);
+}
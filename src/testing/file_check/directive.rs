@@ -0,0 +1,67 @@
+//!
+//! The FileCheck-style directive.
+//!
+
+use super::pattern::Pattern;
+
+///
+/// The FileCheck-style directive.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Directive {
+    /// The pattern must match at or after the previous match.
+    Check(Pattern),
+    /// The pattern must match on the line following the previous match.
+    Next(Pattern),
+    /// The pattern must match on the same line as the previous match.
+    Same(Pattern),
+    /// The pattern must not match between the surrounding matches.
+    Not(Pattern),
+}
+
+impl Directive {
+    ///
+    /// Parses a directive from `line`, e.g. `; CHECK-NEXT: ret void` with `prefix` `CHECK`.
+    ///
+    /// Returns `None` if the line contains no directive.
+    ///
+    pub fn parse(line: &str, prefix: &str) -> Option<anyhow::Result<Self>> {
+        let start = line.find(prefix)?;
+        let line = &line[start + prefix.len()..];
+        let (suffix, pattern) = line.split_once(':')?;
+        if !suffix.is_empty() && !suffix.starts_with('-') {
+            return None;
+        }
+        let result = Pattern::new(pattern).and_then(|pattern| match suffix {
+            "" => Ok(Self::Check(pattern)),
+            "-NEXT" => Ok(Self::Next(pattern)),
+            "-SAME" => Ok(Self::Same(pattern)),
+            "-NOT" => Ok(Self::Not(pattern)),
+            suffix => anyhow::bail!("Unknown directive `{prefix}{suffix}`"),
+        });
+        Some(result)
+    }
+
+    ///
+    /// Returns the directive pattern.
+    ///
+    pub fn pattern(&self) -> &Pattern {
+        match self {
+            Self::Check(pattern) => pattern,
+            Self::Next(pattern) => pattern,
+            Self::Same(pattern) => pattern,
+            Self::Not(pattern) => pattern,
+        }
+    }
+}
+
+impl std::fmt::Display for Directive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Check(pattern) => write!(f, "CHECK: {pattern}"),
+            Self::Next(pattern) => write!(f, "CHECK-NEXT: {pattern}"),
+            Self::Same(pattern) => write!(f, "CHECK-SAME: {pattern}"),
+            Self::Not(pattern) => write!(f, "CHECK-NOT: {pattern}"),
+        }
+    }
+}
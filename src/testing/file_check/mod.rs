@@ -0,0 +1,266 @@
+//!
+//! The FileCheck-style IR pattern matcher.
+//!
+
+pub mod directive;
+pub mod pattern;
+
+#[cfg(test)]
+mod tests;
+
+use self::directive::Directive;
+use self::pattern::Pattern;
+
+///
+/// The FileCheck-style IR pattern matcher.
+///
+/// Supports the `CHECK`, `CHECK-NEXT`, `CHECK-SAME`, and `CHECK-NOT` directives.
+/// On mismatch, the error contains the annotated input pointing at the scan start,
+/// the most similar line, or the forbidden match.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileCheck {
+    /// The directives in the order of matching.
+    directives: Vec<Directive>,
+}
+
+///
+/// The input position right after the last match.
+///
+#[derive(Debug, Default, Clone, Copy)]
+struct Cursor {
+    /// The line index.
+    line: usize,
+    /// The byte offset within the line.
+    column: usize,
+}
+
+impl FileCheck {
+    /// The default directive prefix.
+    pub const DEFAULT_PREFIX: &'static str = "CHECK";
+
+    /// The number of input lines shown around the failure point.
+    const CONTEXT_LINES: usize = 8;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Parses the directives from `text`, e.g. `; CHECK: call` lines of an annotated test.
+    ///
+    /// Lines without the `prefix` are ignored.
+    ///
+    pub fn parse(text: &str, prefix: &str) -> anyhow::Result<Self> {
+        let directives = text
+            .lines()
+            .filter_map(|line| Directive::parse(line, prefix))
+            .collect::<anyhow::Result<Vec<Directive>>>()?;
+        Ok(Self { directives })
+    }
+
+    ///
+    /// Appends a `CHECK` directive.
+    ///
+    pub fn check(mut self, pattern: &str) -> anyhow::Result<Self> {
+        self.directives
+            .push(Directive::Check(Pattern::new(pattern)?));
+        Ok(self)
+    }
+
+    ///
+    /// Appends a `CHECK-NEXT` directive.
+    ///
+    pub fn check_next(mut self, pattern: &str) -> anyhow::Result<Self> {
+        self.directives
+            .push(Directive::Next(Pattern::new(pattern)?));
+        Ok(self)
+    }
+
+    ///
+    /// Appends a `CHECK-SAME` directive.
+    ///
+    pub fn check_same(mut self, pattern: &str) -> anyhow::Result<Self> {
+        self.directives
+            .push(Directive::Same(Pattern::new(pattern)?));
+        Ok(self)
+    }
+
+    ///
+    /// Appends a `CHECK-NOT` directive.
+    ///
+    pub fn check_not(mut self, pattern: &str) -> anyhow::Result<Self> {
+        self.directives.push(Directive::Not(Pattern::new(pattern)?));
+        Ok(self)
+    }
+
+    ///
+    /// Matches the directives against `input`.
+    ///
+    pub fn run(&self, input: &str) -> anyhow::Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+
+        let mut cursor = Cursor::default();
+        let mut is_matched = false;
+        let mut forbidden: Vec<&Directive> = Vec::new();
+        for directive in self.directives.iter() {
+            let found = match directive {
+                Directive::Not(_) => {
+                    forbidden.push(directive);
+                    continue;
+                }
+                Directive::Check(pattern) => (cursor.line..lines.len()).find_map(|index| {
+                    let start = if index == cursor.line {
+                        cursor.column
+                    } else {
+                        0
+                    };
+                    pattern
+                        .find(&lines[index][start..])
+                        .map(|(match_start, end)| (index, start + match_start, start + end))
+                }),
+                Directive::Next(pattern) => {
+                    if !is_matched {
+                        anyhow::bail!("`{directive}` cannot be the first positive directive");
+                    }
+                    let index = cursor.line + 1;
+                    lines
+                        .get(index)
+                        .and_then(|line| pattern.find(line))
+                        .map(|(start, end)| (index, start, end))
+                }
+                Directive::Same(pattern) => {
+                    if !is_matched {
+                        anyhow::bail!("`{directive}` cannot be the first positive directive");
+                    }
+                    lines
+                        .get(cursor.line)
+                        .and_then(|line| pattern.find(&line[cursor.column..]))
+                        .map(|(start, end)| {
+                            (cursor.line, cursor.column + start, cursor.column + end)
+                        })
+                }
+            };
+            let (line, start, end) = match found {
+                Some(found) => found,
+                None => return Err(Self::mismatch(directive, lines.as_slice(), cursor)),
+            };
+
+            Self::check_forbidden(
+                forbidden.as_slice(),
+                lines.as_slice(),
+                cursor,
+                Cursor {
+                    line,
+                    column: start,
+                },
+            )?;
+            forbidden.clear();
+            cursor = Cursor { line, column: end };
+            is_matched = true;
+        }
+        Self::check_forbidden(
+            forbidden.as_slice(),
+            lines.as_slice(),
+            cursor,
+            Cursor {
+                line: lines.len(),
+                column: 0,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    ///
+    /// Checks that the `CHECK-NOT` patterns do not match between `from` and `to`.
+    ///
+    fn check_forbidden(
+        forbidden: &[&Directive],
+        lines: &[&str],
+        from: Cursor,
+        to: Cursor,
+    ) -> anyhow::Result<()> {
+        for directive in forbidden.iter() {
+            for index in from.line..=to.line {
+                let Some(line) = lines.get(index) else {
+                    break;
+                };
+                let start = if index == from.line { from.column } else { 0 };
+                let end = if index == to.line {
+                    to.column
+                } else {
+                    line.len()
+                };
+                if start > end {
+                    continue;
+                }
+                if directive.pattern().find(&line[start..end]).is_some() {
+                    anyhow::bail!(
+                        "`{directive}` matched forbidden text\n{}",
+                        Self::annotate(lines, &[(from.line, '>'), (index, '!')])
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Builds the mismatch error of `directive`, pointing at the scan start and the closest line.
+    ///
+    fn mismatch(directive: &Directive, lines: &[&str], cursor: Cursor) -> anyhow::Error {
+        let closest = (cursor.line..lines.len())
+            .map(|index| (directive.pattern().similarity(lines[index]), index))
+            .filter(|(similarity, _)| *similarity > 0)
+            .max_by_key(|(similarity, index)| (*similarity, std::cmp::Reverse(*index)))
+            .map(|(_, index)| index);
+
+        let mut markers = vec![(cursor.line, '>')];
+        let mut message = format!("`{directive}` did not match\n");
+        if let Some(closest) = closest {
+            markers.push((closest, '?'));
+            message.push_str(format!("expected: {}\n", directive.pattern()).as_str());
+            message.push_str(format!("closest:  {}\n", lines[closest].trim()).as_str());
+        }
+        message.push_str(Self::annotate(lines, markers.as_slice()).as_str());
+        anyhow::anyhow!(message)
+    }
+
+    ///
+    /// Prints the input lines around `markers` with their numbers and marker characters.
+    ///
+    fn annotate(lines: &[&str], markers: &[(usize, char)]) -> String {
+        let first = markers
+            .iter()
+            .map(|(index, _)| *index)
+            .min()
+            .unwrap_or_default()
+            .saturating_sub(Self::CONTEXT_LINES);
+        let last = markers
+            .iter()
+            .map(|(index, _)| *index)
+            .max()
+            .unwrap_or_default()
+            .saturating_add(Self::CONTEXT_LINES)
+            .min(lines.len().saturating_sub(1));
+
+        let mut output = String::with_capacity(256);
+        for index in first..=last {
+            let Some(line) = lines.get(index) else {
+                break;
+            };
+            let marker = markers
+                .iter()
+                .rev()
+                .find(|(marker_index, _)| *marker_index == index)
+                .map(|(_, marker)| *marker)
+                .unwrap_or(' ');
+            output.push_str(format!("{marker} {:>5} | {line}\n", index + 1).as_str());
+        }
+        output
+    }
+}
@@ -0,0 +1,89 @@
+//!
+//! The FileCheck-style pattern.
+//!
+
+///
+/// The FileCheck-style pattern.
+///
+/// Matches a single line of input. The text is matched literally, except for `{{...}}` blocks
+/// which match any text. Regular expressions inside the blocks are not supported.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    /// The original pattern text.
+    text: String,
+    /// The literal fragments separated by wildcards.
+    fragments: Vec<String>,
+}
+
+impl Pattern {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(text: &str) -> anyhow::Result<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            anyhow::bail!("Empty pattern");
+        }
+
+        let mut fragments = Vec::with_capacity(1);
+        let mut remainder = text;
+        while let Some(start) = remainder.find("{{") {
+            let end = remainder[start..]
+                .find("}}")
+                .ok_or_else(|| anyhow::anyhow!("Unterminated wildcard in pattern `{text}`"))?;
+            fragments.push(remainder[..start].to_owned());
+            remainder = &remainder[start + end + 2..];
+        }
+        fragments.push(remainder.to_owned());
+
+        Ok(Self {
+            text: text.to_owned(),
+            fragments,
+        })
+    }
+
+    ///
+    /// Finds the first match in `line`, returning its start and end byte offsets.
+    ///
+    pub fn find(&self, line: &str) -> Option<(usize, usize)> {
+        let mut start = None;
+        let mut position = 0;
+        for fragment in self.fragments.iter() {
+            let offset = line[position..].find(fragment.as_str())?;
+            if start.is_none() {
+                start = Some(position + offset);
+            }
+            position += offset + fragment.len();
+        }
+        if self.fragments.len() > 1 && self.fragments.last().is_some_and(String::is_empty) {
+            position = line.len();
+        }
+        Some((start.unwrap_or_default(), position))
+    }
+
+    ///
+    /// Returns how many leading characters of the pattern are present in `line`.
+    ///
+    /// Is used to point at the most likely intended match on failure.
+    ///
+    pub fn similarity(&self, line: &str) -> usize {
+        let fragment = self
+            .fragments
+            .iter()
+            .find(|fragment| !fragment.is_empty())
+            .map(String::as_str)
+            .unwrap_or_default();
+        fragment
+            .char_indices()
+            .map(|(index, character)| index + character.len_utf8())
+            .take_while(|end| line.contains(&fragment[..*end]))
+            .count()
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
@@ -0,0 +1,76 @@
+//!
+//! The FileCheck matcher tests.
+//!
+
+use crate::context::IContext;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::testing::file_check::FileCheck;
+
+#[test]
+pub fn check_file_check() {
+    let input = "define i256 @test(i256 %0) {\nentry:\n  %1 = add i256 %0, 1\n  ret i256 %1\n}\n";
+
+    FileCheck::parse(
+        "; CHECK: define i256 @{{.*}}(\n; CHECK-SAME: i256 %0\n; CHECK-NOT: sub\n; CHECK: add\n; CHECK-NEXT: ret i256 %{{[0-9]+}}",
+        FileCheck::DEFAULT_PREFIX,
+    )
+    .expect("Always valid")
+    .run(input)
+    .expect("Must match");
+
+    let error = FileCheck::new()
+        .check("entry:")
+        .and_then(|file_check| file_check.check_next("ret i256 %1"))
+        .expect("Always valid")
+        .run(input)
+        .expect_err("Must not match")
+        .to_string();
+    assert!(
+        error.contains("`CHECK-NEXT: ret i256 %1` did not match"),
+        "{error}"
+    );
+    assert!(error.contains("?     4 |   ret i256 %1"), "{error}");
+
+    let error = FileCheck::new()
+        .check_not("add")
+        .and_then(|file_check| file_check.check("ret"))
+        .expect("Always valid")
+        .run(input)
+        .expect_err("Must not match")
+        .to_string();
+    assert!(error.contains("!     3 |   %1 = add i256 %0, 1"), "{error}");
+}
+
+#[test]
+pub fn check_file_check_translator() {
+    let llvm = inkwell::context::Context::create();
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    let function = crate::testing::add_function(&mut context, "addition", 2, 1)
+        .expect("Failed to add function");
+    let result = crate::evm::instructions::arithmetic::addition(
+        &mut context,
+        function
+            .get_nth_param(0)
+            .expect("Always exists")
+            .into_int_value(),
+        function
+            .get_nth_param(1)
+            .expect("Always exists")
+            .into_int_value(),
+    )
+    .expect("Failed to translate");
+    context
+        .build_return(Some(&result))
+        .expect("Failed to build return");
+
+    let file_check = FileCheck::new()
+        .check("define i256 @addition(i256 %0, i256 %1)")
+        .and_then(|file_check| file_check.check("%addition_result = add i256 %0, %1"))
+        .and_then(|file_check| file_check.check_next("ret i256 %addition_result"))
+        .expect("Always valid");
+    crate::testing::check_function(&context, "addition", &file_check).expect("Must match");
+}
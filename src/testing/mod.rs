@@ -0,0 +1,166 @@
+//!
+//! The LLVM context test harness.
+//!
+//! Spins up minimal EraVM and EVM contexts with a dummy function, and snapshots the resulting IR,
+//! so translators can be tested without the front-end boilerplate.
+//!
+
+pub mod file_check;
+
+#[cfg(test)]
+mod tests;
+
+use crate::context::IContext;
+use crate::dependency::DummyDependency;
+use crate::eravm::context::Context as EraVMContext;
+use crate::evm::context::Context as EVMContext;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+use crate::optimizer::Optimizer;
+
+use self::file_check::FileCheck;
+
+/// The default test module name.
+pub const MODULE_NAME: &str = "test";
+
+/// The default test function name.
+pub const FUNCTION_NAME: &str = "test";
+
+///
+/// Creates an EraVM context with an empty module.
+///
+pub fn create_eravm_context(
+    llvm: &inkwell::context::Context,
+    optimizer_settings: OptimizerSettings,
+) -> EraVMContext<DummyDependency> {
+    crate::eravm::initialize_target();
+
+    let module = llvm.create_module(MODULE_NAME);
+    let optimizer = Optimizer::new(optimizer_settings);
+
+    EraVMContext::<_>::new(llvm, module, vec![], optimizer, None)
+}
+
+///
+/// Creates an EVM context of `code_segment` with an empty module.
+///
+pub fn create_evm_context(
+    llvm: &inkwell::context::Context,
+    code_segment: era_compiler_common::CodeSegment,
+    optimizer_settings: OptimizerSettings,
+) -> EVMContext<DummyDependency> {
+    crate::evm::initialize_target();
+
+    let module = llvm.create_module(MODULE_NAME);
+    let optimizer = Optimizer::new(optimizer_settings);
+
+    EVMContext::<_>::new(llvm, module, vec![], code_segment, optimizer, None, None)
+}
+
+///
+/// Adds a function with `arguments_length` field arguments and `return_values_length` field
+/// return values, makes it current, and positions the builder at its entry block.
+///
+pub fn add_function<'ctx, C>(
+    context: &mut C,
+    name: &str,
+    arguments_length: usize,
+    return_values_length: usize,
+) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>>
+where
+    C: IContext<'ctx>,
+{
+    let argument_types: Vec<inkwell::types::BasicMetadataTypeEnum<'ctx>> =
+        vec![context.field_type().into(); arguments_length];
+    let r#type = match return_values_length {
+        0 => context
+            .void_type()
+            .fn_type(argument_types.as_slice(), false),
+        1 => context
+            .field_type()
+            .fn_type(argument_types.as_slice(), false),
+        size => context
+            .structure_type(vec![context.field_type(); size].as_slice())
+            .fn_type(argument_types.as_slice(), false),
+    };
+    context.add_function(
+        name,
+        r#type,
+        return_values_length,
+        Some(inkwell::module::Linkage::External),
+    )?;
+    context.set_current_function(name)?;
+
+    let value = context
+        .module()
+        .get_function(name)
+        .ok_or_else(|| anyhow::anyhow!("Function `{name}` is not declared"))?;
+    let entry_block = value
+        .get_first_basic_block()
+        .ok_or_else(|| anyhow::anyhow!("Function `{name}` has no entry block"))?;
+    context.set_basic_block(entry_block);
+    Ok(value)
+}
+
+///
+/// Adds the default test function without arguments and return values.
+///
+/// See `add_function` for details.
+///
+pub fn add_dummy_function<'ctx, C>(
+    context: &mut C,
+) -> anyhow::Result<inkwell::values::FunctionValue<'ctx>>
+where
+    C: IContext<'ctx>,
+{
+    add_function(context, FUNCTION_NAME, 0, 0)
+}
+
+///
+/// Returns the printed IR of the whole module.
+///
+pub fn snapshot_module<'ctx, C>(context: &C) -> String
+where
+    C: IContext<'ctx>,
+{
+    context.module().print_to_string().to_string()
+}
+
+///
+/// Returns the printed IR of function `name`.
+///
+pub fn snapshot_function<'ctx, C>(context: &C, name: &str) -> anyhow::Result<String>
+where
+    C: IContext<'ctx>,
+{
+    let value = context
+        .module()
+        .get_function(name)
+        .ok_or_else(|| anyhow::anyhow!("Function `{name}` is not declared"))?;
+    Ok(value.print_to_string().to_string())
+}
+
+///
+/// Matches `file_check` against the printed IR of the whole module.
+///
+pub fn check_module<'ctx, C>(context: &C, file_check: &FileCheck) -> anyhow::Result<()>
+where
+    C: IContext<'ctx>,
+{
+    file_check.run(snapshot_module(context).as_str())
+}
+
+///
+/// Matches `file_check` against the printed IR of function `name`.
+///
+pub fn check_function<'ctx, C>(
+    context: &C,
+    name: &str,
+    file_check: &FileCheck,
+) -> anyhow::Result<()>
+where
+    C: IContext<'ctx>,
+{
+    file_check
+        .run(snapshot_function(context, name)?.as_str())
+        .map_err(|error| anyhow::anyhow!("Function `{name}`: {error}"))
+}
@@ -0,0 +1,31 @@
+//!
+//! The testing harness tests.
+//!
+
+use crate::context::IContext;
+use crate::optimizer::settings::Settings as OptimizerSettings;
+
+#[test]
+pub fn check_testing_harness() {
+    let llvm = inkwell::context::Context::create();
+
+    let mut context = crate::testing::create_eravm_context(&llvm, OptimizerSettings::none());
+    crate::testing::add_function(&mut context, "eravm", 2, 1).expect("Failed to add function");
+    context
+        .build_unreachable()
+        .expect("Failed to build unreachable");
+    let snapshot = crate::testing::snapshot_function(&context, "eravm").expect("Always exists");
+    assert!(snapshot.contains("define i256 @eravm(i256"), "{snapshot}");
+    assert!(crate::testing::snapshot_module(&context).contains(&snapshot));
+
+    let mut context = crate::testing::create_evm_context(
+        &llvm,
+        era_compiler_common::CodeSegment::Runtime,
+        OptimizerSettings::none(),
+    );
+    crate::testing::add_dummy_function(&mut context).expect("Failed to add function");
+    context.build_return(None).expect("Failed to build return");
+    let snapshot = crate::testing::snapshot_function(&context, crate::testing::FUNCTION_NAME)
+        .expect("Always exists");
+    assert!(snapshot.contains("ret void"), "{snapshot}");
+}
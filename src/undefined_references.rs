@@ -0,0 +1,77 @@
+//!
+//! The undefined references of an unlinked object.
+//!
+
+use std::collections::BTreeSet;
+
+///
+/// The undefined references of an unlinked object.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UndefinedReferences {
+    /// The library placeholders to be resolved with linker symbols.
+    pub linker_symbols: BTreeSet<String>,
+    /// The factory dependency placeholders to be resolved with bytecode hashes.
+    pub factory_dependencies: BTreeSet<String>,
+}
+
+impl UndefinedReferences {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(linker_symbols: Vec<String>, factory_dependencies: Vec<String>) -> Self {
+        Self {
+            linker_symbols: linker_symbols.into_iter().collect(),
+            factory_dependencies: factory_dependencies.into_iter().collect(),
+        }
+    }
+
+    ///
+    /// Whether there are no undefined references left.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.linker_symbols.is_empty() && self.factory_dependencies.is_empty()
+    }
+
+    ///
+    /// Returns all undefined references.
+    ///
+    pub fn all(&self) -> Vec<String> {
+        self.linker_symbols
+            .iter()
+            .chain(self.factory_dependencies.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UndefinedReferences;
+
+    #[test]
+    pub fn check_undefined_references() {
+        let references = UndefinedReferences::new(
+            vec!["library.sol:Library".to_owned(), "a.sol:A".to_owned()],
+            vec![
+                "factory.sol:Factory".to_owned(),
+                "factory.sol:Factory".to_owned(),
+            ],
+        );
+        assert!(!references.is_empty());
+        assert_eq!(
+            references.all(),
+            vec![
+                "a.sol:A".to_owned(),
+                "library.sol:Library".to_owned(),
+                "factory.sol:Factory".to_owned(),
+            ]
+        );
+
+        let bytecode_buffer = inkwell::memory_buffer::MemoryBuffer::create_from_memory_range_copy(
+            &[0u8; era_compiler_common::BYTE_LENGTH_FIELD],
+            "bytecode_buffer",
+        );
+        assert!(crate::eravm::get_undefined_references(&bytecode_buffer).is_empty());
+    }
+}